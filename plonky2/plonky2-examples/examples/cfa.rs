@@ -0,0 +1,219 @@
+use log::{info, Level, LevelFilter};
+use plonky2::hash::hash_types::{HashOut, HashOutTarget};
+use plonky2::hash::merkle_tree::MerkleTree;
+use plonky2::hash::poseidon::PoseidonHash;
+use plonky2::iop::target::Target;
+use plonky2::iop::witness::{PartialWitness, WitnessWrite};
+use plonky2::plonk::circuit_builder::CircuitBuilder;
+use plonky2::plonk::circuit_data::CircuitConfig;
+use plonky2::plonk::config::{GenericConfig, Hasher, PoseidonGoldilocksConfig};
+use plonky2::plonk::prover::prove;
+use plonky2::util::timing::TimingTree;
+use plonky2_field::types::{Field, Sample};
+use rand::{thread_rng, Rng};
+
+use anyhow::Result;
+use env_logger::Builder;
+
+// Circuit setup - leave as is
+const D: usize = 2;
+type C = PoseidonGoldilocksConfig;
+type F = <C as GenericConfig<D>>::F;
+
+// the control-flow graph holds at most 2^CFG_LOG_EDGES edges
+const CFG_LOG_EDGES: usize = 4;
+// number of branches recorded in a trace
+const TRACE_LEN: usize = 32;
+// every trace starts at the entry block
+const ENTRY_BLOCK: u64 = 0;
+
+// control-flow graph of the attested program as (source block, destination block) edges
+const CFG: [(u64, u64); 11] = [
+    (0, 1),
+    (1, 2),
+    (1, 3),
+    (2, 4),
+    (3, 4),
+    (4, 1),
+    (4, 5),
+    (5, 6),
+    (5, 7),
+    (6, 7),
+    (7, 0),
+];
+
+// CFG edges padded to a power of two by repeating the last edge
+fn cfg_edges() -> Vec<(F, F)> {
+    let mut edges: Vec<(F, F)> = CFG
+        .iter()
+        .map(|&(src, dst)| (F::from_canonical_u64(src), F::from_canonical_u64(dst)))
+        .collect();
+    edges.resize(1 << CFG_LOG_EDGES, edges[CFG.len() - 1]);
+    edges
+}
+
+// public commitment to the CFG
+fn cfg_root(edges: &[(F, F)]) -> HashOut<F> {
+    let leaves = edges.iter().map(|&(src, dst)| vec![src, dst]).collect();
+    MerkleTree::<F, PoseidonHash>::new(leaves, 0).cap.0[0]
+}
+
+// the device folds every taken branch into a hash chain seeded by the challenge
+fn trace_hash(chall: F, trace: &[F]) -> HashOut<F> {
+    let mut h = PoseidonHash::hash_no_pad(&[chall]);
+    for &block in trace {
+        let mut preimage = h.elements.to_vec();
+        preimage.push(block);
+        h = PoseidonHash::hash_no_pad(&preimage);
+    }
+    h
+}
+
+// random execution of the program, returns the visited blocks and the edge taken at each step
+fn execute(edges: &[(F, F)]) -> (Vec<F>, Vec<usize>) {
+    let mut rng = thread_rng();
+    let mut trace = vec![F::from_canonical_u64(ENTRY_BLOCK)];
+    let mut taken = Vec::with_capacity(TRACE_LEN);
+    for _ in 0..TRACE_LEN {
+        let current = trace[trace.len() - 1];
+        let candidates: Vec<usize> = (0..CFG.len()).filter(|&i| edges[i].0 == current).collect();
+        let edge = candidates[rng.gen_range(0..candidates.len())];
+        taken.push(edge);
+        trace.push(edges[edge].1);
+    }
+    (trace, taken)
+}
+
+// Recomputes the CFG root from its edges and checks that every consecutive pair of blocks in
+// the trace is one of them. Returns the hash chain over the trace.
+fn cfa_circuit(
+    builder: &mut CircuitBuilder<F, D>,
+    chall: Target,
+    cfg_root: HashOutTarget,
+    srcs: &[Target],
+    dsts: &[Target],
+    trace: &[Target],
+    taken: &[Target],
+) -> HashOutTarget {
+    assert_eq!(trace.len(), taken.len() + 1);
+
+    let mut layer: Vec<HashOutTarget> = srcs
+        .iter()
+        .zip(dsts.iter())
+        .map(|(&src, &dst)| builder.hash_or_noop::<PoseidonHash>(vec![src, dst]))
+        .collect();
+    while layer.len() > 1 {
+        layer = layer
+            .chunks(2)
+            .map(|pair| {
+                let mut preimage = pair[0].elements.to_vec();
+                preimage.extend(pair[1].elements);
+                builder.hash_n_to_hash_no_pad::<PoseidonHash>(preimage)
+            })
+            .collect();
+    }
+    builder.connect_hashes(layer[0], cfg_root);
+
+    let entry = builder.constant(F::from_canonical_u64(ENTRY_BLOCK));
+    builder.connect(trace[0], entry);
+    for (i, &edge) in taken.iter().enumerate() {
+        let src = builder.random_access(edge, srcs.to_vec());
+        let dst = builder.random_access(edge, dsts.to_vec());
+        builder.connect(src, trace[i]);
+        builder.connect(dst, trace[i + 1]);
+    }
+
+    let mut h = builder.hash_n_to_hash_no_pad::<PoseidonHash>(vec![chall]);
+    for &block in &trace[1..] {
+        let mut preimage = h.elements.to_vec();
+        preimage.push(block);
+        h = builder.hash_n_to_hash_no_pad::<PoseidonHash>(preimage);
+    }
+    h
+}
+
+// Proves that the reported trace hash belongs to a valid path through the committed CFG,
+// without revealing the path itself.
+fn cfa_proof(
+    config: &CircuitConfig,
+    edges: &[(F, F)],
+    root: HashOut<F>,
+    chall: F,
+    trace: &[F],
+    taken: &[usize],
+    rsp: HashOut<F>,
+) -> Result<()> {
+    let mut builder = CircuitBuilder::<F, D>::new(config.clone());
+
+    let chall_tgt = builder.add_virtual_target();
+    builder.register_public_input(chall_tgt);
+    let root_tgt = builder.add_virtual_hash_public_input();
+    let rsp_tgt = builder.add_virtual_hash_public_input();
+
+    let srcs_tgt = builder.add_virtual_targets(edges.len());
+    let dsts_tgt = builder.add_virtual_targets(edges.len());
+    let trace_tgt = builder.add_virtual_targets(TRACE_LEN + 1);
+    let taken_tgt = builder.add_virtual_targets(TRACE_LEN);
+
+    let h = cfa_circuit(&mut builder, chall_tgt, root_tgt, &srcs_tgt, &dsts_tgt, &trace_tgt, &taken_tgt);
+    builder.connect_hashes(h, rsp_tgt);
+
+    let mut pw = PartialWitness::new();
+    pw.set_target(chall_tgt, chall)?;
+    pw.set_hash_target(root_tgt, root)?;
+    pw.set_hash_target(rsp_tgt, rsp)?;
+    for (i, &(src, dst)) in edges.iter().enumerate() {
+        pw.set_target(srcs_tgt[i], src)?;
+        pw.set_target(dsts_tgt[i], dst)?;
+    }
+    for (tgt, block) in trace_tgt.iter().zip(trace.iter()) {
+        pw.set_target(*tgt, *block)?;
+    }
+    for (tgt, edge) in taken_tgt.iter().zip(taken.iter()) {
+        pw.set_target(*tgt, F::from_canonical_usize(*edge))?;
+    }
+
+    let mut timing = TimingTree::new("build circuit", Level::Info);
+    let data = builder.build::<C>();
+    timing.print();
+
+    timing = TimingTree::new("prove", Level::Info);
+    let proof = prove::<F, C, D>(&data.prover_only, &data.common, pw, &mut timing)?;
+    timing.print();
+
+    timing = TimingTree::new("Verify", Level::Info);
+    data.verify(proof.clone())?;
+    timing.print();
+
+    println!("Successfully generated CFA proof for challenge: {:?} with trace hash: {:?}", proof.public_inputs[0], &proof.public_inputs[5..9]);
+
+    Ok(())
+}
+
+fn main() -> Result<()> {
+    // enable logging
+    Builder::new()
+        .filter_level(LevelFilter::Info)
+        .init();
+    info!("Starting program");
+
+    let mut rng = thread_rng();
+    let edges = cfg_edges();
+    let root = cfg_root(&edges);
+    let chall = F::sample(&mut rng);
+
+    // the device runs the program and reports the hash over its branch trace
+    let (trace, taken) = execute(&edges);
+    let rsp = trace_hash(chall, &trace[1..]);
+
+    let config = CircuitConfig::standard_recursion_zk_config();
+    cfa_proof(&config, &edges, root, chall, &trace, &taken, rsp)?;
+
+    // a hijacked control flow jumping 0 -> 7 is not an edge of the CFG
+    let mut hijacked = trace.clone();
+    hijacked[1] = F::from_canonical_u64(7);
+    let bad_rsp = trace_hash(chall, &hijacked[1..]);
+    assert!(cfa_proof(&config, &edges, root, chall, &hijacked, &taken, bad_rsp).is_err());
+
+    Ok(())
+}
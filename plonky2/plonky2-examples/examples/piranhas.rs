@@ -1,20 +1,44 @@
 use log::{info, Level};
-use plonky2::{hash::{hashing::hash_n_to_m_no_pad, poseidon::PoseidonPermutation}, iop::{target::{BoolTarget, Target}, witness::PartialWitness}, plonk::{circuit_builder::CircuitBuilder, circuit_data::CircuitConfig, config::{GenericConfig, PoseidonGoldilocksConfig}, prover::prove}, util::timing::TimingTree};
-use plonky2_ecdsa::gadgets::{nonnative::CircuitBuilderNonNative};
-use plonky2_ecgfp5::{curve::{curve::Point, scalar_field::Scalar}, gadgets::{base_field::{CircuitBuilderGFp5, QuinticExtensionTarget}, curve::{CircuitBuilderEcGFp5, CurveTarget}}};
-use plonky2_field::{extension::{quintic::QuinticExtension}, types::{Field, Sample}};
-use rand::{thread_rng};
-
-use anyhow::Result;
+use plonky2::{iop::{target::{BoolTarget, Target}, witness::PartialWitness}, plonk::{circuit_builder::CircuitBuilder, circuit_data::CircuitConfig, config::{GenericConfig, PoseidonGoldilocksConfig}, prover::prove}, util::timing::TimingTree};
+use plonky2_ecgfp5::schnorr::{CircuitBuilderSchnorr, PartialWitnessSchnorr, PublicKey, SchnorrSignatureTarget, SecretKey, Signature};
+use plonky2_ecgfp5::{curve::curve::Point, gadgets::{base_field::{CircuitBuilderGFp5, QuinticExtensionTarget}, curve::{CircuitBuilderEcGFp5, CurveTarget}}};
+use plonky2_ecgfp5::curve::scalar_field::Scalar;
+use plonky2_ecgfp5::elgamal::{self, message_to_point, Ciphertext, CircuitBuilderElGamal, PartialWitnessElGamal};
+use plonky2_ecgfp5::gadgets::scalar_field::CircuitBuilderScalar;
+use plonky2_ecgfp5::vrf::{self, CircuitBuilderVrf, PartialWitnessVrf};
+use plonky2_field::secp256k1_scalar::Secp256K1Scalar;
+use plonky2_field::types::{Field, PrimeField, PrimeField64, Sample};
+use rand::{thread_rng, RngCore};
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+use anyhow::{bail, ensure, Result};
 use log::{LevelFilter};
 use env_logger::Builder;
 use plonky2::iop::witness::{WitnessWrite};
 use plonky2::hash::poseidon::{PoseidonHash};
+use plonky2::hash::hash_types::{HashOut, HashOutTarget, MerkleCapTarget};
+use plonky2::hash::merkle_proofs::{MerkleProof, MerkleProofTarget};
+use plonky2::hash::merkle_tree::MerkleTree;
+use plonky2::plonk::config::Hasher;
+use plonky2_u32::gadgets::arithmetic_u32::{CircuitBuilderU32, U32Target};
+use plonky2_u32::gadgets::arithmetic_uint::CircuitBuilderUInt;
+use plonky2_u32::gadgets::multiple_comparison::list_le_u32_circuit;
+use plonky2_u32::gadgets::range_check::range_check_u32_circuit;
+use plonky2_u32::gadgets::sha256::{bytes_to_sha256_words, CircuitBuilderSha256};
+use plonky2_u32::witness::WitnessU32;
 
 pub const SPONGE_WIDTH: usize = 12;
 pub const SPONGE_RATE: usize = 8;
-use plonky2::plonk::circuit_data::{CommonCircuitData, VerifierOnlyCircuitData};
+use plonky2::plonk::circuit_data::{CommonCircuitData, VerifierCircuitData, VerifierOnlyCircuitData};
+use std::time::{SystemTime, UNIX_EPOCH};
 use plonky2::plonk::config::{AlgebraicHasher};
+use plonky2_ecdsa::curve::curve_types::Curve;
+use plonky2_ecdsa::curve::ecdsa::{sign_message, ECDSAPublicKey, ECDSASecretKey, ECDSASignature};
+use plonky2_ecdsa::curve::secp256k1::Secp256K1;
+use plonky2_ecdsa::gadgets::biguint::{BigUintTarget, CircuitBuilderBiguint, WitnessBigUint};
+use plonky2_ecdsa::gadgets::ecdsa::{verify_message_circuit, ECDSAPublicKeyTarget, ECDSASignatureTarget};
+use plonky2_ecdsa::gadgets::nonnative::{CircuitBuilderNonNative, NonNativeTarget};
 
 // Recursion imports 
 use plonky2::plonk::proof::{ProofWithPublicInputs};
@@ -33,14 +57,432 @@ const D: usize = 2;
 type C = PoseidonGoldilocksConfig;
 type F = <C as GenericConfig<D>>::F;
 
-// we define a hash function whose digest is 5 GFp5 elems
-// note: this doesn't apply any padding, so this is vulnerable to length extension attacks
-fn sig_hash(message: &[F]) -> [F; 5] {
-	let mut res = [F::ZERO; 5];
-	let out = hash_n_to_m_no_pad::<F, PoseidonPermutation<F>>(message, 5);
-	res.copy_from_slice(&out[..5]);
+// height of the approved firmware tree, i.e. up to 2^FW_TREE_HEIGHT accepted versions
+const FW_TREE_HEIGHT: usize = 4;
+
+// the public allow-list root together with the device's measurement and its opening
+struct Firmware {
+    root: HashOut<F>,
+    measurement: HashOut<F>,
+    index: usize,
+    proof: MerkleProof<F, PoseidonHash>,
+}
+
+// how long devices have to answer a challenge, in seconds
+const CHALLENGE_TTL: u32 = 600;
+
+// attestation challenge of one round, timestamps are unix seconds. The nonce is the VRF output of
+// the challenger key over the public round seed and the other fields, so the challenger cannot pick
+// it and anyone can check how it was derived.
+struct Challenge {
+    round_id: u32,
+    issued_at: u32,
+    expires_at: u32,
+    seed: F,
+    nonce: F,
+    proof: vrf::Proof,
+}
+
+impl Challenge {
+    fn derive(round_id: u32, issued_at: u32, seed: F, sk: &vrf::SecretKey) -> Self {
+        let expires_at = issued_at + CHALLENGE_TTL;
+        let alpha = [
+            seed,
+            F::from_canonical_u32(round_id),
+            F::from_canonical_u32(issued_at),
+            F::from_canonical_u32(expires_at),
+        ];
+        let (beta, proof) = sk.prove(&alpha);
+        Self {
+            round_id,
+            issued_at,
+            expires_at,
+            seed,
+            nonce: beta.elements[0],
+            proof,
+        }
+    }
+
+    fn elements(&self) -> [F; 4] {
+        [
+            F::from_canonical_u32(self.round_id),
+            F::from_canonical_u32(self.issued_at),
+            F::from_canonical_u32(self.expires_at),
+            self.nonce,
+        ]
+    }
+
+    // the field element devices compute their response over
+    fn hash(&self) -> F {
+        PoseidonHash::hash_no_pad(&self.elements()).elements[0]
+    }
+}
+
+fn unix_now() -> u32 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("system clock is before the unix epoch")
+        .as_secs() as u32
+}
+
+// Signature scheme the manufacturer signs device statements with. The signed message and the
+// public statement are the same for every scheme, only the in-circuit verification differs.
+trait SignatureScheme {
+    const NAME: &'static str;
+
+    type SecretKey;
+    type PublicKey: Copy;
+    type Signature;
+    type SignatureTarget;
+
+    fn keygen<R: RngCore>(rng: &mut R) -> (Self::SecretKey, Self::PublicKey);
+
+    fn sign(sk: &Self::SecretKey, m: &[F]) -> Self::Signature;
+
+    fn add_virtual_signature_target(builder: &mut CircuitBuilder<F, D>) -> Self::SignatureTarget;
+
+    // asserts that `sig` is a valid signature on `m` under the fixed manufacturer key `pk`
+    fn verify_circuit(
+        builder: &mut CircuitBuilder<F, D>,
+        pk: Self::PublicKey,
+        m: &[Target],
+        sig: &Self::SignatureTarget,
+    );
+
+    fn set_signature_target(
+        pw: &mut PartialWitness<F>,
+        target: &Self::SignatureTarget,
+        sig: &Self::Signature,
+    );
+}
+
+// Schnorr over ecgfp5, whose challenge hash is native to the circuit
+struct Schnorr;
+
+impl SignatureScheme for Schnorr {
+    const NAME: &'static str = "schnorr";
+
+    type SecretKey = SecretKey;
+    type PublicKey = PublicKey;
+    type Signature = Signature;
+    type SignatureTarget = SchnorrSignatureTarget;
+
+    fn keygen<R: RngCore>(rng: &mut R) -> (SecretKey, PublicKey) {
+        let sk = SecretKey::sample(rng);
+        let pk = sk.public_key();
+        (sk, pk)
+    }
+
+    fn sign(sk: &SecretKey, m: &[F]) -> Signature {
+        sk.sign(m)
+    }
+
+    fn add_virtual_signature_target(builder: &mut CircuitBuilder<F, D>) -> Self::SignatureTarget {
+        builder.add_virtual_schnorr_signature_target()
+    }
+
+    fn verify_circuit(
+        builder: &mut CircuitBuilder<F, D>,
+        pk: PublicKey,
+        m: &[Target],
+        sig: &Self::SignatureTarget,
+    ) {
+        let pk_tgt = builder.constant_schnorr_public_key(pk);
+        builder.verify_schnorr(pk_tgt, m, sig);
+    }
+
+    fn set_signature_target(pw: &mut PartialWitness<F>, target: &Self::SignatureTarget, sig: &Signature) {
+        pw.set_schnorr_signature_target(target, sig);
+    }
+}
+
+// ECDSA over secp256k1, for manufacturers that already sign device certificates with it
+struct Ecdsa;
+
+// (r, s) as 32-bit limbs, like the Schnorr signature target
+struct EcdsaSignatureTarget {
+    r: BigUintTarget,
+    s: BigUintTarget,
+}
+
+impl Ecdsa {
+    // the signed scalar is H(m) = (h_0, .., h_3) read as a little-endian 256-bit integer mod n
+    fn message_scalar(m: &[F]) -> Secp256K1Scalar {
+        let two_32 = Secp256K1Scalar::from_canonical_u64(1 << 32);
+        let two_64 = two_32 * two_32;
+        PoseidonHash::hash_no_pad(m)
+            .elements
+            .iter()
+            .rev()
+            .fold(Secp256K1Scalar::ZERO, |acc, e| {
+                acc * two_64 + Secp256K1Scalar::from_canonical_u64(e.to_canonical_u64())
+            })
+    }
+
+    fn message_scalar_circuit(
+        builder: &mut CircuitBuilder<F, D>,
+        m: &[Target],
+    ) -> NonNativeTarget<Secp256K1Scalar> {
+        let h = builder.hash_n_to_hash_no_pad::<PoseidonHash>(m.to_vec());
+        // the split must be canonical, or h + p would give a second scalar for the same hash
+        let limbs = h
+            .elements
+            .iter()
+            .flat_map(|&e| builder.split_canonical_u64(e).limbs)
+            .collect();
+        builder.reduce(&BigUintTarget { limbs })
+    }
+}
+
+impl SignatureScheme for Ecdsa {
+    const NAME: &'static str = "ecdsa";
+
+    type SecretKey = ECDSASecretKey<Secp256K1>;
+    type PublicKey = ECDSAPublicKey<Secp256K1>;
+    type Signature = ECDSASignature<Secp256K1>;
+    type SignatureTarget = EcdsaSignatureTarget;
+
+    fn keygen<R: RngCore>(rng: &mut R) -> (Self::SecretKey, Self::PublicKey) {
+        let sk = ECDSASecretKey(<Secp256K1 as Curve>::ScalarField::sample(rng));
+        let pk = sk.to_public();
+        (sk, pk)
+    }
+
+    fn sign(sk: &Self::SecretKey, m: &[F]) -> Self::Signature {
+        sign_message(Self::message_scalar(m), *sk)
+    }
+
+    fn add_virtual_signature_target(builder: &mut CircuitBuilder<F, D>) -> Self::SignatureTarget {
+        let num_limbs = CircuitBuilder::<F, D>::num_nonnative_limbs::<Secp256K1Scalar>();
+        let r = builder.add_virtual_biguint_target(num_limbs);
+        let s = builder.add_virtual_biguint_target(num_limbs);
+        range_check_u32_circuit(builder, r.limbs.clone());
+        range_check_u32_circuit(builder, s.limbs.clone());
+        EcdsaSignatureTarget { r, s }
+    }
+
+    fn verify_circuit(
+        builder: &mut CircuitBuilder<F, D>,
+        pk: Self::PublicKey,
+        m: &[Target],
+        sig: &Self::SignatureTarget,
+    ) {
+        // not imported at the top, its curve methods clash with the ecgfp5 ones
+        use plonky2_ecdsa::gadgets::curve::CircuitBuilderCurve;
+
+        let msg = Self::message_scalar_circuit(builder, m);
+        let pk_tgt = ECDSAPublicKeyTarget(builder.constant_affine_point(pk.0));
+        let sig_tgt = ECDSASignatureTarget {
+            r: builder.biguint_to_nonnative(&sig.r),
+            s: builder.biguint_to_nonnative(&sig.s),
+        };
+        verify_message_circuit(builder, msg, sig_tgt, pk_tgt);
+    }
+
+    fn set_signature_target(pw: &mut PartialWitness<F>, target: &Self::SignatureTarget, sig: &Self::Signature) {
+        pw.set_biguint_target(&target.r, &sig.r.to_canonical_biguint());
+        pw.set_biguint_target(&target.s, &sig.s.to_canonical_biguint());
+    }
+}
+
+// How a device answers a challenge. The response stays private and enters the commitment the
+// manufacturer signs, next to the challenge and the firmware measurement.
+trait ResponseScheme {
+    const NAME: &'static str;
+
+    type Key;
+    type Response;
+    type ResponseTarget;
+
+    fn keygen<R: RngCore>(rng: &mut R) -> Self::Key;
+
+    // what the device computes natively
+    fn respond(key: &Self::Key, chall: F, measurement: HashOut<F>) -> Self::Response;
+
+    // the field elements the response is committed as
+    fn elements(rsp: &Self::Response) -> Vec<F>;
+
+    // public commitment to the device key, empty if the response is not checked in-circuit
+    fn key_commitment(key: &Self::Key) -> Vec<F>;
+
+    fn add_virtual_response_target(builder: &mut CircuitBuilder<F, D>) -> Self::ResponseTarget;
+
+    // constrains the response to the challenge and measurement and returns its committed elements
+    fn verify_response_circuit(
+        builder: &mut CircuitBuilder<F, D>,
+        rsp: &Self::ResponseTarget,
+        chall: Target,
+        measurement: HashOutTarget,
+    ) -> Vec<Target>;
+
+    fn key_commitment_targets(rsp: &Self::ResponseTarget) -> Vec<Target>;
+
+    fn set_response_target(
+        pw: &mut PartialWitness<F>,
+        target: &Self::ResponseTarget,
+        key: &Self::Key,
+        rsp: &Self::Response,
+    ) -> Result<()>;
+}
+
+// A single field element the circuit only commits to, without checking how it was computed
+struct OpaqueResponse;
+
+impl ResponseScheme for OpaqueResponse {
+    const NAME: &'static str = "opaque";
+
+    type Key = F;
+    type Response = F;
+    type ResponseTarget = Target;
+
+    fn keygen<R: RngCore>(rng: &mut R) -> F {
+        F::sample(rng)
+    }
+
+    fn respond(key: &F, chall: F, measurement: HashOut<F>) -> F {
+        let mut preimage = vec![*key, chall];
+        preimage.extend(measurement.elements);
+        PoseidonHash::hash_no_pad(&preimage).elements[0]
+    }
+
+    fn elements(rsp: &F) -> Vec<F> {
+        vec![*rsp]
+    }
+
+    fn key_commitment(_key: &F) -> Vec<F> {
+        vec![]
+    }
+
+    fn add_virtual_response_target(builder: &mut CircuitBuilder<F, D>) -> Target {
+        builder.add_virtual_target()
+    }
+
+    fn verify_response_circuit(
+        _builder: &mut CircuitBuilder<F, D>,
+        rsp: &Target,
+        _chall: Target,
+        _measurement: HashOutTarget,
+    ) -> Vec<Target> {
+        vec![*rsp]
+    }
+
+    fn key_commitment_targets(_rsp: &Target) -> Vec<Target> {
+        vec![]
+    }
 
-	res
+    fn set_response_target(pw: &mut PartialWitness<F>, target: &Target, _key: &F, rsp: &F) -> Result<()> {
+        pw.set_target(*target, *rsp)
+    }
+}
+
+// HMAC-SHA256 over measurement || challenge, as computed by classical symmetric RA schemes such as
+// SMART or TrustLite. The key stays private, the verifier learns a Poseidon commitment to it.
+struct HmacResponse;
+
+struct HmacResponseTarget {
+    key: Vec<U32Target>,
+    tag: Vec<U32Target>,
+    key_commitment: HashOutTarget,
+}
+
+impl HmacResponse {
+    // each field element is hashed as its 8 big-endian canonical bytes
+    fn message(chall: F, measurement: HashOut<F>) -> Vec<u8> {
+        measurement
+            .elements
+            .iter()
+            .chain([chall].iter())
+            .flat_map(|e| e.to_canonical_u64().to_be_bytes())
+            .collect()
+    }
+
+    fn message_circuit(
+        builder: &mut CircuitBuilder<F, D>,
+        chall: Target,
+        measurement: HashOutTarget,
+    ) -> Vec<U32Target> {
+        // canonical, so that the hashed words are determined by the committed elements
+        let mut words = Vec::with_capacity(10);
+        for e in measurement.elements.into_iter().chain([chall]) {
+            let [lo, hi] = builder.split_canonical_u64(e).limbs;
+            words.push(hi);
+            words.push(lo);
+        }
+        words
+    }
+}
+
+impl ResponseScheme for HmacResponse {
+    const NAME: &'static str = "hmac-sha256";
+
+    type Key = [u32; 8];
+    type Response = [u32; 8];
+    type ResponseTarget = HmacResponseTarget;
+
+    fn keygen<R: RngCore>(rng: &mut R) -> [u32; 8] {
+        std::array::from_fn(|_| rng.next_u32())
+    }
+
+    fn respond(key: &[u32; 8], chall: F, measurement: HashOut<F>) -> [u32; 8] {
+        let key_bytes: Vec<u8> = key.iter().flat_map(|w| w.to_be_bytes()).collect();
+        let mut mac = Hmac::<Sha256>::new_from_slice(&key_bytes).expect("HMAC accepts any key length");
+        mac.update(&Self::message(chall, measurement));
+        bytes_to_sha256_words(&mac.finalize().into_bytes())
+            .try_into()
+            .expect("HMAC-SHA256 tags have 8 words")
+    }
+
+    fn elements(rsp: &[u32; 8]) -> Vec<F> {
+        rsp.iter().map(|&w| F::from_canonical_u32(w)).collect()
+    }
+
+    fn key_commitment(key: &[u32; 8]) -> Vec<F> {
+        let key: Vec<F> = key.iter().map(|&w| F::from_canonical_u32(w)).collect();
+        PoseidonHash::hash_no_pad(&key).elements.to_vec()
+    }
+
+    fn add_virtual_response_target(builder: &mut CircuitBuilder<F, D>) -> HmacResponseTarget {
+        // the key words are range-checked by the bitwise gates of the HMAC, the tag by the
+        // final additions it is connected to
+        let key = builder.add_virtual_u32_targets(8);
+        let tag = builder.add_virtual_u32_targets(8);
+        let key_elements = key.iter().map(|w| w.0).collect();
+        let key_commitment = builder.hash_n_to_hash_no_pad::<PoseidonHash>(key_elements);
+        HmacResponseTarget { key, tag, key_commitment }
+    }
+
+    fn verify_response_circuit(
+        builder: &mut CircuitBuilder<F, D>,
+        rsp: &HmacResponseTarget,
+        chall: Target,
+        measurement: HashOutTarget,
+    ) -> Vec<Target> {
+        let msg = Self::message_circuit(builder, chall, measurement);
+        let tag = builder.hmac_sha256(&rsp.key, &msg, 4 * msg.len());
+        for (&expected, &actual) in tag.iter().zip(rsp.tag.iter()) {
+            builder.connect_u32(expected, actual);
+        }
+        rsp.tag.iter().map(|w| w.0).collect()
+    }
+
+    fn key_commitment_targets(rsp: &HmacResponseTarget) -> Vec<Target> {
+        rsp.key_commitment.elements.to_vec()
+    }
+
+    fn set_response_target(
+        pw: &mut PartialWitness<F>,
+        target: &HmacResponseTarget,
+        key: &[u32; 8],
+        rsp: &[u32; 8],
+    ) -> Result<()> {
+        for (&tgt, &w) in target.key.iter().zip(key.iter()) {
+            pw.set_u32_target(tgt, w);
+        }
+        for (&tgt, &w) in target.tag.iter().zip(rsp.iter()) {
+            pw.set_u32_target(tgt, w);
+        }
+        Ok(())
+    }
 }
 
 // why would you pad this to 5 field elems? Seems weird to me except for Quintic Extension
@@ -62,62 +504,74 @@ fn sig_hash_circuit(builder: &mut CircuitBuilder<F, D>, message: &[Target]) -> [
 	]
 }
 
-fn sign(
-    rsp: F,
+// the manufacturer signs m = root || k_prf || svn || device_id, hashing exactly as the circuit does
+#[allow(clippy::too_many_arguments)]
+fn sign<S: SignatureScheme>(
+    rsp: &[F],
     path: [F;5],
     k_prf: F,
     chall: F,
-    sk: Scalar,
-) -> Result<(Scalar,Scalar)>{
-    let com = sig_hash(&[rsp, chall]);
+    measurement: HashOut<F>,
+    svn: u32,
+    device_id: &[F; 4],
+    sk: &S::SecretKey,
+) -> S::Signature {
+    let mut com_preimage = rsp.to_vec();
+    com_preimage.push(chall);
+    com_preimage.extend(measurement.elements);
+    let com = PoseidonHash::hash_no_pad(&com_preimage);
 
     let mut hsh = com;
     for i in 0..5 {
-        let mut x = hsh.to_vec();
+        let mut x = hsh.elements.to_vec();
         x.push(path[i]);
-        hsh = sig_hash(&x);
+        hsh = PoseidonHash::hash_no_pad(&x);
     }
     let root = hsh;
-    let mut m = root.to_vec();
+    let mut m = root.elements.to_vec();
     m.push(k_prf);
-	
-    let mut rng = thread_rng();
-	let k  = Scalar::sample(&mut rng);
-
-	// Compute R = k*G
-	let r = Point::GENERATOR * k;
+    m.push(F::from_canonical_u32(svn));
+    m.extend_from_slice(device_id);
 
-	// 4. e = H(R || m)
-	let mut preimage = r.encode().0.to_vec();
-	preimage.extend(m);
-	let e_elems = sig_hash(&preimage);
-	let e = Scalar::from_gfp5(QuinticExtension(e_elems));
-
-	// 5. s = k - e*sk
-	// signature = (s, e)
-	let s = k - e * sk;
-
-    Ok((s,e))
+    S::sign(sk, &m)
 }
 
-fn single_client_proof(
+#[allow(clippy::too_many_arguments)]
+fn single_client_proof<S: SignatureScheme, R: ResponseScheme>(
     config: &CircuitConfig,
-    rsp: F,
+    key: &R::Key,
+    rsp: &R::Response,
     path: [F;5],
     k_prf: F,
-    chall: F,
-    pk: Point,
-    sig: (Scalar,Scalar),
+    chall: &Challenge,
+    vrf_pk: vrf::PublicKey,
+    fw: &Firmware,
+    svn: u32,
+    min_svn: u32,
+    device_id: &[F; 4],
+    auditor_pk: elgamal::PublicKey,
+    escrow_r: Scalar,
+    pk: S::PublicKey,
+    sig: &S::Signature,
 ) -> Result<ProofTuple<F,C,D>>
 {
     let mut builder = CircuitBuilder::<F, D>::new(config.clone());
 
     // Circuit implementation
-    let rsp_tgt = builder.add_virtual_target();
-    let chall_tgt = builder.add_virtual_target();
+    let rsp_tgt = R::add_virtual_response_target(&mut builder);
+    // the challenge is hashed in-circuit so its fields can be exposed as public inputs
+    let chall_fields_tgt = builder.add_virtual_targets(4);
+    let chall_tgt = builder
+        .hash_n_to_hash_no_pad::<PoseidonHash>(chall_fields_tgt.clone())
+        .elements[0];
     builder.register_public_input(chall_tgt);
 
-    let com = builder.hash_n_to_hash_no_pad::<PoseidonHash>([rsp_tgt,chall_tgt].to_vec());
+    // the response is bound to the firmware measurement it was computed over
+    let measurement_tgt = builder.add_virtual_hash();
+    let mut com_preimage = R::verify_response_circuit(&mut builder, &rsp_tgt, chall_tgt, measurement_tgt);
+    com_preimage.push(chall_tgt);
+    com_preimage.extend(measurement_tgt.elements);
+    let com = builder.hash_n_to_hash_no_pad::<PoseidonHash>(com_preimage);
 
     let mut hsh = com;
     let path_tgt: Vec<Target> = path.iter().map(|_| builder.add_virtual_target()).collect(); 
@@ -131,41 +585,94 @@ fn single_client_proof(
     let kprf_tgt = builder.add_virtual_target();
     let tag = sig_hash_circuit(&mut builder,&[kprf_tgt,chall_tgt].to_vec());
     builder.register_public_inputs(&tag[0..2]);
+
+    // the measurement must be a leaf of the public allow-list root, hiding which version it is
+    let fw_root_tgt = builder.add_virtual_hash_public_input();
+    let fw_index_tgt = builder.add_virtual_target();
+    let fw_index_bits = builder.split_le(fw_index_tgt, FW_TREE_HEIGHT);
+    let fw_proof_tgt = MerkleProofTarget {
+        siblings: builder.add_virtual_hashes(FW_TREE_HEIGHT),
+    };
+    builder.verify_merkle_proof_to_cap::<PoseidonHash>(
+        measurement_tgt.elements.to_vec(),
+        &fw_index_bits,
+        &MerkleCapTarget(vec![fw_root_tgt]),
+        &fw_proof_tgt,
+    );
     
+    // anti-rollback: the signed version counter must be at least the public minimum
+    let svn_tgt = builder.add_virtual_u32_target();
+    let min_svn_tgt = builder.add_virtual_u32_target();
+    builder.register_public_input(min_svn_tgt.0);
+    let svn_ok = list_le_u32_circuit(&mut builder, vec![min_svn_tgt], vec![svn_tgt]);
+    builder.assert_one(svn_ok.target);
+
+    // expose (round_id, issued_at, expires_at, nonce) so verifiers can reject stale statements
+    builder.register_public_inputs(&chall_fields_tgt);
+    let issued_at_tgt = U32Target(chall_fields_tgt[1]);
+    let expires_at_tgt = U32Target(chall_fields_tgt[2]);
+    let window_ok = list_le_u32_circuit(&mut builder, vec![issued_at_tgt], vec![expires_at_tgt]);
+    builder.assert_one(window_ok.target);
+
+    // the nonce must be the challenger's VRF output over the public round seed and the other fields
+    let seed_tgt = builder.add_virtual_target();
+    builder.register_public_input(seed_tgt);
+    let vrf_pk_tgt = builder.add_virtual_vrf_public_key_target();
+    builder.register_quintic_ext_public_input(vrf_pk_tgt.0);
+    let vrf_proof_tgt = builder.add_virtual_vrf_proof_target();
+    let mut alpha = vec![seed_tgt];
+    alpha.extend(&chall_fields_tgt[0..3]);
+    let beta = builder.verify_vrf(vrf_pk_tgt, &alpha, &vrf_proof_tgt);
+    builder.connect(chall_fields_tgt[3], beta.elements[0]);
+
+    // escrow: the signed device ID is encrypted under the public auditor key, so that only the
+    // auditor can trace a statement back to its device
+    let device_id_tgt = builder.add_virtual_targets(4);
+    let auditor_pk_tgt = builder.add_virtual_elgamal_public_key_target();
+    builder.register_quintic_ext_public_input(auditor_pk_tgt.0);
+    let escrow_r_tgt = builder.add_virtual_biguint_target(CircuitBuilder::<F, D>::num_nonnative_limbs::<Scalar>());
+    let escrow_r_nn = builder.biguint_to_nonnative(&escrow_r_tgt);
+    builder.assert_canonical_scalar(&escrow_r_nn);
+    let device_point = builder.elgamal_message_to_point(&device_id_tgt);
+    let escrow_ct = builder.elgamal_encrypt(auditor_pk_tgt, device_point, &escrow_r_nn);
+    builder.register_elgamal_ciphertext_public_input(escrow_ct);
+
+    // lets verifiers that know the device key check which key the response was computed with
+    builder.register_public_inputs(&R::key_commitment_targets(&rsp_tgt));
+
     let mut m = root.elements.to_vec();
     m.push(kprf_tgt);
+    m.push(svn_tgt.0);
+    m.extend(&device_id_tgt);
 
     // vfy sig 
-    let (s,e) = sig;
-	let s_tgt = builder.constant_nonnative::<Scalar>(s);
-	let e_tgt = builder.constant_nonnative::<Scalar>(e);
-
-	let g_tgt = builder.curve_constant(Point::GENERATOR.to_weierstrass());
-	let pk_tgt = builder.curve_constant(pk.to_weierstrass());
-
-	// r_v = s*G + e*pk
-	let r_v = builder.curve_muladd_2(
-		g_tgt,
-		pk_tgt,
-		&s_tgt,
-		&e_tgt
-	);
-
-	let mut preimage = builder.curve_encode_to_quintic_ext(r_v).0.to_vec();
-	preimage.extend(&m);
-	let e_v_ext = QuinticExtensionTarget(
-		sig_hash_circuit(&mut builder, &preimage)
-	);
-	let _e_v = builder.encode_quintic_ext_as_scalar(e_v_ext);
-    // ...final verification and tests to be added    
+    let sig_tgt = S::add_virtual_signature_target(&mut builder);
+    S::verify_circuit(&mut builder, pk, &m, &sig_tgt);
 
     let mut pw = PartialWitness::new();
-    pw.set_target(rsp_tgt,rsp)?;
-    pw.set_target(chall_tgt, chall)?;
+    R::set_response_target(&mut pw, &rsp_tgt, key, rsp)?;
+    for (tgt, val) in chall_fields_tgt.iter().zip(chall.elements().iter()) {
+        pw.set_target(*tgt, *val)?;
+    }
+    pw.set_target(seed_tgt, chall.seed)?;
+    pw.set_vrf_public_key_target(vrf_pk_tgt, vrf_pk);
+    pw.set_vrf_proof_target(&vrf_proof_tgt, &chall.proof);
     pw.set_target(kprf_tgt, k_prf)?;
     for i in 0..5 {
         pw.set_target(path_tgt[i], path[i])?;
     }
+    pw.set_hash_target(measurement_tgt, fw.measurement)?;
+    pw.set_hash_target(fw_root_tgt, fw.root)?;
+    pw.set_target(fw_index_tgt, F::from_canonical_usize(fw.index))?;
+    for (sibling_tgt, sibling) in fw_proof_tgt.siblings.iter().zip(fw.proof.siblings.iter()) {
+        pw.set_hash_target(*sibling_tgt, *sibling)?;
+    }
+    pw.set_u32_target(svn_tgt, svn);
+    pw.set_u32_target(min_svn_tgt, min_svn);
+    pw.set_target_arr(&device_id_tgt, device_id)?;
+    pw.set_elgamal_public_key_target(auditor_pk_tgt, auditor_pk);
+    pw.set_biguint_target(&escrow_r_tgt, &escrow_r.to_canonical_biguint());
+    S::set_signature_target(&mut pw, &sig_tgt, sig);
 
     let mut timing = TimingTree::new("build witness", Level::Info);
     let data = builder.build::<C>();
@@ -218,6 +725,9 @@ where
     let curve_tag = builder.curve_scalar_mul(gen_tgt, &tag_tgt);
     builder.register_curve_public_input(curve_tag);
 
+    // forward the firmware allow-list root, the minimum version, the challenge with its seed and
+    // challenger key, the auditor key, the escrowed device ID and the device key commitment, if any
+    builder.register_public_inputs(&pt.public_inputs[3..]);
 
     // add public inputs and set value
     
@@ -328,6 +838,16 @@ where
     let grp_agg = builder.curve_add(curve_tgt1, curve_tgt2);
     builder.register_curve_public_input(grp_agg);
 
+    // both devices must attest against the same firmware allow-list, minimum version, challenge
+    // derivation and auditor key
+    for i in 12..32 {
+        builder.connect(pt.public_inputs[i], pt2.public_inputs[i]);
+    }
+    builder.register_public_inputs(&pt.public_inputs[12..32]);
+    // followed by the escrowed device ID and the key commitment, if any, of each device
+    builder.register_public_inputs(&pt.public_inputs[32..]);
+    builder.register_public_inputs(&pt2.public_inputs[32..]);
+
     let data = builder.build::<C>();
 
     let mut pw = PartialWitness::new();
@@ -356,33 +876,128 @@ where
 }
 
 
-fn main() -> Result<()> {
-    // enable logging
-    Builder::new()
-        .filter_level(LevelFilter::Info)
-        .init();
-    info!("Starting program");
+// Verifies an aggregated statement for the given allow-list, minimum version, round seed,
+// challenger key, auditor key and, for each device, its escrowed ID and key commitment, rejecting
+// it once its challenge has expired at `now`, which defaults to the local clock.
+#[allow(clippy::too_many_arguments)]
+fn verify_statement(
+    agg: &ProofTuple<F, C, D>,
+    fw_root: HashOut<F>,
+    min_svn: u32,
+    seed: F,
+    vrf_pk: vrf::PublicKey,
+    auditor_pk: elgamal::PublicKey,
+    escrow_cts: &[Ciphertext],
+    key_commitments: &[Vec<F>],
+    now: Option<u32>,
+) -> Result<()> {
+    let (proof, vd, cd) = agg;
+    let pis = &proof.public_inputs;
+
+    // the verifier only learns that all devices run an approved firmware
+    ensure!(pis[11..15] == fw_root.elements, "firmware allow-list root mismatch");
+    ensure!(pis[15] == F::from_canonical_u32(min_svn), "minimum firmware version mismatch");
+    // the circuit checked that the challenge nonce was derived from this seed under this key
+    ensure!(pis[20] == seed, "round seed mismatch");
+    ensure!(pis[21..26] == vrf_pk.encode().0, "challenger key mismatch");
+    ensure!(pis[26..31] == auditor_pk.encode().0, "auditor key mismatch");
+
+    ensure!(escrow_cts.len() == key_commitments.len(), "one key commitment per device expected");
+    let devices: Vec<F> = escrow_cts
+        .iter()
+        .zip(key_commitments)
+        .flat_map(|(ct, kc)| ct.encode().into_iter().flat_map(|w| w.0).chain(kc.iter().copied()))
+        .collect();
+    ensure!(pis[31..] == devices, "escrowed device ID or key commitment mismatch");
+
+    let now = F::from_canonical_u32(now.unwrap_or_else(unix_now));
+    let (issued_at, expires_at) = (pis[17], pis[18]);
+    if now.to_canonical_u64() < issued_at.to_canonical_u64() {
+        bail!("challenge of round {} is not yet valid", pis[16]);
+    }
+    if now.to_canonical_u64() >= expires_at.to_canonical_u64() {
+        bail!("challenge of round {} has expired", pis[16]);
+    }
+
+    let data = VerifierCircuitData {
+        verifier_only: vd.clone(),
+        common: cd.clone(),
+    };
+    data.verify(proof.clone())
+}
+
+// Runs the attestation flow for two devices whose statements are signed with scheme `S` and
+// which answer challenges with scheme `R`.
+fn run<S: SignatureScheme, R: ResponseScheme>() -> Result<()> {
+    info!("Manufacturer signatures use {}, device responses use {}", S::NAME, R::NAME);
 
     // Sample initial witness
     let mut rng = thread_rng();
-    let rsp = F::sample(&mut rng);
+    let device_key = R::keygen(&mut rng);
     // in practice, test first which witness we need. For benchmarking irrelevant
     let path = std::array::from_fn(|_| F::sample(&mut rng));
     let k_prf = F::sample(&mut rng);
-    let chall = F::sample(&mut rng);
+    // the public seed of this round, e.g. taken from a randomness beacon, fixes the challenge
+    let seed = F::sample(&mut rng);
+    let challenger_sk = vrf::SecretKey::sample(&mut rng);
+    let challenger_pk = challenger_sk.public_key();
+    let chall = Challenge::derive(1, unix_now(), seed, &challenger_sk);
+
+    // publish the approved firmware versions as a Merkle root
+    let measurements: Vec<HashOut<F>> = (0..1 << FW_TREE_HEIGHT)
+        .map(|_| PoseidonHash::hash_no_pad(&F::rand_vec(16)))
+        .collect();
+    let fw_tree = MerkleTree::<F, PoseidonHash>::new(
+        measurements.iter().map(|m| m.elements.to_vec()).collect(),
+        0,
+    );
+    let fw_index = 5;
+    let fw = Firmware {
+        root: fw_tree.cap.0[0],
+        measurement: measurements[fw_index],
+        index: fw_index,
+        proof: fw_tree.prove(fw_index),
+    };
+
+    // manufacturer-signed version counter, the verifier only learns it is not below min_svn
+    let svn = 7;
+    let min_svn = 5;
+
+    // the manufacturer-assigned device ID, which only the auditor can recover from a statement
+    let device_id: [F; 4] = std::array::from_fn(|_| F::sample(&mut rng));
+    let auditor_sk = elgamal::SecretKey::sample(&mut rng);
+    let auditor_pk = auditor_sk.public_key();
+
+    // the device answers the challenge over its measurement
+    let rsp = R::respond(&device_key, chall.hash(), fw.measurement);
 
     // generate signature
-	let sk  = Scalar::sample(&mut rng);
-    let pk = Point::GENERATOR * sk;
-    let sig= sign(rsp,path,k_prf,chall,sk)?;
+    let (sk, pk) = S::keygen(&mut rng);
+    let sig = sign::<S>(&R::elements(&rsp),path,k_prf,chall.hash(),fw.measurement,svn,&device_id,&sk);
+
+    // fresh escrow randomness per statement keeps the two ciphertexts unlinkable
+    let (r1, r2) = (Scalar::sample(&mut rng), Scalar::sample(&mut rng));
+    let device_point = message_to_point(&device_id);
+    let escrow_cts = [
+        auditor_pk.encrypt_with_randomness(device_point, r1),
+        auditor_pk.encrypt_with_randomness(device_point, r2),
+    ];
 
     // do a single proof
     let config_zk = CircuitConfig::standard_recursion_zk_config(); // requires zero-knowledge
-    let inner = single_client_proof(&config_zk,rsp,path,k_prf,chall,pk,sig).unwrap();
+    let inner = single_client_proof::<S, R>(&config_zk,&device_key,&rsp,path,k_prf,&chall,challenger_pk,&fw,svn,min_svn,&device_id,auditor_pk,r1,pk,&sig).unwrap();
 
     // do another proof
     let k_prf2 = F::sample(&mut rng);
-    let inner2 = single_client_proof(&config_zk,rsp,path,k_prf2,chall,pk,sig).unwrap();
+    let sig2 = sign::<S>(&R::elements(&rsp),path,k_prf2,chall.hash(),fw.measurement,svn,&device_id,&sk);
+    let inner2 = single_client_proof::<S, R>(&config_zk,&device_key,&rsp,path,k_prf2,&chall,challenger_pk,&fw,svn,min_svn,&device_id,auditor_pk,r2,pk,&sig2).unwrap();
+
+    // a nonce derived from another seed cannot be proven under this round's seed, even with a
+    // valid VRF proof and a response and signature over it
+    let forged = Challenge { seed, ..Challenge::derive(chall.round_id, chall.issued_at, seed + F::ONE, &challenger_sk) };
+    let forged_rsp = R::respond(&device_key, forged.hash(), fw.measurement);
+    let forged_sig = sign::<S>(&R::elements(&forged_rsp),path,k_prf,forged.hash(),fw.measurement,svn,&device_id,&sk);
+    ensure!(single_client_proof::<S, R>(&config_zk,&device_key,&forged_rsp,path,k_prf,&forged,challenger_pk,&fw,svn,min_svn,&device_id,auditor_pk,r1,pk,&forged_sig).is_err(), "challenge for another seed was accepted");
 
     // do a recursive proof
     let config = CircuitConfig::standard_recursion_config(); // does not require zero-knowledge
@@ -390,7 +1005,46 @@ fn main() -> Result<()> {
     let outer12 = simple_recursive_proof_wrapper(&outer11, &config)?;
     let outer21 = recursive_proof_wrapper(&inner2, &config)?;
     let outer22 = simple_recursive_proof_wrapper(&outer21, &config)?;
-    let _outer_agg = double_recursive_proof(&outer12, &outer22, &config)?;
+    let outer_agg = double_recursive_proof(&outer12, &outer22, &config)?;
+
+    // both proofs were made for the same device key
+    let key_commitments = [R::key_commitment(&device_key), R::key_commitment(&device_key)];
+    verify_statement(&outer_agg, fw.root, min_svn, seed, challenger_pk, auditor_pk, &escrow_cts, &key_commitments, None)?;
+
+    // the statement does not verify against another round seed
+    ensure!(verify_statement(&outer_agg, fw.root, min_svn, seed + F::ONE, challenger_pk, auditor_pk, &escrow_cts, &key_commitments, None).is_err());
+
+    // the same statement is stale once the challenge has expired
+    ensure!(verify_statement(&outer_agg, fw.root, min_svn, seed, challenger_pk, auditor_pk, &escrow_cts, &key_commitments, Some(chall.expires_at)).is_err());
+
+    // only the auditor can tell which device a statement came from
+    for ct in &escrow_cts {
+        ensure!(auditor_sk.decrypt(ct).equals(device_point), "escrow does not decrypt to the device ID");
+    }
 
     Ok(())
 }
+
+fn run_with_response<R: ResponseScheme>(scheme: Option<&str>) -> Result<()> {
+    match scheme {
+        None | Some("schnorr") => run::<Schnorr, R>(),
+        Some("ecdsa") => run::<Ecdsa, R>(),
+        Some(other) => bail!("unknown signature scheme {}, expected schnorr or ecdsa", other),
+    }
+}
+
+// usage: cargo run --release --example piranhas [schnorr|ecdsa] [opaque|hmac]
+fn main() -> Result<()> {
+    // enable logging
+    Builder::new()
+        .filter_level(LevelFilter::Info)
+        .init();
+    info!("Starting program");
+
+    let scheme = std::env::args().nth(1);
+    match std::env::args().nth(2).as_deref() {
+        None | Some("opaque") => run_with_response::<OpaqueResponse>(scheme.as_deref()),
+        Some("hmac") => run_with_response::<HmacResponse>(scheme.as_deref()),
+        Some(other) => bail!("unknown response scheme {}, expected opaque or hmac", other),
+    }
+}
@@ -0,0 +1,143 @@
+use log::{info, Level, LevelFilter};
+use plonky2::hash::hash_types::HashOut;
+use plonky2::hash::poseidon::PoseidonHash;
+use plonky2::iop::target::Target;
+use plonky2::iop::witness::{PartialWitness, WitnessWrite};
+use plonky2::plonk::circuit_builder::CircuitBuilder;
+use plonky2::plonk::circuit_data::CircuitConfig;
+use plonky2::plonk::config::{GenericConfig, Hasher, PoseidonGoldilocksConfig};
+use plonky2::plonk::prover::prove;
+use plonky2::util::timing::TimingTree;
+use plonky2_field::types::{Field, PrimeField64, Sample};
+use plonky2_u32::gadgets::arithmetic_uint::CircuitBuilderUInt;
+use rand::thread_rng;
+
+use anyhow::Result;
+use env_logger::Builder;
+
+// Circuit setup - leave as is
+const D: usize = 2;
+type C = PoseidonGoldilocksConfig;
+type F = <C as GenericConfig<D>>::F;
+
+// number of attested memory words, a single `random_access` gate holds at most 64 of them
+const MEM_LOG_SIZE: usize = 6;
+const MEM_SIZE: usize = 1 << MEM_LOG_SIZE;
+// number of pseudorandom memory reads, should exceed MEM_SIZE * ln(MEM_SIZE) to cover all words
+const ITERATIONS: usize = 512;
+
+// SWATT-style checksum: each step derives the next address from the challenge and the
+// running checksum via Poseidon, so the traversal cannot be precomputed by the device
+fn swatt_checksum(memory: &[F], chall: F) -> F {
+    assert_eq!(memory.len(), MEM_SIZE);
+
+    let mut checksum = chall;
+    for i in 0..ITERATIONS {
+        let h = PoseidonHash::hash_no_pad(&[chall, checksum, F::from_canonical_usize(i)]).elements;
+        let addr = (h[0].to_canonical_u64() as usize) % MEM_SIZE;
+        checksum = h[1] + memory[addr];
+    }
+
+    checksum
+}
+
+fn swatt_checksum_circuit(
+    builder: &mut CircuitBuilder<F, D>,
+    memory: &[Target],
+    chall: Target,
+) -> Target {
+    assert_eq!(memory.len(), MEM_SIZE);
+
+    let mut checksum = chall;
+    for i in 0..ITERATIONS {
+        let idx = builder.constant(F::from_canonical_usize(i));
+        let h = builder
+            .hash_n_to_hash_no_pad::<PoseidonHash>(vec![chall, checksum, idx])
+            .elements;
+        // addr = h[0] mod MEM_SIZE, taken from the canonical representation so that the prover
+        // cannot pick the address of h[0] + p instead
+        let low = builder.split_canonical_u64(h[0]).limbs[0];
+        let (addr, _) = builder.split_low_high(low.0, MEM_LOG_SIZE, 32);
+        let word = builder.random_access(addr, memory.to_vec());
+        checksum = builder.add(h[1], word);
+    }
+
+    checksum
+}
+
+// Proves that the device response equals the checksum over a memory image whose
+// Poseidon hash is the public measurement, without revealing the memory itself.
+fn swatt_proof(
+    config: &CircuitConfig,
+    memory: &[F],
+    measurement: HashOut<F>,
+    chall: F,
+    rsp: F,
+) -> Result<()> {
+    let mut builder = CircuitBuilder::<F, D>::new(config.clone());
+
+    let chall_tgt = builder.add_virtual_target();
+    builder.register_public_input(chall_tgt);
+    let rsp_tgt = builder.add_virtual_target();
+    builder.register_public_input(rsp_tgt);
+    let measurement_tgt = builder.add_virtual_hash_public_input();
+
+    let memory_tgt = builder.add_virtual_targets(MEM_SIZE);
+    let mem_hash = builder.hash_n_to_hash_no_pad::<PoseidonHash>(memory_tgt.clone());
+    builder.connect_hashes(mem_hash, measurement_tgt);
+
+    let checksum = swatt_checksum_circuit(&mut builder, &memory_tgt, chall_tgt);
+    builder.connect(checksum, rsp_tgt);
+
+    let mut pw = PartialWitness::new();
+    pw.set_target(chall_tgt, chall)?;
+    pw.set_target(rsp_tgt, rsp)?;
+    pw.set_hash_target(measurement_tgt, measurement)?;
+    for (tgt, word) in memory_tgt.iter().zip(memory.iter()) {
+        pw.set_target(*tgt, *word)?;
+    }
+
+    let mut timing = TimingTree::new("build circuit", Level::Info);
+    let data = builder.build::<C>();
+    timing.print();
+    info!("SWATT circuit has 2^{} rows", data.common.degree_bits());
+
+    timing = TimingTree::new("prove", Level::Info);
+    let proof = prove::<F, C, D>(&data.prover_only, &data.common, pw, &mut timing)?;
+    timing.print();
+
+    timing = TimingTree::new("Verify", Level::Info);
+    data.verify(proof.clone())?;
+    timing.print();
+
+    println!("Successfully generated SWATT proof for challenge: {:?} with response: {:?}", proof.public_inputs[0], proof.public_inputs[1]);
+
+    Ok(())
+}
+
+fn main() -> Result<()> {
+    // enable logging
+    Builder::new()
+        .filter_level(LevelFilter::Info)
+        .init();
+    info!("Starting program");
+
+    let mut rng = thread_rng();
+    let memory = F::rand_vec(MEM_SIZE);
+    let measurement = PoseidonHash::hash_no_pad(&memory);
+    let chall = F::sample(&mut rng);
+
+    // response the device computes over its own memory
+    let rsp = swatt_checksum(&memory, chall);
+
+    let config = CircuitConfig::standard_recursion_zk_config();
+    swatt_proof(&config, &memory, measurement, chall, rsp)?;
+
+    // a device with modified memory cannot answer correctly
+    let mut tampered = memory.clone();
+    tampered[0] += F::ONE;
+    let bad_rsp = swatt_checksum(&tampered, chall);
+    assert!(swatt_proof(&config, &memory, measurement, chall, bad_rsp).is_err());
+
+    Ok(())
+}
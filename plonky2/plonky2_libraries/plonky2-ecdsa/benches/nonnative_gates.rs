@@ -0,0 +1,300 @@
+//! Gate counts of the non-native arithmetic behind signature verification. Every case is built
+//! twice: the baseline reduces each intermediate result with `add_nonnative`, `sub_nonnative` and
+//! `mul_nonnative` and multiplies big integers limb by limb, while the optimised build uses the
+//! gadgets as they are, with lazy `*_unreduced` accumulation and Karatsuba `mul_biguint`.
+//!
+//! Run with `cargo bench --bench nonnative_gates`.
+
+use num::BigUint;
+use plonky2::field::secp256k1_scalar::Secp256K1Scalar;
+use plonky2::field::types::Field;
+use plonky2::hash::keccak::KeccakHash;
+use plonky2::iop::target::BoolTarget;
+use plonky2::plonk::circuit_builder::CircuitBuilder;
+use plonky2::plonk::circuit_data::CircuitConfig;
+use plonky2::plonk::config::{GenericConfig, GenericHashOut, Hasher, PoseidonGoldilocksConfig};
+use plonky2_ecdsa::curve::curve_types::{Curve, CurveScalar};
+use plonky2_ecdsa::curve::secp256k1::Secp256K1;
+use plonky2_ecdsa::gadgets::biguint::{BigUintTarget, CircuitBuilderBiguint};
+use plonky2_ecdsa::gadgets::curve::{AffinePointTarget, CircuitBuilderCurve};
+use plonky2_ecdsa::gadgets::curve_msm::curve_msm_circuit;
+use plonky2_ecdsa::gadgets::curve_windowed_mul::CircuitBuilderWindowedMul;
+use plonky2_ecdsa::gadgets::nonnative::{CircuitBuilderNonNative, NonNativeTarget};
+use plonky2_ecdsa::gadgets::split_nonnative::CircuitBuilderSplit;
+use plonky2_u32::gadgets::arithmetic_u32::CircuitBuilderU32;
+use plonky2_u32::gadgets::range_check::range_check_u32_circuit;
+
+const D: usize = 2;
+type C = PoseidonGoldilocksConfig;
+type F = <C as GenericConfig<D>>::F;
+
+type Point = AffinePointTarget<Secp256K1>;
+
+#[derive(Clone, Copy)]
+enum Build {
+    Baseline,
+    Optimised,
+}
+
+type Case = (&'static str, fn(&mut CircuitBuilder<F, D>, Build));
+
+const CASES: [Case; 5] = [
+    ("curve_add (secp256k1)", curve_add),
+    ("curve_double (secp256k1)", curve_double),
+    // The two-scalar multiplication that ECDSA verification is built on, like `curve_muladd_2`.
+    ("curve_msm_circuit (secp256k1)", curve_msm),
+    ("mul_biguint, 64 x 64 limbs", mul_biguint_2048),
+    ("x^65537 mod n, 2048-bit n", modpow_2048),
+];
+
+fn curve_add(builder: &mut CircuitBuilder<F, D>, build: Build) {
+    let p = builder.add_virtual_affine_point_target();
+    let q = builder.add_virtual_affine_point_target();
+    add(builder, build, &p, &q);
+}
+
+fn curve_double(builder: &mut CircuitBuilder<F, D>, build: Build) {
+    let p = builder.add_virtual_affine_point_target();
+    double(builder, build, &p);
+}
+
+fn curve_msm(builder: &mut CircuitBuilder<F, D>, build: Build) {
+    let p = builder.add_virtual_affine_point_target();
+    let q = builder.add_virtual_affine_point_target();
+    let n = builder.add_virtual_nonnative_target::<Secp256K1Scalar>();
+    let m = builder.add_virtual_nonnative_target::<Secp256K1Scalar>();
+    match build {
+        Build::Baseline => msm_reduced(builder, &p, &q, &n, &m),
+        Build::Optimised => curve_msm_circuit(builder, &p, &q, &n, &m),
+    };
+}
+
+fn mul_biguint_2048(builder: &mut CircuitBuilder<F, D>, build: Build) {
+    let a = builder.add_virtual_biguint_target(64);
+    let b = builder.add_virtual_biguint_target(64);
+    mul(builder, build, &a, &b);
+}
+
+fn modpow_2048(builder: &mut CircuitBuilder<F, D>, build: Build) {
+    let x = builder.add_virtual_biguint_target(64);
+    let n = builder.add_virtual_biguint_target(64);
+    let exponent = BigUint::from(65537u32);
+    match build {
+        Build::Baseline => {
+            // square-and-multiply as in `modpow_biguint_const_exponent`
+            let mut result = x.clone();
+            for i in (0..exponent.bits() - 1).rev() {
+                result = mul_mod_schoolbook(builder, &result, &result, &n);
+                if exponent.bit(i) {
+                    result = mul_mod_schoolbook(builder, &result, &x, &n);
+                }
+            }
+        }
+        Build::Optimised => {
+            builder.modpow_biguint_const_exponent(&x, &exponent, &n);
+        }
+    }
+}
+
+fn add(builder: &mut CircuitBuilder<F, D>, build: Build, p1: &Point, p2: &Point) -> Point {
+    let Build::Baseline = build else {
+        return builder.curve_add(p1, p2);
+    };
+
+    let u = builder.sub_nonnative(&p2.y, &p1.y);
+    let v = builder.sub_nonnative(&p2.x, &p1.x);
+    let v_inv = builder.inv_nonnative(&v);
+    let s = builder.mul_nonnative(&u, &v_inv);
+    let s_squared = builder.mul_nonnative(&s, &s);
+    let x_sum = builder.add_nonnative(&p2.x, &p1.x);
+    let x3 = builder.sub_nonnative(&s_squared, &x_sum);
+    let x_diff = builder.sub_nonnative(&p1.x, &x3);
+    let prod = builder.mul_nonnative(&s, &x_diff);
+    let y3 = builder.sub_nonnative(&prod, &p1.y);
+
+    AffinePointTarget { x: x3, y: y3 }
+}
+
+fn double(builder: &mut CircuitBuilder<F, D>, build: Build, p: &Point) -> Point {
+    let Build::Baseline = build else {
+        return builder.curve_double(p);
+    };
+
+    let AffinePointTarget { x, y } = p;
+    let double_y = builder.add_nonnative(y, y);
+    let inv_double_y = builder.inv_nonnative(&double_y);
+    let x_squared = builder.mul_nonnative(x, x);
+    let double_x_squared = builder.add_nonnative(&x_squared, &x_squared);
+    let triple_x_squared = builder.add_nonnative(&double_x_squared, &x_squared);
+
+    let a = builder.constant_nonnative(Secp256K1::A);
+    let triple_xx_a = builder.add_nonnative(&triple_x_squared, &a);
+    let lambda = builder.mul_nonnative(&triple_xx_a, &inv_double_y);
+    let lambda_squared = builder.mul_nonnative(&lambda, &lambda);
+    let x_double = builder.add_nonnative(x, x);
+    let x3 = builder.sub_nonnative(&lambda_squared, &x_double);
+
+    let x_diff = builder.sub_nonnative(x, &x3);
+    let lambda_x_diff = builder.mul_nonnative(&lambda, &x_diff);
+    let y3 = builder.sub_nonnative(&lambda_x_diff, y);
+
+    AffinePointTarget { x: x3, y: y3 }
+}
+
+fn conditional_add_reduced(
+    builder: &mut CircuitBuilder<F, D>,
+    p1: &Point,
+    p2: &Point,
+    b: BoolTarget,
+) -> Point {
+    let not_b = builder.not(b);
+    let sum = add(builder, Build::Baseline, p1, p2);
+    let x_if_true = builder.mul_nonnative_by_bool(&sum.x, b);
+    let y_if_true = builder.mul_nonnative_by_bool(&sum.y, b);
+    let x_if_false = builder.mul_nonnative_by_bool(&p1.x, not_b);
+    let y_if_false = builder.mul_nonnative_by_bool(&p1.y, not_b);
+
+    let x = builder.add_nonnative(&x_if_true, &x_if_false);
+    let y = builder.add_nonnative(&y_if_true, &y_if_false);
+
+    AffinePointTarget { x, y }
+}
+
+/// `curve_msm_circuit` with every curve operation reduced after each step.
+fn msm_reduced(
+    builder: &mut CircuitBuilder<F, D>,
+    p: &Point,
+    q: &Point,
+    n: &NonNativeTarget<Secp256K1Scalar>,
+    m: &NonNativeTarget<Secp256K1Scalar>,
+) -> Point {
+    let limbs_n = builder.split_nonnative_to_2_bit_limbs(n);
+    let limbs_m = builder.split_nonnative_to_2_bit_limbs(m);
+    let num_limbs = limbs_n.len();
+
+    let hash_0 = KeccakHash::<32>::hash_no_pad(&[F::ZERO]);
+    let hash_0_scalar = Secp256K1Scalar::from_noncanonical_biguint(BigUint::from_bytes_le(
+        &GenericHashOut::<F>::to_bytes(&hash_0),
+    ));
+    let rando = (CurveScalar(hash_0_scalar) * Secp256K1::GENERATOR_PROJECTIVE).to_affine();
+    let rando_t = builder.constant_affine_point(rando);
+    let neg_rando = builder.constant_affine_point(-rando);
+
+    let mut precomputation = vec![p.clone(); 16];
+    let mut cur_p = rando_t.clone();
+    let mut cur_q = rando_t.clone();
+    for i in 0..4 {
+        precomputation[i] = cur_p.clone();
+        precomputation[4 * i] = cur_q.clone();
+        cur_p = add(builder, Build::Baseline, &cur_p, p);
+        cur_q = add(builder, Build::Baseline, &cur_q, q);
+    }
+    for i in 1..4 {
+        precomputation[i] = add(builder, Build::Baseline, &precomputation[i], &neg_rando);
+        precomputation[4 * i] = add(builder, Build::Baseline, &precomputation[4 * i], &neg_rando);
+    }
+    for i in 1..4 {
+        for j in 1..4 {
+            precomputation[i + 4 * j] = add(
+                builder,
+                Build::Baseline,
+                &precomputation[i],
+                &precomputation[4 * j],
+            );
+        }
+    }
+
+    let four = builder.constant(F::from_canonical_usize(4));
+
+    let zero = builder.zero();
+    let mut result = rando_t;
+    for (limb_n, limb_m) in limbs_n.into_iter().zip(limbs_m).rev() {
+        result = double(builder, Build::Baseline, &result);
+        result = double(builder, Build::Baseline, &result);
+        let index = builder.mul_add(four, limb_m, limb_n);
+        let r = builder.random_access_curve_points(index, precomputation.clone());
+        let is_zero = builder.is_equal(index, zero);
+        let should_add = builder.not(is_zero);
+        result = conditional_add_reduced(builder, &result, &r, should_add);
+    }
+    let starting_point_multiplied = (0..2 * num_limbs).fold(rando, |acc, _| acc.double());
+    let to_add = builder.constant_affine_point(-starting_point_multiplied);
+    add(builder, Build::Baseline, &result, &to_add)
+}
+
+fn mul(
+    builder: &mut CircuitBuilder<F, D>,
+    build: Build,
+    a: &BigUintTarget,
+    b: &BigUintTarget,
+) -> BigUintTarget {
+    let Build::Baseline = build else {
+        return builder.mul_biguint(a, b);
+    };
+
+    let mut to_add = vec![vec![]; a.num_limbs() + b.num_limbs()];
+    for (i, &x) in a.limbs.iter().enumerate() {
+        for (j, &y) in b.limbs.iter().enumerate() {
+            let (product, carry) = builder.mul_u32(x, y);
+            to_add[i + j].push(product);
+            to_add[i + j + 1].push(carry);
+        }
+    }
+
+    // `U32AddManyGate` takes at most 16 addends, as in `mul_biguint`.
+    for k in 0..to_add.len() {
+        while to_add[k].len() > 16 {
+            let chunk: Vec<_> = to_add[k].drain(..16).collect();
+            let (sum, carry) = builder.add_many_u32(&chunk);
+            to_add[k].push(sum);
+            to_add[k + 1].push(carry);
+        }
+    }
+
+    let mut limbs = vec![];
+    let mut carry = builder.zero_u32();
+    for summands in &to_add {
+        let (sum, new_carry) = builder.add_u32s_with_carry(summands, carry);
+        limbs.push(sum);
+        carry = new_carry;
+    }
+    limbs.push(carry);
+
+    BigUintTarget { limbs }
+}
+
+/// `mul_mod_biguint` with schoolbook products. The quotient and remainder have no generator, as
+/// the circuit is only built to count its gates.
+fn mul_mod_schoolbook(
+    builder: &mut CircuitBuilder<F, D>,
+    a: &BigUintTarget,
+    b: &BigUintTarget,
+    modulus: &BigUintTarget,
+) -> BigUintTarget {
+    let prod = mul(builder, Build::Baseline, a, b);
+    let div = builder.add_virtual_biguint_target(prod.num_limbs() - modulus.num_limbs() + 1);
+    let rem = builder.add_virtual_biguint_target(modulus.num_limbs());
+
+    let div_modulus = mul(builder, Build::Baseline, &div, modulus);
+    let div_modulus_plus_rem = builder.add_biguint(&div_modulus, &rem);
+    builder.connect_biguint(&prod, &div_modulus_plus_rem);
+
+    let cmp_rem_modulus = builder.cmp_biguint(&rem, modulus);
+    builder.assert_one(cmp_rem_modulus.target);
+    range_check_u32_circuit(builder, div.limbs);
+
+    rem
+}
+
+fn main() {
+    println!("{:<34} {:>8} {:>9}", "", "baseline", "optimised");
+    for (name, build_case) in CASES {
+        let [baseline, optimised] = [Build::Baseline, Build::Optimised].map(|build| {
+            let mut builder = CircuitBuilder::<F, D>::new(CircuitConfig::standard_ecc_config());
+            build_case(&mut builder, build);
+            builder.num_gates()
+        });
+        let change = 100.0 * (optimised as f64 - baseline as f64) / baseline as f64;
+        println!("{name:<34} {baseline:>8} {optimised:>9} ({change:+.1}%)");
+    }
+}
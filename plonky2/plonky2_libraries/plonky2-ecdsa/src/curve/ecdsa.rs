@@ -48,6 +48,10 @@ pub fn verify_message<C: Curve>(
 
     assert!(pk.0.is_valid());
 
+    if r.is_zero() || s.is_zero() {
+        return false;
+    }
+
     let c = s.inverse();
     let u1 = msg * c;
     let u2 = r * c;
@@ -56,6 +60,9 @@ pub fn verify_message<C: Curve>(
     let w = 5; // Experimentally fastest
     let point_proj = msm_parallel(&[u1, u2], &[g, pk.0.to_projective()], w);
     let point = point_proj.to_affine();
+    if point.zero {
+        return false;
+    }
 
     let x = base_to_scalar::<C>(point.x);
     r == x
@@ -63,10 +70,13 @@ pub fn verify_message<C: Curve>(
 
 #[cfg(test)]
 mod tests {
+    use plonky2::field::p256_scalar::P256Scalar;
     use plonky2::field::secp256k1_scalar::Secp256K1Scalar;
-    use plonky2::field::types::Sample;
+    use plonky2::field::types::{Field, Sample};
 
-    use crate::curve::ecdsa::{sign_message, verify_message, ECDSASecretKey};
+    use crate::curve::ecdsa::{sign_message, verify_message, ECDSASecretKey, ECDSASignature};
+    use crate::curve::p256::P256;
+    use crate::curve::p256_test_vectors::{p256_public_key, P256_SHA256_VECTORS};
     use crate::curve::secp256k1::Secp256K1;
 
     #[test]
@@ -81,4 +91,46 @@ mod tests {
         let result = verify_message(msg, sig, pk);
         assert!(result);
     }
+
+    #[test]
+    fn test_ecdsa_native_p256() {
+        type C = P256;
+
+        let msg = P256Scalar::rand();
+        let sk = ECDSASecretKey::<C>(P256Scalar::rand());
+        let pk = sk.to_public();
+
+        let sig = sign_message(msg, sk);
+        assert!(verify_message(msg, sig, pk));
+        assert!(!verify_message(msg + P256Scalar::ONE, sig, pk));
+    }
+
+    #[test]
+    fn test_ecdsa_native_zero_signature() {
+        type C = P256;
+
+        let msg = P256Scalar::rand();
+        let pk = ECDSASecretKey::<C>(P256Scalar::rand()).to_public();
+        let sig = sign_message(msg, ECDSASecretKey::<C>(P256Scalar::rand()));
+
+        let zero_r = ECDSASignature { r: P256Scalar::ZERO, s: sig.s };
+        let zero_s = ECDSASignature { r: sig.r, s: P256Scalar::ZERO };
+        assert!(!verify_message(msg, zero_r, pk));
+        assert!(!verify_message(msg, zero_s, pk));
+    }
+
+    #[test]
+    fn test_ecdsa_p256_test_vectors() {
+        for v in P256_SHA256_VECTORS.iter() {
+            let pk = p256_public_key(v.key);
+            let result = v
+                .signature()
+                .is_some_and(|sig| verify_message(v.msg_scalar(), sig, pk));
+            assert_eq!(
+                result, v.valid,
+                "tcId {}: {} (msg {:?})",
+                v.tc_id, v.comment, v.msg
+            );
+        }
+    }
 }
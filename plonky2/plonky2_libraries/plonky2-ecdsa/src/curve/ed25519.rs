@@ -0,0 +1,169 @@
+use num::BigUint;
+use plonky2::field::ed25519_base::Ed25519Base;
+use plonky2::field::ed25519_scalar::Ed25519Scalar;
+use plonky2::field::types::{Field, PrimeField};
+use serde::{Deserialize, Serialize};
+
+use crate::curve::twisted_edwards::{EdwardsPoint, TwistedEdwardsCurve};
+
+/// The twisted Edwards form of Curve25519 used by Ed25519, `-x^2 + y^2 = 1 + d * x^2 * y^2`.
+#[derive(Debug, Copy, Clone, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub struct Ed25519;
+
+impl TwistedEdwardsCurve for Ed25519 {
+    type BaseField = Ed25519Base;
+    type ScalarField = Ed25519Scalar;
+
+    const A: Ed25519Base = Ed25519Base::NEG_ONE;
+    // -121665 / 121666
+    // 37095705934669439343138083508754565189542113879843219016388785533085940283555
+    const D: Ed25519Base = Ed25519Base([
+        0x75EB4DCA135978A3,
+        0x00700A4D4141D8AB,
+        0x8CC740797779E898,
+        0x52036CEE2B6FFE73,
+    ]);
+    const GENERATOR: EdwardsPoint<Self> = EdwardsPoint {
+        x: ED25519_GENERATOR_X,
+        y: ED25519_GENERATOR_Y,
+    };
+    const COFACTOR: u64 = 8;
+}
+
+/// 15112221349535400772501151409588531511454012693041857206046113283949847762202
+const ED25519_GENERATOR_X: Ed25519Base = Ed25519Base([
+    0xC9562D608F25D51A,
+    0x692CC7609525A7B2,
+    0xC0A4E231FDD6DC5C,
+    0x216936D3CD6E53FE,
+]);
+
+/// 46316835694926478169428394003475163141307993866256225615783033603165251855960 (= 4/5)
+const ED25519_GENERATOR_Y: Ed25519Base = Ed25519Base([
+    0x6666666666666658,
+    0x6666666666666666,
+    0x6666666666666666,
+    0x6666666666666666,
+]);
+
+/// Encodes a point as in RFC 8032: the little-endian `y` coordinate, with the lowest bit of `x`
+/// stored in the most significant bit.
+pub fn compress_point(point: &EdwardsPoint<Ed25519>) -> [u8; 32] {
+    let mut bytes = [0u8; 32];
+    let y_bytes = point.y.to_canonical_biguint().to_bytes_le();
+    bytes[..y_bytes.len()].copy_from_slice(&y_bytes);
+    if point.x.to_canonical_biguint().bit(0) {
+        bytes[31] |= 0x80;
+    }
+    bytes
+}
+
+/// Decodes a point encoded as in RFC 8032, rejecting non-canonical `y` coordinates.
+pub fn decompress_point(bytes: &[u8; 32]) -> Option<EdwardsPoint<Ed25519>> {
+    let x_is_odd = bytes[31] >> 7 == 1;
+    let mut y_bytes = *bytes;
+    y_bytes[31] &= 0x7F;
+    let y_biguint = BigUint::from_bytes_le(&y_bytes);
+    if y_biguint >= Ed25519Base::order() {
+        return None;
+    }
+    let y = Ed25519Base::from_noncanonical_biguint(y_biguint);
+
+    // x^2 = (y^2 - 1) / (d * y^2 + 1); the denominator never vanishes since d is not a square.
+    let yy = y * y;
+    let xx = (yy - Ed25519Base::ONE) / (Ed25519::D * yy + Ed25519Base::ONE);
+    let mut x = xx.sqrt()?;
+    if x.is_zero() && x_is_odd {
+        return None;
+    }
+    if x.to_canonical_biguint().bit(0) != x_is_odd {
+        x = -x;
+    }
+
+    Some(EdwardsPoint::new(x, y))
+}
+
+#[cfg(test)]
+mod tests {
+    use num::BigUint;
+    use plonky2::field::ed25519_base::Ed25519Base;
+    use plonky2::field::ed25519_scalar::Ed25519Scalar;
+    use plonky2::field::types::{Field, PrimeField, Sample};
+
+    use crate::curve::ed25519::{compress_point, decompress_point, Ed25519};
+    use crate::curve::twisted_edwards::{EdwardsPoint, TwistedEdwardsCurve};
+
+    #[test]
+    fn test_generator() {
+        let g = Ed25519::GENERATOR;
+        assert!(g.is_valid());
+        assert!((-g).is_valid());
+        assert_eq!(Ed25519::A, -Ed25519Base::ONE);
+        assert_eq!(
+            Ed25519::D * Ed25519Base::from_canonical_u64(121666),
+            -Ed25519Base::from_canonical_u64(121665)
+        );
+        assert!(!Ed25519::D.is_quadratic_residue());
+    }
+
+    #[test]
+    fn test_generator_order() {
+        let g = Ed25519::GENERATOR;
+        let neg_g = g.mul_scalar(Ed25519Scalar::NEG_ONE);
+        assert_eq!(neg_g, -g);
+        assert!((neg_g + g).is_identity());
+        assert!(g.mul_biguint(&Ed25519Scalar::order()).is_identity());
+    }
+
+    #[test]
+    fn test_complete_addition() {
+        let g = Ed25519::GENERATOR;
+        let identity = EdwardsPoint::<Ed25519>::IDENTITY;
+        assert_eq!(g + identity, g);
+        assert_eq!(g + g, g.double());
+        assert_eq!(identity.double(), identity);
+
+        // A point of order 4, which the incomplete Weierstrass formulas could not handle.
+        let t =
+            EdwardsPoint::<Ed25519>::new(Ed25519Base::NEG_ONE.sqrt().unwrap(), Ed25519Base::ZERO);
+        assert_eq!(
+            t.double(),
+            EdwardsPoint::new(Ed25519Base::ZERO, Ed25519Base::NEG_ONE)
+        );
+        assert!(t.double().double().is_identity());
+    }
+
+    #[test]
+    fn test_scalar_multiplication() {
+        let g = Ed25519::GENERATOR;
+        let ten = Ed25519Scalar::from_canonical_u64(10);
+        let sum = g + g + g + g + g + g + g + g + g + g;
+        assert_eq!(g.mul_scalar(ten), sum);
+
+        let a = Ed25519Scalar::rand();
+        let b = Ed25519Scalar::rand();
+        assert_eq!(g.mul_scalar(a) + g.mul_scalar(b), g.mul_scalar(a + b));
+        assert_eq!(g.mul_scalar(a).mul_scalar(b), g.mul_scalar(a * b));
+    }
+
+    #[test]
+    fn test_compression() {
+        // The RFC 8032 encoding of the base point.
+        let mut expected = [0x66u8; 32];
+        expected[0] = 0x58;
+        assert_eq!(compress_point(&Ed25519::GENERATOR), expected);
+
+        for _ in 0..8 {
+            let p = Ed25519::GENERATOR.mul_scalar(Ed25519Scalar::rand());
+            assert_eq!(decompress_point(&compress_point(&p)), Some(p));
+            assert_eq!(decompress_point(&compress_point(&-p)), Some(-p));
+        }
+
+        // y = p is not a canonical encoding of y = 0.
+        let mut non_canonical = [0xFFu8; 32];
+        non_canonical[0] = 0xED;
+        non_canonical[31] = 0x7F;
+        assert_eq!(BigUint::from_bytes_le(&non_canonical), Ed25519Base::order());
+        assert_eq!(decompress_point(&non_canonical), None);
+    }
+}
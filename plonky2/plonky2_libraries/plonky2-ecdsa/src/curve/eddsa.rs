@@ -0,0 +1,187 @@
+use num::BigUint;
+use plonky2::field::ed25519_scalar::Ed25519Scalar;
+use plonky2::field::types::{Field, PrimeField};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha512};
+
+use crate::curve::ed25519::{compress_point, decompress_point, Ed25519};
+use crate::curve::twisted_edwards::{EdwardsPoint, TwistedEdwardsCurve};
+
+/// An Ed25519 signature `(R, S)`, as defined in RFC 8032.
+#[derive(Copy, Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub struct EdDSASignature {
+    pub r: EdwardsPoint<Ed25519>,
+    pub s: Ed25519Scalar,
+}
+
+impl EdDSASignature {
+    pub fn to_bytes(&self) -> [u8; 64] {
+        let mut bytes = [0u8; 64];
+        bytes[..32].copy_from_slice(&compress_point(&self.r));
+        let s_bytes = self.s.to_canonical_biguint().to_bytes_le();
+        bytes[32..32 + s_bytes.len()].copy_from_slice(&s_bytes);
+        bytes
+    }
+
+    /// Decodes a signature, rejecting invalid points and non-canonical `S`.
+    pub fn from_bytes(bytes: &[u8; 64]) -> Option<Self> {
+        let r = decompress_point(bytes[..32].try_into().unwrap())?;
+        let s = BigUint::from_bytes_le(&bytes[32..]);
+        if s >= Ed25519Scalar::order() {
+            return None;
+        }
+        Some(Self {
+            r,
+            s: Ed25519Scalar::from_noncanonical_biguint(s),
+        })
+    }
+}
+
+/// An Ed25519 secret key, i.e. the 32-byte seed from which the signing scalar is derived.
+#[derive(Copy, Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub struct EdDSASecretKey(pub [u8; 32]);
+
+impl EdDSASecretKey {
+    /// Returns the clamped secret scalar and the nonce prefix.
+    fn expand(&self) -> (BigUint, [u8; 32]) {
+        let h = Sha512::digest(self.0);
+        let mut scalar = [0u8; 32];
+        scalar.copy_from_slice(&h[..32]);
+        scalar[0] &= 0xF8;
+        scalar[31] &= 0x7F;
+        scalar[31] |= 0x40;
+        let mut prefix = [0u8; 32];
+        prefix.copy_from_slice(&h[32..]);
+        (BigUint::from_bytes_le(&scalar), prefix)
+    }
+
+    pub fn to_public(&self) -> EdDSAPublicKey {
+        let (a, _) = self.expand();
+        EdDSAPublicKey(Ed25519::GENERATOR.mul_biguint(&a))
+    }
+}
+
+#[derive(Copy, Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub struct EdDSAPublicKey(pub EdwardsPoint<Ed25519>);
+
+impl EdDSAPublicKey {
+    pub fn to_bytes(&self) -> [u8; 32] {
+        compress_point(&self.0)
+    }
+
+    pub fn from_bytes(bytes: &[u8; 32]) -> Option<Self> {
+        decompress_point(bytes).map(Self)
+    }
+}
+
+/// Interprets a 64-byte SHA-512 digest as a little-endian integer modulo the group order.
+fn hash_to_scalar(digest: &[u8]) -> Ed25519Scalar {
+    Ed25519Scalar::from_noncanonical_biguint(BigUint::from_bytes_le(digest))
+}
+
+/// The Ed25519 challenge `k = SHA-512(R || A || M) mod L`.
+pub fn challenge(r: &EdwardsPoint<Ed25519>, pk: &EdDSAPublicKey, msg: &[u8]) -> Ed25519Scalar {
+    let mut hasher = Sha512::new();
+    hasher.update(compress_point(r));
+    hasher.update(pk.to_bytes());
+    hasher.update(msg);
+    hash_to_scalar(&hasher.finalize())
+}
+
+pub fn sign_message(msg: &[u8], sk: EdDSASecretKey) -> EdDSASignature {
+    let (a, prefix) = sk.expand();
+    let pk = EdDSAPublicKey(Ed25519::GENERATOR.mul_biguint(&a));
+
+    let mut hasher = Sha512::new();
+    hasher.update(prefix);
+    hasher.update(msg);
+    let r = hash_to_scalar(&hasher.finalize());
+    let rr = Ed25519::GENERATOR.mul_scalar(r);
+
+    let k = challenge(&rr, &pk, msg);
+    let s = r + k * Ed25519Scalar::from_noncanonical_biguint(a);
+
+    EdDSASignature { r: rr, s }
+}
+
+/// Checks the cofactorless equation `[S]B = R + [k]A`, which is also what the circuit enforces.
+pub fn verify_message(msg: &[u8], sig: EdDSASignature, pk: EdDSAPublicKey) -> bool {
+    let EdDSASignature { r, s } = sig;
+
+    assert!(pk.0.is_valid());
+
+    let k = challenge(&r, &pk, msg);
+    Ed25519::GENERATOR.mul_scalar(s) == r + pk.0.mul_scalar(k)
+}
+
+#[cfg(test)]
+mod tests {
+    use plonky2::field::ed25519_scalar::Ed25519Scalar;
+    use plonky2::field::types::Field;
+    use rand::rngs::OsRng;
+    use rand::RngCore;
+
+    use crate::curve::ed25519_test_vectors::{decode_hex, RFC8032_VECTORS};
+    use crate::curve::eddsa::{
+        sign_message, verify_message, EdDSAPublicKey, EdDSASecretKey, EdDSASignature,
+    };
+
+    fn random_secret_key() -> EdDSASecretKey {
+        let mut seed = [0u8; 32];
+        OsRng.fill_bytes(&mut seed);
+        EdDSASecretKey(seed)
+    }
+
+    #[test]
+    fn test_eddsa_native() {
+        let sk = random_secret_key();
+        let pk = sk.to_public();
+        let msg = b"firmware manifest";
+
+        let sig = sign_message(msg, sk);
+        assert!(verify_message(msg, sig, pk));
+        assert!(!verify_message(b"firmware manifesto", sig, pk));
+
+        let forged = EdDSASignature {
+            r: sig.r,
+            s: sig.s + Ed25519Scalar::ONE,
+        };
+        assert!(!verify_message(msg, forged, pk));
+
+        let other_pk = random_secret_key().to_public();
+        assert!(!verify_message(msg, sig, other_pk));
+
+        let bytes = sig.to_bytes();
+        assert_eq!(EdDSASignature::from_bytes(&bytes), Some(sig));
+        assert_eq!(EdDSAPublicKey::from_bytes(&pk.to_bytes()), Some(pk));
+    }
+
+    #[test]
+    fn test_eddsa_rfc8032_vectors() {
+        for v in RFC8032_VECTORS {
+            let sk = EdDSASecretKey(decode_hex(v.secret_key).try_into().unwrap());
+            let pk = sk.to_public();
+            assert_eq!(pk.to_bytes().to_vec(), decode_hex(v.public_key));
+
+            let msg = decode_hex(v.msg);
+            let sig = sign_message(&msg, sk);
+            assert_eq!(sig.to_bytes().to_vec(), decode_hex(v.signature));
+
+            let decoded = EdDSASignature::from_bytes(&sig.to_bytes()).unwrap();
+            assert!(verify_message(&msg, decoded, pk));
+        }
+    }
+
+    #[test]
+    fn test_eddsa_rejects_non_canonical_s() {
+        let v = &RFC8032_VECTORS[0];
+        let mut bytes: [u8; 64] = decode_hex(v.signature).try_into().unwrap();
+        assert!(EdDSASignature::from_bytes(&bytes).is_some());
+
+        // S + L still verifies under a naive check, but must be rejected as malleable.
+        let s = num::BigUint::from_bytes_le(&bytes[32..]) + Ed25519Scalar::order();
+        let s_bytes = s.to_bytes_le();
+        bytes[32..32 + s_bytes.len()].copy_from_slice(&s_bytes);
+        assert!(EdDSASignature::from_bytes(&bytes).is_none());
+    }
+}
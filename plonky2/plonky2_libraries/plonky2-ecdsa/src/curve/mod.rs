@@ -4,5 +4,14 @@ pub mod curve_multiplication;
 pub mod curve_summation;
 pub mod curve_types;
 pub mod ecdsa;
+pub mod ed25519;
+pub mod eddsa;
 pub mod glv;
+pub mod p256;
 pub mod secp256k1;
+pub mod twisted_edwards;
+
+#[cfg(test)]
+pub(crate) mod ed25519_test_vectors;
+#[cfg(test)]
+pub(crate) mod p256_test_vectors;
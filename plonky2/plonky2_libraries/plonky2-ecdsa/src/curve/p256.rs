@@ -0,0 +1,120 @@
+use plonky2::field::p256_base::P256Base;
+use plonky2::field::p256_scalar::P256Scalar;
+use serde::{Deserialize, Serialize};
+
+use crate::curve::curve_types::{AffinePoint, Curve};
+
+/// The NIST P-256 curve, also known as secp256r1 or prime256v1.
+#[derive(Debug, Copy, Clone, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub struct P256;
+
+impl Curve for P256 {
+    type BaseField = P256Base;
+    type ScalarField = P256Scalar;
+
+    // -3
+    const A: P256Base = P256Base([
+        0xFFFFFFFFFFFFFFFC,
+        0x00000000FFFFFFFF,
+        0x0000000000000000,
+        0xFFFFFFFF00000001,
+    ]);
+    // 41058363725152142129326129780047268409114441015993725554835256314039467401291
+    const B: P256Base = P256Base([
+        0x3BCE3C3E27D2604B,
+        0x651D06B0CC53B0F6,
+        0xB3EBBD55769886BC,
+        0x5AC635D8AA3A93E7,
+    ]);
+    const GENERATOR_AFFINE: AffinePoint<Self> = AffinePoint {
+        x: P256_GENERATOR_X,
+        y: P256_GENERATOR_Y,
+        zero: false,
+    };
+}
+
+/// 48439561293906451759052585252797914202762949526041747995844080717082404635286
+const P256_GENERATOR_X: P256Base = P256Base([
+    0xF4A13945D898C296,
+    0x77037D812DEB33A0,
+    0xF8BCE6E563A440F2,
+    0x6B17D1F2E12C4247,
+]);
+
+/// 36134250956749795798585127919587881956611106672985015071877198253568414405109
+const P256_GENERATOR_Y: P256Base = P256Base([
+    0xCBB6406837BF51F5,
+    0x2BCE33576B315ECE,
+    0x8EE7EB4A7C0F9E16,
+    0x4FE342E2FE1A7F9B,
+]);
+
+#[cfg(test)]
+mod tests {
+    use num::BigUint;
+    use plonky2::field::p256_base::P256Base;
+    use plonky2::field::p256_scalar::P256Scalar;
+    use plonky2::field::types::{Field, PrimeField};
+
+    use crate::curve::curve_types::{AffinePoint, Curve, ProjectivePoint};
+    use crate::curve::p256::P256;
+
+    #[test]
+    fn test_generator() {
+        let g = P256::GENERATOR_AFFINE;
+        assert!(g.is_valid());
+        assert!(P256::is_safe_curve());
+        assert_eq!(P256::A, -P256Base::from_canonical_u64(3));
+
+        let neg_g = AffinePoint::<P256> {
+            x: g.x,
+            y: -g.y,
+            zero: g.zero,
+        };
+        assert!(neg_g.is_valid());
+    }
+
+    #[test]
+    fn test_generator_order() {
+        let g = P256::GENERATOR_PROJECTIVE;
+        let neg_g = P256::convert(P256Scalar::NEG_ONE) * g;
+        assert_eq!(neg_g, -g);
+        assert_eq!(neg_g + g, ProjectivePoint::ZERO);
+    }
+
+    #[test]
+    fn test_naive_multiplication() {
+        let g = P256::GENERATOR_PROJECTIVE;
+        let ten = P256Scalar::from_canonical_u64(10);
+        let product = mul_naive(ten, g);
+        let sum = g + g + g + g + g + g + g + g + g + g;
+        assert_eq!(product, sum);
+    }
+
+    #[test]
+    fn test_g1_multiplication() {
+        let lhs = P256Scalar::from_noncanonical_biguint(BigUint::from_slice(&[
+            1111, 2222, 3333, 4444, 5555, 6666, 7777, 8888,
+        ]));
+        assert_eq!(
+            P256::convert(lhs) * P256::GENERATOR_PROJECTIVE,
+            mul_naive(lhs, P256::GENERATOR_PROJECTIVE)
+        );
+    }
+
+    /// A simple, somewhat inefficient implementation of multiplication which is used as a reference
+    /// for correctness.
+    fn mul_naive(lhs: P256Scalar, rhs: ProjectivePoint<P256>) -> ProjectivePoint<P256> {
+        let mut g = rhs;
+        let mut sum = ProjectivePoint::ZERO;
+        for limb in lhs.to_canonical_biguint().to_u64_digits().iter() {
+            for j in 0..64 {
+                if (limb >> j & 1u64) != 0u64 {
+                    sum = sum + g;
+                }
+                g = g.double();
+            }
+        }
+        sum
+    }
+}
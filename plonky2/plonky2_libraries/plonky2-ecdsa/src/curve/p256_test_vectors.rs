@@ -0,0 +1,254 @@
+//! ECDSA P-256 / SHA-256 test vectors in the style of Project Wycheproof.
+//!
+//! Valid signatures were produced with OpenSSL from fixed keys; the invalid ones are
+//! modifications of them. Every verdict was cross-checked against OpenSSL.
+
+use num::{BigUint, Num};
+use plonky2::field::p256_base::P256Base;
+use plonky2::field::p256_scalar::P256Scalar;
+use plonky2::field::types::Field;
+
+use crate::curve::curve_types::AffinePoint;
+use crate::curve::ecdsa::{ECDSAPublicKey, ECDSASignature};
+use crate::curve::p256::P256;
+
+pub(crate) struct EcdsaTestVector {
+    pub tc_id: usize,
+    pub comment: &'static str,
+    /// Index into `P256_PUBLIC_KEYS`.
+    pub key: usize,
+    pub msg: &'static str,
+    /// SHA-256 of `msg`, big-endian hex.
+    pub hash: &'static str,
+    pub r: &'static str,
+    pub s: &'static str,
+    pub valid: bool,
+}
+
+/// Uncompressed public keys `(x, y)`, big-endian hex.
+pub(crate) const P256_PUBLIC_KEYS: [(&str, &str); 2] = [
+    (
+        "60fed4ba255a9d31c961eb74c6356d68c049b8923b61fa6ce669622e60f29fb6",
+        "7903fe1008b8bc99a41ae9e95628bc64f2f1b20c2d7e9f5177a3c294d4462299",
+    ),
+    (
+        "e266ddfdc12668db30d4ca3e8f7749432c416044f2d2b8c10bf3d4012aeffa8a",
+        "bfa86404a2e9ffe67d47c587ef7a97a7f456b863b4d02cfc6928973ab5b1cb39",
+    ),
+];
+
+pub(crate) const P256_SHA256_VECTORS: [EcdsaTestVector; 18] = [
+    EcdsaTestVector {
+        tc_id: 1,
+        comment: "valid signature",
+        key: 0,
+        msg: "",
+        hash: "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855",
+        r: "8189e99ceae980dec5eddc03945145ba5bf73b50d954a7aed7f58cfb1258794e",
+        s: "86b6fa22babff5e62fc14dd3849bcc1a38ae977ad718ead8d23454a5a6dbbe18",
+        valid: true,
+    },
+    EcdsaTestVector {
+        tc_id: 2,
+        comment: "valid signature",
+        key: 0,
+        msg: "sample",
+        hash: "af2bdbe1aa9b6ec1e2ade1d694f41fc71a831d0268e9891562113d8a62add1bf",
+        r: "1c1e6d0a5f307220597b173233bdf6ef3a07b3fe0bc7aa46f786e128a145984f",
+        s: "1dfe83343142223b2d0bbc63f03ccde4d5a9ca348d25feaf4cb568491a74799b",
+        valid: true,
+    },
+    EcdsaTestVector {
+        tc_id: 3,
+        comment: "valid signature",
+        key: 0,
+        msg: "test",
+        hash: "9f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08",
+        r: "c707890a307b21c465375aee04426a2101266aa0a5c3b4f883af82c9ce3d6c19",
+        s: "8f0e1db85df54bc8991ab2c8cf004c10bb3f8349d92f0c04df016357ce790028",
+        valid: true,
+    },
+    EcdsaTestVector {
+        tc_id: 4,
+        comment: "valid signature",
+        key: 0,
+        msg: "piranhas device attestation",
+        hash: "ec4ac7235adcb2159bf2a903ca3eed80a3ae27e3781215b7f05dab1982ac9da5",
+        r: "6671135b6d4bb9ec1b6ac0fc7d56ebe2530163418a88fe9ea448dc48fa354d86",
+        s: "8e86e7170425ca7ae6136d8ac89915c9ea79356217469794fe043659c9100751",
+        valid: true,
+    },
+    EcdsaTestVector {
+        tc_id: 5,
+        comment: "valid signature",
+        key: 0,
+        msg: "123400",
+        hash: "bb5a52f42f9c9261ed4361f59422a1e30036e7c32b270c8807a419feca605023",
+        r: "50ade26071c44bc89adced4a61f1584ea39edbb4416b19046d650292db3ae301",
+        s: "a7056308de629c4618f44be0ffeff83c1e2128c4ddc9cd1c1258170f735f7156",
+        valid: true,
+    },
+    EcdsaTestVector {
+        tc_id: 6,
+        comment: "s replaced by n - s",
+        key: 0,
+        msg: "123400",
+        hash: "bb5a52f42f9c9261ed4361f59422a1e30036e7c32b270c8807a419feca605023",
+        r: "50ade26071c44bc89adced4a61f1584ea39edbb4416b19046d650292db3ae301",
+        s: "58fa9cf6219d63bae70bb41f001007c39ec5d1e8c94dd168e161b3b38903b3fb",
+        valid: true,
+    },
+    EcdsaTestVector {
+        tc_id: 7,
+        comment: "r modified",
+        key: 0,
+        msg: "123400",
+        hash: "bb5a52f42f9c9261ed4361f59422a1e30036e7c32b270c8807a419feca605023",
+        r: "50ade26071c44bc89adced4a61f1584ea39edbb4416b19046d650292db3ae300",
+        s: "a7056308de629c4618f44be0ffeff83c1e2128c4ddc9cd1c1258170f735f7156",
+        valid: false,
+    },
+    EcdsaTestVector {
+        tc_id: 8,
+        comment: "s modified",
+        key: 0,
+        msg: "123400",
+        hash: "bb5a52f42f9c9261ed4361f59422a1e30036e7c32b270c8807a419feca605023",
+        r: "50ade26071c44bc89adced4a61f1584ea39edbb4416b19046d650292db3ae301",
+        s: "a7056308de629c4618f44be0ffeff83c1e2128c4ddc9cd1c1258170f735f7157",
+        valid: false,
+    },
+    EcdsaTestVector {
+        tc_id: 9,
+        comment: "r = 0",
+        key: 0,
+        msg: "123400",
+        hash: "bb5a52f42f9c9261ed4361f59422a1e30036e7c32b270c8807a419feca605023",
+        r: "0",
+        s: "a7056308de629c4618f44be0ffeff83c1e2128c4ddc9cd1c1258170f735f7156",
+        valid: false,
+    },
+    EcdsaTestVector {
+        tc_id: 10,
+        comment: "s = 0",
+        key: 0,
+        msg: "123400",
+        hash: "bb5a52f42f9c9261ed4361f59422a1e30036e7c32b270c8807a419feca605023",
+        r: "50ade26071c44bc89adced4a61f1584ea39edbb4416b19046d650292db3ae301",
+        s: "0",
+        valid: false,
+    },
+    EcdsaTestVector {
+        tc_id: 11,
+        comment: "r = n",
+        key: 0,
+        msg: "123400",
+        hash: "bb5a52f42f9c9261ed4361f59422a1e30036e7c32b270c8807a419feca605023",
+        r: "ffffffff00000000ffffffffffffffffbce6faada7179e84f3b9cac2fc632551",
+        s: "a7056308de629c4618f44be0ffeff83c1e2128c4ddc9cd1c1258170f735f7156",
+        valid: false,
+    },
+    EcdsaTestVector {
+        tc_id: 12,
+        comment: "s = n",
+        key: 0,
+        msg: "123400",
+        hash: "bb5a52f42f9c9261ed4361f59422a1e30036e7c32b270c8807a419feca605023",
+        r: "50ade26071c44bc89adced4a61f1584ea39edbb4416b19046d650292db3ae301",
+        s: "ffffffff00000000ffffffffffffffffbce6faada7179e84f3b9cac2fc632551",
+        valid: false,
+    },
+    EcdsaTestVector {
+        tc_id: 13,
+        comment: "r replaced by r + n",
+        key: 0,
+        msg: "123400",
+        hash: "bb5a52f42f9c9261ed4361f59422a1e30036e7c32b270c8807a419feca605023",
+        r: "150ade25f71c44bc99adced4a61f1584e6085d661e882b789611ecd55d79e0852",
+        s: "a7056308de629c4618f44be0ffeff83c1e2128c4ddc9cd1c1258170f735f7156",
+        valid: false,
+    },
+    EcdsaTestVector {
+        tc_id: 14,
+        comment: "s replaced by s + n",
+        key: 0,
+        msg: "123400",
+        hash: "bb5a52f42f9c9261ed4361f59422a1e30036e7c32b270c8807a419feca605023",
+        r: "50ade26071c44bc89adced4a61f1584ea39edbb4416b19046d650292db3ae301",
+        s: "1a7056307de629c4718f44be0ffeff83bdb08237284e16ba10611e1d26fc296a7",
+        valid: false,
+    },
+    EcdsaTestVector {
+        tc_id: 15,
+        comment: "r and s swapped",
+        key: 0,
+        msg: "123400",
+        hash: "bb5a52f42f9c9261ed4361f59422a1e30036e7c32b270c8807a419feca605023",
+        r: "a7056308de629c4618f44be0ffeff83c1e2128c4ddc9cd1c1258170f735f7156",
+        s: "50ade26071c44bc89adced4a61f1584ea39edbb4416b19046d650292db3ae301",
+        valid: false,
+    },
+    EcdsaTestVector {
+        tc_id: 16,
+        comment: "message modified",
+        key: 0,
+        msg: "123401",
+        hash: "cff0875a3a3f073e2b862423e366f66ea98eeebde3523bd7e4bd26437a1c4510",
+        r: "50ade26071c44bc89adced4a61f1584ea39edbb4416b19046d650292db3ae301",
+        s: "a7056308de629c4618f44be0ffeff83c1e2128c4ddc9cd1c1258170f735f7156",
+        valid: false,
+    },
+    EcdsaTestVector {
+        tc_id: 17,
+        comment: "signature under a different key",
+        key: 1,
+        msg: "123400",
+        hash: "bb5a52f42f9c9261ed4361f59422a1e30036e7c32b270c8807a419feca605023",
+        r: "50ade26071c44bc89adced4a61f1584ea39edbb4416b19046d650292db3ae301",
+        s: "a7056308de629c4618f44be0ffeff83c1e2128c4ddc9cd1c1258170f735f7156",
+        valid: false,
+    },
+    EcdsaTestVector {
+        tc_id: 18,
+        comment: "signature of a different message",
+        key: 0,
+        msg: "123400",
+        hash: "bb5a52f42f9c9261ed4361f59422a1e30036e7c32b270c8807a419feca605023",
+        r: "6e662279e506c6f6416118735f44ddacdde68e1ac80adab7b12d6df0c933c948",
+        s: "41f76077d2b6493b7443b7839454017dd711d3c0f089628b7a76ef965f3dddc2",
+        valid: false,
+    },
+];
+
+fn from_hex(s: &str) -> BigUint {
+    BigUint::from_str_radix(s, 16).expect("invalid hex")
+}
+
+pub(crate) fn p256_public_key(index: usize) -> ECDSAPublicKey<P256> {
+    let (x, y) = P256_PUBLIC_KEYS[index];
+    ECDSAPublicKey(AffinePoint::nonzero(
+        P256Base::from_noncanonical_biguint(from_hex(x)),
+        P256Base::from_noncanonical_biguint(from_hex(y)),
+    ))
+}
+
+impl EcdsaTestVector {
+    /// The message hash as a scalar, i.e. reduced modulo the group order.
+    pub fn msg_scalar(&self) -> P256Scalar {
+        P256Scalar::from_noncanonical_biguint(from_hex(self.hash) % P256Scalar::order())
+    }
+
+    /// Decodes `(r, s)`, rejecting values outside `[1, n)` like a DER decoder would.
+    pub fn signature(&self) -> Option<ECDSASignature<P256>> {
+        let (r, s) = (from_hex(self.r), from_hex(self.s));
+        let order = P256Scalar::order();
+        let in_range = |x: &BigUint| *x > BigUint::from(0u32) && *x < order;
+        if !in_range(&r) || !in_range(&s) {
+            return None;
+        }
+        Some(ECDSASignature {
+            r: P256Scalar::from_noncanonical_biguint(r),
+            s: P256Scalar::from_noncanonical_biguint(s),
+        })
+    }
+}
@@ -0,0 +1,131 @@
+use core::fmt::Debug;
+use core::hash::{Hash, Hasher};
+use core::ops::{Add, Neg};
+
+use num::BigUint;
+use plonky2::field::ops::Square;
+use plonky2::field::types::{Field, PrimeField};
+use serde::{Deserialize, Serialize};
+
+/// A twisted Edwards curve `a * x^2 + y^2 = 1 + d * x^2 * y^2`.
+pub trait TwistedEdwardsCurve: 'static + Sync + Sized + Copy + Debug {
+    type BaseField: PrimeField;
+    type ScalarField: PrimeField;
+
+    const A: Self::BaseField;
+    const D: Self::BaseField;
+
+    /// A generator of the prime-order subgroup.
+    const GENERATOR: EdwardsPoint<Self>;
+
+    /// The curve order divided by the order of `GENERATOR`.
+    const COFACTOR: u64;
+}
+
+/// A point on a twisted Edwards curve, represented in affine coordinates.
+///
+/// If `a` is a square and `d` is not, the addition law is complete, so unlike
+/// `AffinePoint` there is no need for a separate flag for the neutral element `(0, 1)`.
+#[derive(Copy, Clone, Debug, Deserialize, Serialize)]
+pub struct EdwardsPoint<C: TwistedEdwardsCurve> {
+    pub x: C::BaseField,
+    pub y: C::BaseField,
+}
+
+impl<C: TwistedEdwardsCurve> EdwardsPoint<C> {
+    pub const IDENTITY: Self = Self {
+        x: C::BaseField::ZERO,
+        y: C::BaseField::ONE,
+    };
+
+    pub fn new(x: C::BaseField, y: C::BaseField) -> Self {
+        let point = Self { x, y };
+        debug_assert!(point.is_valid());
+        point
+    }
+
+    pub fn is_valid(&self) -> bool {
+        let Self { x, y } = *self;
+        let xx = x.square();
+        let yy = y.square();
+        C::A * xx + yy == C::BaseField::ONE + C::D * xx * yy
+    }
+
+    pub fn is_identity(&self) -> bool {
+        *self == Self::IDENTITY
+    }
+
+    #[must_use]
+    pub fn double(&self) -> Self {
+        let Self { x, y } = *self;
+        let xy = x * y;
+        let a_xx = C::A * x.square();
+        let yy = y.square();
+
+        let x3 = xy.double() / (a_xx + yy);
+        let y3 = (yy - a_xx) / (C::BaseField::TWO - a_xx - yy);
+        Self { x: x3, y: y3 }
+    }
+
+    /// Multiplies this point by `n`, using a simple double-and-add.
+    #[must_use]
+    pub fn mul_biguint(&self, n: &BigUint) -> Self {
+        let mut result = Self::IDENTITY;
+        for i in (0..n.bits()).rev() {
+            result = result.double();
+            if n.bit(i) {
+                result = result + *self;
+            }
+        }
+        result
+    }
+
+    #[must_use]
+    pub fn mul_scalar(&self, n: C::ScalarField) -> Self {
+        self.mul_biguint(&n.to_canonical_biguint())
+    }
+}
+
+impl<C: TwistedEdwardsCurve> PartialEq for EdwardsPoint<C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.x == other.x && self.y == other.y
+    }
+}
+
+impl<C: TwistedEdwardsCurve> Eq for EdwardsPoint<C> {}
+
+impl<C: TwistedEdwardsCurve> Hash for EdwardsPoint<C> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.x.hash(state);
+        self.y.hash(state);
+    }
+}
+
+impl<C: TwistedEdwardsCurve> Add for EdwardsPoint<C> {
+    type Output = Self;
+
+    // The unified addition law, from https://eprint.iacr.org/2008/013
+    fn add(self, rhs: Self) -> Self {
+        let Self { x: x1, y: y1 } = self;
+        let Self { x: x2, y: y2 } = rhs;
+
+        let x1x2 = x1 * x2;
+        let y1y2 = y1 * y2;
+        let d_xxyy = C::D * x1x2 * y1y2;
+
+        let x3 = (x1 * y2 + y1 * x2) / (C::BaseField::ONE + d_xxyy);
+        let y3 = (y1y2 - C::A * x1x2) / (C::BaseField::ONE - d_xxyy);
+        Self { x: x3, y: y3 }
+    }
+}
+
+impl<C: TwistedEdwardsCurve> Neg for EdwardsPoint<C> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self {
+            x: -self.x,
+            y: self.y,
+        }
+    }
+}
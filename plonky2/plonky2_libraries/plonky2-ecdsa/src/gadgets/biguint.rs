@@ -2,7 +2,7 @@ use alloc::vec;
 use alloc::vec::Vec;
 use core::marker::PhantomData;
 
-use num::{BigUint, Integer, Zero};
+use num::{BigUint, Integer, One, Zero};
 use plonky2::field::extension::Extendable;
 use plonky2::field::types::{PrimeField, PrimeField64};
 use plonky2::hash::hash_types::RichField;
@@ -12,12 +12,21 @@ use plonky2::iop::witness::{PartitionWitness, Witness};
 use plonky2::plonk::circuit_builder::CircuitBuilder;
 use plonky2::util::serialization::{Buffer, IoResult};
 use plonky2::plonk::circuit_data::CommonCircuitData;
-use alloc::string::{String};
+use alloc::string::{String, ToString};
 use plonky2_u32::gadgets::arithmetic_u32::{CircuitBuilderU32, U32Target};
 use plonky2_u32::gadgets::multiple_comparison::list_le_u32_circuit;
+use plonky2_u32::gadgets::range_check::range_check_u32_circuit;
 use plonky2_u32::witness::{GeneratedValuesU32, WitnessU32};
 
-#[derive(Clone, Debug)]
+use crate::serialization::{ReadBigUint, WriteBigUint};
+
+/// The most addends `U32AddManyGate` supports.
+const MAX_ADDENDS: usize = 16;
+
+/// The operand length, in limbs, from which `mul_biguint` uses Karatsuba multiplication.
+const KARATSUBA_THRESHOLD: usize = 20;
+
+#[derive(Clone, Debug, Default)]
 pub struct BigUintTarget {
     pub limbs: Vec<U32Target>,
 }
@@ -76,6 +85,32 @@ pub trait CircuitBuilderBiguint<F: RichField + Extendable<D>, const D: usize> {
     fn div_biguint(&mut self, a: &BigUintTarget, b: &BigUintTarget) -> BigUintTarget;
 
     fn rem_biguint(&mut self, a: &BigUintTarget, b: &BigUintTarget) -> BigUintTarget;
+
+    /// Returns `a * b % modulus`.
+    fn mul_mod_biguint(
+        &mut self,
+        a: &BigUintTarget,
+        b: &BigUintTarget,
+        modulus: &BigUintTarget,
+    ) -> BigUintTarget;
+
+    /// Returns `base^exponent % modulus` by square-and-multiply over the bits of `exponent`,
+    /// which costs two modular multiplications per exponent bit.
+    fn modpow_biguint(
+        &mut self,
+        base: &BigUintTarget,
+        exponent: &BigUintTarget,
+        modulus: &BigUintTarget,
+    ) -> BigUintTarget;
+
+    /// Returns `base^exponent % modulus` for an exponent fixed when the circuit is built, such as
+    /// the RSA public exponent 65537. Only the set bits of `exponent` cost a multiplication.
+    fn modpow_biguint_const_exponent(
+        &mut self,
+        base: &BigUintTarget,
+        exponent: &BigUint,
+        modulus: &BigUintTarget,
+    ) -> BigUintTarget;
 }
 
 impl<F: RichField + Extendable<D>, const D: usize> CircuitBuilderBiguint<F, D>
@@ -184,6 +219,10 @@ impl<F: RichField + Extendable<D>, const D: usize> CircuitBuilderBiguint<F, D>
     }
 
     fn mul_biguint(&mut self, a: &BigUintTarget, b: &BigUintTarget) -> BigUintTarget {
+        if a.num_limbs().min(b.num_limbs()) >= KARATSUBA_THRESHOLD {
+            return mul_biguint_karatsuba(self, a, b);
+        }
+
         let total_limbs = a.limbs.len() + b.limbs.len();
 
         let mut to_add = vec![vec![]; total_limbs];
@@ -195,18 +234,7 @@ impl<F: RichField + Extendable<D>, const D: usize> CircuitBuilderBiguint<F, D>
             }
         }
 
-        let mut combined_limbs = vec![];
-        let mut carry = self.zero_u32();
-        for summands in &mut to_add {
-            let (new_result, new_carry) = self.add_u32s_with_carry(summands, carry);
-            combined_limbs.push(new_result);
-            carry = new_carry;
-        }
-        combined_limbs.push(carry);
-
-        BigUintTarget {
-            limbs: combined_limbs,
-        }
+        sum_columns(self, to_add)
     }
 
     fn mul_biguint_by_bool(&mut self, a: &BigUintTarget, b: BoolTarget) -> BigUintTarget {
@@ -273,6 +301,150 @@ impl<F: RichField + Extendable<D>, const D: usize> CircuitBuilderBiguint<F, D>
         let (_div, rem) = self.div_rem_biguint(a, b);
         rem
     }
+
+    fn mul_mod_biguint(
+        &mut self,
+        a: &BigUintTarget,
+        b: &BigUintTarget,
+        modulus: &BigUintTarget,
+    ) -> BigUintTarget {
+        let prod = self.mul_biguint(a, b);
+        let (div, rem) = self.div_rem_biguint(&prod, modulus);
+
+        // `div` only feeds `mul_biguint`, which does not constrain its inputs to 32 bits.
+        range_check_u32_circuit(self, div.limbs);
+
+        rem
+    }
+
+    fn modpow_biguint(
+        &mut self,
+        base: &BigUintTarget,
+        exponent: &BigUintTarget,
+        modulus: &BigUintTarget,
+    ) -> BigUintTarget {
+        let one = self.constant_biguint(&BigUint::one());
+        let (base, padded_one) = self.pad_biguints(base, &one);
+
+        let bits: Vec<BoolTarget> = exponent
+            .limbs
+            .iter()
+            .flat_map(|limb| self.split_le(limb.0, 32))
+            .collect();
+
+        let mut result = one;
+        for (i, &bit) in bits.iter().rev().enumerate() {
+            if i > 0 {
+                result = self.mul_mod_biguint(&result, &result, modulus);
+            }
+            let factor = BigUintTarget {
+                limbs: base
+                    .limbs
+                    .iter()
+                    .zip(&padded_one.limbs)
+                    .map(|(b, o)| U32Target(self.select(bit, b.0, o.0)))
+                    .collect(),
+            };
+            result = self.mul_mod_biguint(&result, &factor, modulus);
+        }
+        result
+    }
+
+    fn modpow_biguint_const_exponent(
+        &mut self,
+        base: &BigUintTarget,
+        exponent: &BigUint,
+        modulus: &BigUintTarget,
+    ) -> BigUintTarget {
+        assert!(!exponent.is_zero(), "exponent must be nonzero");
+        if exponent.is_one() {
+            return self.rem_biguint(base, modulus);
+        }
+
+        // The leading bit is accounted for by starting from `base`.
+        let mut result = base.clone();
+        for i in (0..exponent.bits() - 1).rev() {
+            result = self.mul_mod_biguint(&result, &result, modulus);
+            if exponent.bit(i) {
+                result = self.mul_mod_biguint(&result, base, modulus);
+            }
+        }
+        result
+    }
+}
+
+/// Karatsuba multiplication: `a * b = z0 + z1 * 2^(32 h) + z2 * 2^(64 h)` with
+/// `z1 = (a0 + a1)(b0 + b1) - z0 - z2`, which takes three half-size products instead of four.
+fn mul_biguint_karatsuba<F: RichField + Extendable<D>, const D: usize>(
+    builder: &mut CircuitBuilder<F, D>,
+    a: &BigUintTarget,
+    b: &BigUintTarget,
+) -> BigUintTarget {
+    let total_limbs = a.num_limbs() + b.num_limbs();
+    let half = a.num_limbs().min(b.num_limbs()) / 2;
+    let split = |x: &BigUintTarget| {
+        let (lo, hi) = x.limbs.split_at(half);
+        (
+            BigUintTarget { limbs: lo.to_vec() },
+            BigUintTarget { limbs: hi.to_vec() },
+        )
+    };
+    let (a0, a1) = split(a);
+    let (b0, b1) = split(b);
+
+    let z0 = builder.mul_biguint(&a0, &b0);
+    let z2 = builder.mul_biguint(&a1, &b1);
+    let a_sum = builder.add_biguint(&a0, &a1);
+    let b_sum = builder.add_biguint(&b0, &b1);
+    let sum_prod = builder.mul_biguint(&a_sum, &b_sum);
+    let z1 = builder.sub_biguint(&sum_prod, &z0);
+    let z1 = builder.sub_biguint(&z1, &z2);
+
+    // The limbs of each `z_i` beyond the product's length are zero, as the exact sums and
+    // differences above never borrow past their top limb.
+    let mut to_add = vec![vec![]; total_limbs];
+    for (shift, z) in [(0, &z0), (half, &z1), (2 * half, &z2)] {
+        for (i, &limb) in z.limbs.iter().enumerate() {
+            if shift + i < total_limbs {
+                to_add[shift + i].push(limb);
+            } else {
+                builder.assert_zero_u32(limb);
+            }
+        }
+    }
+
+    sum_columns(builder, to_add)
+}
+
+/// Adds up columns of limbs, where column `k` has weight `2^(32 k)`, into one more limb than
+/// there are columns.
+fn sum_columns<F: RichField + Extendable<D>, const D: usize>(
+    builder: &mut CircuitBuilder<F, D>,
+    mut to_add: Vec<Vec<U32Target>>,
+) -> BigUintTarget {
+    // `U32AddManyGate` takes at most 16 addends, so long columns, which appear beyond 8 limbs,
+    // are summed in chunks whose carries move to the next column.
+    for k in 0..to_add.len() {
+        while to_add[k].len() > MAX_ADDENDS {
+            let chunk: Vec<_> = to_add[k].drain(..MAX_ADDENDS).collect();
+            let (sum, carry) = builder.add_many_u32(&chunk);
+            to_add[k].push(sum);
+            to_add[k + 1].push(carry);
+        }
+    }
+
+    let mut combined_limbs = vec![];
+    let mut carry = builder.zero_u32();
+    for summands in &mut to_add {
+        let (new_result, new_carry) = builder.add_u32s_with_carry(summands, carry);
+        combined_limbs.push(new_result);
+        carry = new_carry;
+    }
+    combined_limbs.push(carry);
+
+    BigUintTarget {
+        limbs: combined_limbs,
+    }
 }
 
 pub trait WitnessBigUint<F: PrimeField64>: Witness<F> {
@@ -316,8 +488,8 @@ impl<F: PrimeField> GeneratedValuesBigUint<F> for GeneratedValues<F> {
     }
 }
 
-#[derive(Debug)]
-struct BigUintDivRemGenerator<F: RichField + Extendable<D>, const D: usize> {
+#[derive(Debug, Default)]
+pub struct BigUintDivRemGenerator<F: RichField + Extendable<D>, const D: usize> {
     a: BigUintTarget,
     b: BigUintTarget,
     div: BigUintTarget,
@@ -348,17 +520,28 @@ impl<F: RichField + Extendable<D>, const D: usize> SimpleGenerator<F, D>
     }
     
     fn id(&self) -> String {
-        todo!()
+        "BigUintDivRemGenerator".to_string()
     }
-    
-    fn serialize(&self, _dst: &mut Vec<u8>, _common_data: &CommonCircuitData<F, D>) -> IoResult<()> {
-        todo!()
+
+    fn serialize(&self, dst: &mut Vec<u8>, _common_data: &CommonCircuitData<F, D>) -> IoResult<()> {
+        dst.write_target_biguint(&self.a)?;
+        dst.write_target_biguint(&self.b)?;
+        dst.write_target_biguint(&self.div)?;
+        dst.write_target_biguint(&self.rem)
     }
-    
-    fn deserialize(_src: &mut Buffer, _common_data: &CommonCircuitData<F, D>) -> IoResult<Self>
-    where
-        Self: Sized {
-        todo!()
+
+    fn deserialize(src: &mut Buffer, _common_data: &CommonCircuitData<F, D>) -> IoResult<Self> {
+        let a = src.read_target_biguint()?;
+        let b = src.read_target_biguint()?;
+        let div = src.read_target_biguint()?;
+        let rem = src.read_target_biguint()?;
+        Ok(Self {
+            a,
+            b,
+            div,
+            rem,
+            _phantom: PhantomData,
+        })
     }
 }
 
@@ -373,7 +556,7 @@ mod tests {
     use rand::rngs::OsRng;
     use rand::Rng;
 
-    use crate::gadgets::biguint::{CircuitBuilderBiguint, WitnessBigUint};
+    use crate::gadgets::biguint::{CircuitBuilderBiguint, WitnessBigUint, KARATSUBA_THRESHOLD};
 
     #[test]
     fn test_biguint_add() -> Result<()> {
@@ -465,6 +648,52 @@ mod tests {
         data.verify(proof)
     }
 
+    #[test]
+    fn test_biguint_mul_karatsuba() -> Result<()> {
+        const D: usize = 2;
+        type C = PoseidonGoldilocksConfig;
+        type F = <C as GenericConfig<D>>::F;
+        let mut rng = OsRng;
+
+        let config = CircuitConfig::standard_recursion_config();
+        let mut pw = PartialWitness::new();
+        let mut builder = CircuitBuilder::<F, D>::new(config);
+
+        let max = |num_limbs: usize| BigUint::from_slice(&vec![u32::MAX; num_limbs]);
+        let random = |rng: &mut OsRng, num_limbs: usize| {
+            let mut limbs: Vec<u32> = (0..num_limbs).map(|_| rng.gen()).collect();
+            // keep the top limb nonzero, so the operand has exactly `num_limbs` limbs
+            limbs[num_limbs - 1] |= 1 << 31;
+            BigUint::from_slice(&limbs)
+        };
+        // At the threshold with all limbs set, the halves' sums and `a1 * b1` carry into the top
+        // limb; 41 limbs recurse into Karatsuba again, and 32 x 21 splits unevenly.
+        let cases = [
+            (max(KARATSUBA_THRESHOLD), max(KARATSUBA_THRESHOLD)),
+            (random(&mut rng, 41), random(&mut rng, 41)),
+            (random(&mut rng, 32), random(&mut rng, 21)),
+            (max(21), max(32)),
+        ];
+        for (x_value, y_value) in cases {
+            let expected_z_value = &x_value * &y_value;
+
+            let x = builder.add_virtual_biguint_target(x_value.to_u32_digits().len());
+            let y = builder.add_virtual_biguint_target(y_value.to_u32_digits().len());
+            let z = builder.mul_biguint(&x, &y);
+            let expected_z =
+                builder.add_virtual_biguint_target(expected_z_value.to_u32_digits().len());
+            builder.connect_biguint(&z, &expected_z);
+
+            pw.set_biguint_target(&x, &x_value);
+            pw.set_biguint_target(&y, &y_value);
+            pw.set_biguint_target(&expected_z, &expected_z_value);
+        }
+
+        let data = builder.build::<C>();
+        let proof = data.prove(pw).unwrap();
+        data.verify(proof)
+    }
+
     #[test]
     fn test_biguint_cmp() -> Result<()> {
         const D: usize = 2;
@@ -523,4 +752,65 @@ mod tests {
         let proof = data.prove(pw).unwrap();
         data.verify(proof)
     }
+
+    #[test]
+    fn test_biguint_modpow() -> Result<()> {
+        const D: usize = 2;
+        type C = PoseidonGoldilocksConfig;
+        type F = <C as GenericConfig<D>>::F;
+        let mut rng = OsRng;
+
+        let modulus_value = BigUint::from_u128(rng.gen::<u128>() | 1).unwrap();
+        let base_value = BigUint::from_u128(rng.gen()).unwrap() % &modulus_value;
+        let exponent_value = BigUint::from_u32(rng.gen()).unwrap();
+        let expected_value = base_value.modpow(&exponent_value, &modulus_value);
+
+        let config = CircuitConfig::standard_recursion_config();
+        let mut pw = PartialWitness::new();
+        let mut builder = CircuitBuilder::<F, D>::new(config);
+
+        let base = builder.add_virtual_biguint_target(4);
+        let exponent = builder.add_virtual_biguint_target(1);
+        let modulus = builder.constant_biguint(&modulus_value);
+        let result = builder.modpow_biguint(&base, &exponent, &modulus);
+        let expected = builder.constant_biguint(&expected_value);
+        builder.connect_biguint(&result, &expected);
+
+        pw.set_biguint_target(&base, &base_value);
+        pw.set_biguint_target(&exponent, &exponent_value);
+
+        let data = builder.build::<C>();
+        let proof = data.prove(pw).unwrap();
+        data.verify(proof)
+    }
+
+    #[test]
+    fn test_biguint_modpow_const_exponent() -> Result<()> {
+        const D: usize = 2;
+        type C = PoseidonGoldilocksConfig;
+        type F = <C as GenericConfig<D>>::F;
+        let mut rng = OsRng;
+
+        let modulus_value = BigUint::from_u128(rng.gen::<u128>() | 1).unwrap();
+        let base_value = BigUint::from_u128(rng.gen()).unwrap() % &modulus_value;
+        let exponent_value = BigUint::from_u32(65537).unwrap();
+        let expected_value = base_value.modpow(&exponent_value, &modulus_value);
+
+        let config = CircuitConfig::standard_recursion_config();
+        let mut pw = PartialWitness::new();
+        let mut builder = CircuitBuilder::<F, D>::new(config);
+
+        let base = builder.add_virtual_biguint_target(4);
+        let modulus = builder.add_virtual_biguint_target(4);
+        let result = builder.modpow_biguint_const_exponent(&base, &exponent_value, &modulus);
+        let expected = builder.constant_biguint(&expected_value);
+        builder.connect_biguint(&result, &expected);
+
+        pw.set_biguint_target(&base, &base_value);
+        pw.set_biguint_target(&modulus, &modulus_value);
+
+        let data = builder.build::<C>();
+        let proof = data.prove(pw).unwrap();
+        data.verify(proof)
+    }
 }
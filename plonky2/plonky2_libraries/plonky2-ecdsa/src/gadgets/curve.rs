@@ -136,19 +136,21 @@ impl<F: RichField + Extendable<D>, const D: usize> CircuitBuilderCurve<F, D>
 
     fn curve_double<C: Curve>(&mut self, p: &AffinePointTarget<C>) -> AffinePointTarget<C> {
         let AffinePointTarget { x, y } = p;
-        let double_y = self.add_nonnative(y, y);
+        let double_y = self.add_nonnative_unreduced(y, y);
         let inv_double_y = self.inv_nonnative(&double_y);
         let x_squared = self.mul_nonnative(x, x);
-        let double_x_squared = self.add_nonnative(&x_squared, &x_squared);
-        let triple_x_squared = self.add_nonnative(&double_x_squared, &x_squared);
+        let double_x_squared = self.add_nonnative_unreduced(&x_squared, &x_squared);
+        let triple_x_squared = self.add_nonnative_unreduced(&double_x_squared, &x_squared);
 
         let a = self.constant_nonnative(C::A);
-        let triple_xx_a = self.add_nonnative(&triple_x_squared, &a);
-        let lambda = self.mul_nonnative(&triple_xx_a, &inv_double_y);
-        let lambda_squared = self.mul_nonnative(&lambda, &lambda);
-        let x_double = self.add_nonnative(x, x);
+        let triple_xx_a = self.add_nonnative_unreduced(&triple_x_squared, &a);
+        let zero = self.zero_nonnative();
+        let lambda = self.mul_add_nonnative(&triple_xx_a, &inv_double_y, &zero);
 
-        let x3 = self.sub_nonnative(&lambda_squared, &x_double);
+        // lambda^2 - 2x is reduced only once, by the multiplication.
+        let x_double = self.add_nonnative_unreduced(x, x);
+        let neg_x_double = self.neg_nonnative_unreduced(&x_double);
+        let x3 = self.mul_add_nonnative(&lambda, &lambda, &neg_x_double);
 
         let x_diff = self.sub_nonnative(x, &x3);
         let lambda_x_diff = self.mul_nonnative(&lambda, &x_diff);
@@ -184,9 +186,10 @@ impl<F: RichField + Extendable<D>, const D: usize> CircuitBuilderCurve<F, D>
         let v = self.sub_nonnative(x2, x1);
         let v_inv = self.inv_nonnative(&v);
         let s = self.mul_nonnative(&u, &v_inv);
-        let s_squared = self.mul_nonnative(&s, &s);
-        let x_sum = self.add_nonnative(x2, x1);
-        let x3 = self.sub_nonnative(&s_squared, &x_sum);
+        // s^2 - x1 - x2 is reduced only once, by the multiplication.
+        let x_sum = self.add_nonnative_unreduced(x2, x1);
+        let neg_x_sum = self.neg_nonnative_unreduced(&x_sum);
+        let x3 = self.mul_add_nonnative(&s, &s, &neg_x_sum);
         let x_diff = self.sub_nonnative(x1, &x3);
         let prod = self.mul_nonnative(&s, &x_diff);
         let y3 = self.sub_nonnative(&prod, y1);
@@ -200,15 +203,9 @@ impl<F: RichField + Extendable<D>, const D: usize> CircuitBuilderCurve<F, D>
         p2: &AffinePointTarget<C>,
         b: BoolTarget,
     ) -> AffinePointTarget<C> {
-        let not_b = self.not(b);
         let sum = self.curve_add(p1, p2);
-        let x_if_true = self.mul_nonnative_by_bool(&sum.x, b);
-        let y_if_true = self.mul_nonnative_by_bool(&sum.y, b);
-        let x_if_false = self.mul_nonnative_by_bool(&p1.x, not_b);
-        let y_if_false = self.mul_nonnative_by_bool(&p1.y, not_b);
-
-        let x = self.add_nonnative(&x_if_true, &x_if_false);
-        let y = self.add_nonnative(&y_if_true, &y_if_false);
+        let x = self.if_nonnative(b, &sum.x, &p1.x);
+        let y = self.if_nonnative(b, &sum.y, &p1.y);
 
         AffinePointTarget { x, y }
     }
@@ -230,19 +227,7 @@ impl<F: RichField + Extendable<D>, const D: usize> CircuitBuilderCurve<F, D>
         self.connect_affine_point(p, &two_i_times_p);
 
         for &bit in bits.iter() {
-            let not_bit = self.not(bit);
-
-            let result_plus_2_i_p = self.curve_add(&result, &two_i_times_p);
-
-            let new_x_if_bit = self.mul_nonnative_by_bool(&result_plus_2_i_p.x, bit);
-            let new_x_if_not_bit = self.mul_nonnative_by_bool(&result.x, not_bit);
-            let new_y_if_bit = self.mul_nonnative_by_bool(&result_plus_2_i_p.y, bit);
-            let new_y_if_not_bit = self.mul_nonnative_by_bool(&result.y, not_bit);
-
-            let new_x = self.add_nonnative(&new_x_if_bit, &new_x_if_not_bit);
-            let new_y = self.add_nonnative(&new_y_if_bit, &new_y_if_not_bit);
-
-            result = AffinePointTarget { x: new_x, y: new_y };
+            result = self.curve_conditional_add(&result, &two_i_times_p, bit);
 
             two_i_times_p = self.curve_double(&two_i_times_p);
         }
@@ -0,0 +1,433 @@
+use alloc::vec::Vec;
+use core::marker::PhantomData;
+
+use plonky2::field::extension::Extendable;
+use plonky2::field::types::Field;
+use plonky2::hash::hash_types::RichField;
+use plonky2::iop::target::{BoolTarget, Target};
+use plonky2::plonk::circuit_builder::CircuitBuilder;
+use plonky2_u32::gadgets::arithmetic_u32::U32Target;
+
+use crate::curve::twisted_edwards::{EdwardsPoint, TwistedEdwardsCurve};
+use crate::gadgets::biguint::BigUintTarget;
+use crate::gadgets::nonnative::{CircuitBuilderNonNative, NonNativeTarget};
+use crate::gadgets::split_nonnative::CircuitBuilderSplit;
+
+/// A Target representing an affine point on the twisted Edwards curve `C`. The addition law is
+/// complete, so unlike `AffinePointTarget` the neutral element and equal inputs need no special
+/// handling.
+#[derive(Clone, Debug)]
+pub struct EdwardsPointTarget<C: TwistedEdwardsCurve> {
+    pub x: NonNativeTarget<C::BaseField>,
+    pub y: NonNativeTarget<C::BaseField>,
+}
+
+pub trait CircuitBuilderEdwards<F: RichField + Extendable<D>, const D: usize> {
+    fn constant_edwards_point<C: TwistedEdwardsCurve>(
+        &mut self,
+        point: EdwardsPoint<C>,
+    ) -> EdwardsPointTarget<C>;
+
+    fn connect_edwards_point<C: TwistedEdwardsCurve>(
+        &mut self,
+        lhs: &EdwardsPointTarget<C>,
+        rhs: &EdwardsPointTarget<C>,
+    );
+
+    fn add_virtual_edwards_point_target<C: TwistedEdwardsCurve>(&mut self)
+        -> EdwardsPointTarget<C>;
+
+    fn edwards_assert_valid<C: TwistedEdwardsCurve>(&mut self, p: &EdwardsPointTarget<C>);
+
+    fn edwards_neg<C: TwistedEdwardsCurve>(
+        &mut self,
+        p: &EdwardsPointTarget<C>,
+    ) -> EdwardsPointTarget<C>;
+
+    fn if_edwards_point<C: TwistedEdwardsCurve>(
+        &mut self,
+        b: BoolTarget,
+        p1: &EdwardsPointTarget<C>,
+        p2: &EdwardsPointTarget<C>,
+    ) -> EdwardsPointTarget<C>;
+
+    fn random_access_edwards_points<C: TwistedEdwardsCurve>(
+        &mut self,
+        access_index: Target,
+        v: &[EdwardsPointTarget<C>],
+    ) -> EdwardsPointTarget<C>;
+
+    fn edwards_double<C: TwistedEdwardsCurve>(
+        &mut self,
+        p: &EdwardsPointTarget<C>,
+    ) -> EdwardsPointTarget<C>;
+
+    fn edwards_add<C: TwistedEdwardsCurve>(
+        &mut self,
+        p1: &EdwardsPointTarget<C>,
+        p2: &EdwardsPointTarget<C>,
+    ) -> EdwardsPointTarget<C>;
+
+    fn edwards_scalar_mul<C: TwistedEdwardsCurve>(
+        &mut self,
+        p: &EdwardsPointTarget<C>,
+        n: &NonNativeTarget<C::ScalarField>,
+    ) -> EdwardsPointTarget<C>;
+}
+
+impl<F: RichField + Extendable<D>, const D: usize> CircuitBuilderEdwards<F, D>
+    for CircuitBuilder<F, D>
+{
+    fn constant_edwards_point<C: TwistedEdwardsCurve>(
+        &mut self,
+        point: EdwardsPoint<C>,
+    ) -> EdwardsPointTarget<C> {
+        debug_assert!(point.is_valid());
+        EdwardsPointTarget {
+            x: self.constant_nonnative(point.x),
+            y: self.constant_nonnative(point.y),
+        }
+    }
+
+    fn connect_edwards_point<C: TwistedEdwardsCurve>(
+        &mut self,
+        lhs: &EdwardsPointTarget<C>,
+        rhs: &EdwardsPointTarget<C>,
+    ) {
+        self.connect_nonnative(&lhs.x, &rhs.x);
+        self.connect_nonnative(&lhs.y, &rhs.y);
+    }
+
+    fn add_virtual_edwards_point_target<C: TwistedEdwardsCurve>(
+        &mut self,
+    ) -> EdwardsPointTarget<C> {
+        let x = self.add_virtual_nonnative_target();
+        let y = self.add_virtual_nonnative_target();
+
+        EdwardsPointTarget { x, y }
+    }
+
+    fn edwards_assert_valid<C: TwistedEdwardsCurve>(&mut self, p: &EdwardsPointTarget<C>) {
+        let one = self.constant_nonnative(C::BaseField::ONE);
+        let d = self.constant_nonnative(C::D);
+
+        let x_squared = self.mul_nonnative(&p.x, &p.x);
+        let y_squared = self.mul_nonnative(&p.y, &p.y);
+        let a_x_squared = mul_by_a::<C, F, D>(self, &x_squared);
+        let lhs = self.add_nonnative(&a_x_squared, &y_squared);
+
+        let xx_yy = self.mul_nonnative(&x_squared, &y_squared);
+        let d_xx_yy = self.mul_nonnative(&d, &xx_yy);
+        let rhs = self.add_nonnative(&one, &d_xx_yy);
+
+        self.connect_nonnative(&lhs, &rhs);
+    }
+
+    fn edwards_neg<C: TwistedEdwardsCurve>(
+        &mut self,
+        p: &EdwardsPointTarget<C>,
+    ) -> EdwardsPointTarget<C> {
+        EdwardsPointTarget {
+            x: self.neg_nonnative(&p.x),
+            y: p.y.clone(),
+        }
+    }
+
+    fn if_edwards_point<C: TwistedEdwardsCurve>(
+        &mut self,
+        b: BoolTarget,
+        p1: &EdwardsPointTarget<C>,
+        p2: &EdwardsPointTarget<C>,
+    ) -> EdwardsPointTarget<C> {
+        EdwardsPointTarget {
+            x: self.if_nonnative(b, &p1.x, &p2.x),
+            y: self.if_nonnative(b, &p1.y, &p2.y),
+        }
+    }
+
+    fn random_access_edwards_points<C: TwistedEdwardsCurve>(
+        &mut self,
+        access_index: Target,
+        v: &[EdwardsPointTarget<C>],
+    ) -> EdwardsPointTarget<C> {
+        let num_limbs = Self::num_nonnative_limbs::<C::BaseField>();
+        let zero = self.zero();
+        let mut select = |coords: Vec<&NonNativeTarget<C::BaseField>>| {
+            let limbs = (0..num_limbs)
+                .map(|i| {
+                    let column = coords
+                        .iter()
+                        .map(|c| c.value.limbs.get(i).map_or(zero, |l| l.0))
+                        .collect();
+                    U32Target(self.random_access(access_index, column))
+                })
+                .collect();
+            NonNativeTarget {
+                value: BigUintTarget { limbs },
+                _phantom: PhantomData,
+            }
+        };
+
+        let x = select(v.iter().map(|p| &p.x).collect());
+        let y = select(v.iter().map(|p| &p.y).collect());
+        EdwardsPointTarget { x, y }
+    }
+
+    fn edwards_double<C: TwistedEdwardsCurve>(
+        &mut self,
+        p: &EdwardsPointTarget<C>,
+    ) -> EdwardsPointTarget<C> {
+        let EdwardsPointTarget { x, y } = p;
+        let two = self.constant_nonnative(C::BaseField::TWO);
+
+        let xy = self.mul_nonnative(x, y);
+        let x_squared = self.mul_nonnative(x, x);
+        let y_squared = self.mul_nonnative(y, y);
+        let a_x_squared = mul_by_a::<C, F, D>(self, &x_squared);
+
+        // On the curve, `a * x^2 + y^2 = 1 + d * x^2 * y^2`, so these are the denominators of the
+        // addition law with both inputs equal.
+        let x_num = self.add_nonnative(&xy, &xy);
+        let x_den = self.add_nonnative(&a_x_squared, &y_squared);
+        let y_num = self.sub_nonnative(&y_squared, &a_x_squared);
+        let y_den = self.sub_nonnative(&two, &x_den);
+
+        let x_den_inv = self.inv_nonnative(&x_den);
+        let y_den_inv = self.inv_nonnative(&y_den);
+        EdwardsPointTarget {
+            x: self.mul_nonnative(&x_num, &x_den_inv),
+            y: self.mul_nonnative(&y_num, &y_den_inv),
+        }
+    }
+
+    fn edwards_add<C: TwistedEdwardsCurve>(
+        &mut self,
+        p1: &EdwardsPointTarget<C>,
+        p2: &EdwardsPointTarget<C>,
+    ) -> EdwardsPointTarget<C> {
+        let EdwardsPointTarget { x: x1, y: y1 } = p1;
+        let EdwardsPointTarget { x: x2, y: y2 } = p2;
+        let one = self.constant_nonnative(C::BaseField::ONE);
+        let d = self.constant_nonnative(C::D);
+
+        let x1y2 = self.mul_nonnative(x1, y2);
+        let y1x2 = self.mul_nonnative(y1, x2);
+        let x1x2 = self.mul_nonnative(x1, x2);
+        let y1y2 = self.mul_nonnative(y1, y2);
+        let xxyy = self.mul_nonnative(&x1x2, &y1y2);
+        let d_xxyy = self.mul_nonnative(&d, &xxyy);
+        let a_x1x2 = mul_by_a::<C, F, D>(self, &x1x2);
+
+        let x_num = self.add_nonnative(&x1y2, &y1x2);
+        let x_den = self.add_nonnative(&one, &d_xxyy);
+        let y_num = self.sub_nonnative(&y1y2, &a_x1x2);
+        let y_den = self.sub_nonnative(&one, &d_xxyy);
+
+        let x_den_inv = self.inv_nonnative(&x_den);
+        let y_den_inv = self.inv_nonnative(&y_den);
+        EdwardsPointTarget {
+            x: self.mul_nonnative(&x_num, &x_den_inv),
+            y: self.mul_nonnative(&y_num, &y_den_inv),
+        }
+    }
+
+    fn edwards_scalar_mul<C: TwistedEdwardsCurve>(
+        &mut self,
+        p: &EdwardsPointTarget<C>,
+        n: &NonNativeTarget<C::ScalarField>,
+    ) -> EdwardsPointTarget<C> {
+        let identity = self.constant_edwards_point(EdwardsPoint::<C>::IDENTITY);
+        let bits = self.split_nonnative_to_bits(n);
+
+        // The constant identity has fewer limbs than `mul_nonnative` expects, so it is never
+        // doubled.
+        let mut result = identity;
+        for (i, &bit) in bits.iter().rev().enumerate() {
+            if i > 0 {
+                result = self.edwards_double(&result);
+            }
+            let sum = self.edwards_add(&result, p);
+            result = self.if_edwards_point(bit, &sum, &result);
+        }
+        result
+    }
+}
+
+/// Multiplies by the curve coefficient `a`, which is a cheap negation when `a = -1`.
+fn mul_by_a<C: TwistedEdwardsCurve, F: RichField + Extendable<D>, const D: usize>(
+    builder: &mut CircuitBuilder<F, D>,
+    x: &NonNativeTarget<C::BaseField>,
+) -> NonNativeTarget<C::BaseField> {
+    if C::A == C::BaseField::NEG_ONE {
+        builder.neg_nonnative(x)
+    } else {
+        let a = builder.constant_nonnative(C::A);
+        builder.mul_nonnative(&a, x)
+    }
+}
+
+/// Computes `n*p + m*q` with 2-bit windows and a shared doubling chain. The addition law is
+/// complete, so the table may contain the neutral element and `p == q` is fine.
+pub fn edwards_msm_circuit<C: TwistedEdwardsCurve, F: RichField + Extendable<D>, const D: usize>(
+    builder: &mut CircuitBuilder<F, D>,
+    p: &EdwardsPointTarget<C>,
+    q: &EdwardsPointTarget<C>,
+    n: &NonNativeTarget<C::ScalarField>,
+    m: &NonNativeTarget<C::ScalarField>,
+) -> EdwardsPointTarget<C> {
+    let limbs_n = builder.split_nonnative_to_2_bit_limbs(n);
+    let limbs_m = builder.split_nonnative_to_2_bit_limbs(m);
+    assert_eq!(limbs_n.len(), limbs_m.len());
+
+    // Precomputes `precomputation[i + 4*j] = i*p + j*q` for `i,j=0..4`.
+    let identity = builder.constant_edwards_point(EdwardsPoint::<C>::IDENTITY);
+    let mut precomputation = Vec::with_capacity(16);
+    let mut cur_q = identity;
+    for j in 0..4 {
+        if j > 0 {
+            cur_q = builder.edwards_add(&cur_q, q);
+        }
+        let mut cur = cur_q.clone();
+        for i in 0..4 {
+            if i > 0 {
+                cur = builder.edwards_add(&cur, p);
+            }
+            precomputation.push(cur.clone());
+        }
+    }
+
+    let four = builder.constant(F::from_canonical_usize(4));
+
+    // As in `edwards_scalar_mul`, the constant identity `precomputation[0]` is never doubled.
+    let mut result = precomputation[0].clone();
+    for (i, (limb_n, limb_m)) in limbs_n.into_iter().zip(limbs_m).rev().enumerate() {
+        if i > 0 {
+            result = builder.edwards_double(&result);
+            result = builder.edwards_double(&result);
+        }
+        let index = builder.mul_add(four, limb_m, limb_n);
+        let r = builder.random_access_edwards_points(index, &precomputation);
+        result = builder.edwards_add(&result, &r);
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+    use plonky2::field::ed25519_scalar::Ed25519Scalar;
+    use plonky2::field::types::Sample;
+    use plonky2::iop::witness::PartialWitness;
+    use plonky2::plonk::circuit_builder::CircuitBuilder;
+    use plonky2::plonk::circuit_data::CircuitConfig;
+    use plonky2::plonk::config::{GenericConfig, PoseidonGoldilocksConfig};
+
+    use crate::curve::ed25519::Ed25519;
+    use crate::curve::twisted_edwards::{EdwardsPoint, TwistedEdwardsCurve};
+    use crate::gadgets::curve_edwards::{edwards_msm_circuit, CircuitBuilderEdwards};
+    use crate::gadgets::nonnative::CircuitBuilderNonNative;
+
+    #[test]
+    fn test_edwards_add_and_double() -> Result<()> {
+        const D: usize = 2;
+        type C = PoseidonGoldilocksConfig;
+        type F = <C as GenericConfig<D>>::F;
+
+        let config = CircuitConfig::standard_ecc_config();
+
+        let pw = PartialWitness::new();
+        let mut builder = CircuitBuilder::<F, D>::new(config);
+
+        let g = Ed25519::GENERATOR;
+        let p = g.mul_scalar(Ed25519Scalar::rand());
+        let identity = EdwardsPoint::<Ed25519>::IDENTITY;
+
+        let g_target = builder.constant_edwards_point(g);
+        let p_target = builder.constant_edwards_point(p);
+        let identity_target = builder.constant_edwards_point(identity);
+        builder.edwards_assert_valid(&p_target);
+
+        let sum = builder.edwards_add(&g_target, &p_target);
+        let sum_expected = builder.constant_edwards_point(g + p);
+        builder.connect_edwards_point(&sum, &sum_expected);
+
+        // The addition law is complete, so adding a point to itself or to the neutral element
+        // agrees with doubling and the identity.
+        let double = builder.edwards_double(&p_target);
+        let self_sum = builder.edwards_add(&p_target, &p_target);
+        let double_expected = builder.constant_edwards_point(p.double());
+        builder.connect_edwards_point(&double, &double_expected);
+        builder.connect_edwards_point(&self_sum, &double_expected);
+
+        let p_plus_identity = builder.edwards_add(&p_target, &identity_target);
+        builder.connect_edwards_point(&p_plus_identity, &p_target);
+
+        let neg_p = builder.edwards_neg(&p_target);
+        let p_minus_p = builder.edwards_add(&p_target, &neg_p);
+        builder.connect_edwards_point(&p_minus_p, &identity_target);
+
+        let data = builder.build::<C>();
+        let proof = data.prove(pw).unwrap();
+
+        data.verify(proof)
+    }
+
+    #[test]
+    #[ignore]
+    fn test_edwards_scalar_mul() -> Result<()> {
+        const D: usize = 2;
+        type C = PoseidonGoldilocksConfig;
+        type F = <C as GenericConfig<D>>::F;
+
+        let config = CircuitConfig::standard_ecc_config();
+
+        let pw = PartialWitness::new();
+        let mut builder = CircuitBuilder::<F, D>::new(config);
+
+        let p = Ed25519::GENERATOR.mul_scalar(Ed25519Scalar::rand());
+        let n = Ed25519Scalar::rand();
+
+        let p_target = builder.constant_edwards_point(p);
+        let n_target = builder.constant_nonnative(n);
+        let res = builder.edwards_scalar_mul(&p_target, &n_target);
+        let res_expected = builder.constant_edwards_point(p.mul_scalar(n));
+        builder.connect_edwards_point(&res, &res_expected);
+
+        let data = builder.build::<C>();
+        let proof = data.prove(pw).unwrap();
+
+        data.verify(proof)
+    }
+
+    #[test]
+    #[ignore]
+    fn test_edwards_msm() -> Result<()> {
+        const D: usize = 2;
+        type C = PoseidonGoldilocksConfig;
+        type F = <C as GenericConfig<D>>::F;
+
+        let config = CircuitConfig::standard_ecc_config();
+
+        let pw = PartialWitness::new();
+        let mut builder = CircuitBuilder::<F, D>::new(config);
+
+        let p = Ed25519::GENERATOR;
+        let q = p.mul_scalar(Ed25519Scalar::rand());
+        let n = Ed25519Scalar::rand();
+        let m = Ed25519Scalar::rand();
+
+        let p_target = builder.constant_edwards_point(p);
+        let q_target = builder.constant_edwards_point(q);
+        let n_target = builder.constant_nonnative(n);
+        let m_target = builder.constant_nonnative(m);
+        let res = edwards_msm_circuit(&mut builder, &p_target, &q_target, &n_target, &m_target);
+        let res_expected = builder.constant_edwards_point(p.mul_scalar(n) + q.mul_scalar(m));
+        builder.connect_edwards_point(&res, &res_expected);
+
+        let data = builder.build::<C>();
+        let proof = data.prove(pw).unwrap();
+
+        data.verify(proof)
+    }
+}
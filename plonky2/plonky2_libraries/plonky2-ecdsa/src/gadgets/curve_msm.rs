@@ -81,7 +81,6 @@ pub fn curve_msm_circuit<C: Curve, F: RichField + Extendable<D>, const D: usize>
 #[cfg(test)]
 mod tests {
     use anyhow::Result;
-    use plonky2::field::secp256k1_scalar::Secp256K1Scalar;
     use plonky2::field::types::Sample;
     use plonky2::iop::witness::PartialWitness;
     use plonky2::plonk::circuit_builder::CircuitBuilder;
@@ -89,14 +88,13 @@ mod tests {
     use plonky2::plonk::config::{GenericConfig, PoseidonGoldilocksConfig};
 
     use crate::curve::curve_types::{Curve, CurveScalar};
+    use crate::curve::p256::P256;
     use crate::curve::secp256k1::Secp256K1;
     use crate::gadgets::curve::CircuitBuilderCurve;
     use crate::gadgets::curve_msm::curve_msm_circuit;
     use crate::gadgets::nonnative::CircuitBuilderNonNative;
 
-    #[test]
-    #[ignore]
-    fn test_curve_msm() -> Result<()> {
+    fn test_curve_msm_with<EC: Curve>() -> Result<()> {
         const D: usize = 2;
         type C = PoseidonGoldilocksConfig;
         type F = <C as GenericConfig<D>>::F;
@@ -107,11 +105,11 @@ mod tests {
         let mut builder = CircuitBuilder::<F, D>::new(config);
 
         let p =
-            (CurveScalar(Secp256K1Scalar::rand()) * Secp256K1::GENERATOR_PROJECTIVE).to_affine();
+            (CurveScalar(EC::ScalarField::rand()) * EC::GENERATOR_PROJECTIVE).to_affine();
         let q =
-            (CurveScalar(Secp256K1Scalar::rand()) * Secp256K1::GENERATOR_PROJECTIVE).to_affine();
-        let n = Secp256K1Scalar::rand();
-        let m = Secp256K1Scalar::rand();
+            (CurveScalar(EC::ScalarField::rand()) * EC::GENERATOR_PROJECTIVE).to_affine();
+        let n = EC::ScalarField::rand();
+        let m = EC::ScalarField::rand();
 
         let res =
             (CurveScalar(n) * p.to_projective() + CurveScalar(m) * q.to_projective()).to_affine();
@@ -135,4 +133,16 @@ mod tests {
 
         data.verify(proof)
     }
+
+    #[test]
+    #[ignore]
+    fn test_curve_msm() -> Result<()> {
+        test_curve_msm_with::<Secp256K1>()
+    }
+
+    #[test]
+    #[ignore]
+    fn test_curve_msm_p256() -> Result<()> {
+        test_curve_msm_with::<P256>()
+    }
 }
@@ -182,6 +182,7 @@ mod tests {
     use rand::Rng;
 
     use super::*;
+    use crate::curve::p256::P256;
     use crate::curve::secp256k1::Secp256K1;
 
     #[test]
@@ -218,9 +219,7 @@ mod tests {
         data.verify(proof)
     }
 
-    #[test]
-    #[ignore]
-    fn test_curve_windowed_mul() -> Result<()> {
+    fn test_curve_windowed_mul_with<EC: Curve>() -> Result<()> {
         const D: usize = 2;
         type C = PoseidonGoldilocksConfig;
         type F = <C as GenericConfig<D>>::F;
@@ -231,10 +230,10 @@ mod tests {
         let mut builder = CircuitBuilder::<F, D>::new(config);
 
         let g =
-            (CurveScalar(Secp256K1Scalar::rand()) * Secp256K1::GENERATOR_PROJECTIVE).to_affine();
-        let five = Secp256K1Scalar::from_canonical_usize(5);
+            (CurveScalar(EC::ScalarField::rand()) * EC::GENERATOR_PROJECTIVE).to_affine();
+        let five = EC::ScalarField::from_canonical_usize(5);
         let neg_five = five.neg();
-        let neg_five_scalar = CurveScalar::<Secp256K1>(neg_five);
+        let neg_five_scalar = CurveScalar::<EC>(neg_five);
         let neg_five_g = (neg_five_scalar * g.to_projective()).to_affine();
         let neg_five_g_expected = builder.constant_affine_point(neg_five_g);
         builder.curve_assert_valid(&neg_five_g_expected);
@@ -251,4 +250,16 @@ mod tests {
 
         data.verify(proof)
     }
+
+    #[test]
+    #[ignore]
+    fn test_curve_windowed_mul() -> Result<()> {
+        test_curve_windowed_mul_with::<Secp256K1>()
+    }
+
+    #[test]
+    #[ignore]
+    fn test_curve_windowed_mul_p256() -> Result<()> {
+        test_curve_windowed_mul_with::<P256>()
+    }
 }
@@ -9,6 +9,7 @@ use crate::curve::curve_types::Curve;
 use crate::curve::secp256k1::Secp256K1;
 use crate::gadgets::curve::{AffinePointTarget, CircuitBuilderCurve};
 use crate::gadgets::curve_fixed_base::fixed_base_curve_mul_circuit;
+use crate::gadgets::curve_windowed_mul::CircuitBuilderWindowedMul;
 use crate::gadgets::glv::CircuitBuilderGlv;
 use crate::gadgets::nonnative::{CircuitBuilderNonNative, NonNativeTarget};
 
@@ -49,10 +50,37 @@ pub fn verify_message_circuit<F: RichField + Extendable<D>, const D: usize>(
     builder.connect_nonnative(&r, &x);
 }
 
+/// Like `verify_message_circuit`, but for any curve. `u2 * pk` is computed with a windowed
+/// multiplication instead of the secp256k1 GLV endomorphism, so this also works for P-256.
+pub fn verify_message_circuit_windowed<C: Curve, F: RichField + Extendable<D>, const D: usize>(
+    builder: &mut CircuitBuilder<F, D>,
+    msg: NonNativeTarget<C::ScalarField>,
+    sig: ECDSASignatureTarget<C>,
+    pk: ECDSAPublicKeyTarget<C>,
+) {
+    let ECDSASignatureTarget { r, s } = sig;
+
+    builder.curve_assert_valid(&pk.0);
+
+    let c = builder.inv_nonnative(&s);
+    let u1 = builder.mul_nonnative(&msg, &c);
+    let u2 = builder.mul_nonnative(&r, &c);
+
+    let point1 = fixed_base_curve_mul_circuit(builder, C::GENERATOR_AFFINE, &u1);
+    let point2 = builder.curve_scalar_mul_windowed(&pk.0, &u2);
+    let point = builder.curve_add(&point1, &point2);
+
+    // ECDSA compares r with x(R) mod n, and x(R) can exceed n whenever p > n, as for both
+    // secp256k1 and P-256. Reinterpreting the limbs of x(R) as a scalar would then reject
+    // valid signatures, so reduce it instead.
+    let x = builder.reduce::<C::ScalarField>(&point.x.value);
+    builder.connect_nonnative(&r, &x);
+}
+
 #[cfg(test)]
 mod tests {
     use anyhow::Result;
-    use plonky2::field::types::Sample;
+    use plonky2::field::types::{PrimeField, Sample};
     use plonky2::iop::witness::PartialWitness;
     use plonky2::plonk::circuit_data::CircuitConfig;
     use plonky2::plonk::config::{GenericConfig, PoseidonGoldilocksConfig};
@@ -60,6 +88,8 @@ mod tests {
     use super::*;
     use crate::curve::curve_types::CurveScalar;
     use crate::curve::ecdsa::{sign_message, ECDSAPublicKey, ECDSASecretKey, ECDSASignature};
+    use crate::curve::p256_test_vectors::{p256_public_key, EcdsaTestVector, P256_SHA256_VECTORS};
+    use crate::gadgets::biguint::WitnessBigUint;
 
     fn test_ecdsa_circuit_with_config(config: CircuitConfig) -> Result<()> {
         const D: usize = 2;
@@ -108,4 +138,48 @@ mod tests {
     fn test_ecdsa_circuit_wide() -> Result<()> {
         test_ecdsa_circuit_with_config(CircuitConfig::wide_ecc_config())
     }
+
+    fn test_p256_vector_circuit(v: &EcdsaTestVector) -> Result<()> {
+        const D: usize = 2;
+        type C = PoseidonGoldilocksConfig;
+        type F = <C as GenericConfig<D>>::F;
+
+        let config = CircuitConfig::wide_ecc_config();
+        let mut pw = PartialWitness::new();
+        let mut builder = CircuitBuilder::<F, D>::new(config);
+
+        let msg_target = builder.constant_nonnative(v.msg_scalar());
+        let pk_target = ECDSAPublicKeyTarget(builder.constant_affine_point(p256_public_key(v.key).0));
+
+        // out-of-range values cannot be decoded, so they never reach the circuit
+        let ECDSASignature { r, s } = v.signature().expect("signature out of range");
+        let sig_target = ECDSASignatureTarget {
+            r: builder.add_virtual_nonnative_target(),
+            s: builder.add_virtual_nonnative_target(),
+        };
+        pw.set_biguint_target(&sig_target.r.value, &r.to_canonical_biguint());
+        pw.set_biguint_target(&sig_target.s.value, &s.to_canonical_biguint());
+
+        verify_message_circuit_windowed(&mut builder, msg_target, sig_target, pk_target);
+
+        let data = builder.build::<C>();
+        let proof = data.prove(pw)?;
+        data.verify(proof)
+    }
+
+    #[test]
+    #[ignore]
+    fn test_ecdsa_circuit_p256_valid() -> Result<()> {
+        let v = P256_SHA256_VECTORS.iter().find(|v| v.valid).unwrap();
+        test_p256_vector_circuit(v)
+    }
+
+    #[test]
+    #[ignore]
+    fn test_ecdsa_circuit_p256_test_vectors() {
+        for v in P256_SHA256_VECTORS.iter().filter(|v| v.signature().is_some()) {
+            let result = test_p256_vector_circuit(v);
+            assert_eq!(result.is_ok(), v.valid, "tcId {}: {}", v.tc_id, v.comment);
+        }
+    }
 }
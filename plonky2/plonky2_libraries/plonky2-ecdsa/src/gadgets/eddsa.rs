@@ -0,0 +1,226 @@
+use alloc::vec::Vec;
+
+use plonky2::field::ed25519_base::Ed25519Base;
+use plonky2::field::ed25519_scalar::Ed25519Scalar;
+use plonky2::field::extension::Extendable;
+use plonky2::field::types::{Field, PrimeField, PrimeField64};
+use plonky2::hash::hash_types::RichField;
+use plonky2::iop::witness::Witness;
+use plonky2::plonk::circuit_builder::CircuitBuilder;
+use plonky2_u32::gadgets::arithmetic_u32::U32Target;
+use plonky2_u32::gadgets::range_check::range_check_u32_circuit;
+
+use crate::curve::ed25519::Ed25519;
+use crate::curve::eddsa::{EdDSAPublicKey, EdDSASignature};
+use crate::curve::twisted_edwards::TwistedEdwardsCurve;
+use crate::gadgets::biguint::{BigUintTarget, CircuitBuilderBiguint, WitnessBigUint};
+use crate::gadgets::curve_edwards::{
+    edwards_msm_circuit, CircuitBuilderEdwards, EdwardsPointTarget,
+};
+use crate::gadgets::nonnative::{CircuitBuilderNonNative, NonNativeTarget};
+use crate::gadgets::sha512::CircuitBuilderSha512;
+
+#[derive(Clone, Debug)]
+pub struct EdDSAPublicKeyTarget(pub EdwardsPointTarget<Ed25519>);
+
+#[derive(Clone, Debug)]
+pub struct EdDSASignatureTarget {
+    pub r: EdwardsPointTarget<Ed25519>,
+    pub s: NonNativeTarget<Ed25519Scalar>,
+}
+
+/// Verifies an Ed25519 signature over a message of `num_bytes` bytes, given as big-endian words as
+/// in `sha512`. Like the native `verify_message`, this checks the cofactorless equation
+/// `[S]B = R + [k]A` with `k = SHA-512(R || A || M) mod L`.
+///
+/// `A` and `R` are range-checked to canonical coordinates, since their encodings are hashed, and
+/// `S` is checked to be below `L` to rule out malleable signatures.
+pub fn verify_message_circuit<F: RichField + Extendable<D>, const D: usize>(
+    builder: &mut CircuitBuilder<F, D>,
+    msg: &[U32Target],
+    num_bytes: usize,
+    sig: EdDSASignatureTarget,
+    pk: EdDSAPublicKeyTarget,
+) {
+    let EdDSASignatureTarget { r, s } = sig;
+
+    builder.edwards_assert_valid(&pk.0);
+    builder.edwards_assert_valid(&r);
+
+    range_check_u32_circuit(builder, s.value.limbs.clone());
+    let max_s = builder.constant_biguint(&(Ed25519Scalar::order() - 1u32));
+    let s_in_range = builder.cmp_biguint(&s.value, &max_s);
+    builder.assert_one(s_in_range.target);
+
+    // Both encodings are 32 bytes long, so the message stays word-aligned after them.
+    let mut preimage = compress_point_circuit(builder, &r).to_vec();
+    preimage.extend(compress_point_circuit(builder, &pk.0));
+    preimage.extend_from_slice(msg);
+    let digest = builder.sha512(&preimage, 64 + num_bytes);
+
+    // The digest is read as a little-endian integer, so limb `i` is word `i` with its bytes
+    // reversed.
+    let limbs = digest
+        .iter()
+        .map(|&word| {
+            let bits = builder.split_le(word.0, 32);
+            let swapped: Vec<_> = bits.chunks(8).rev().flatten().copied().collect();
+            U32Target(builder.le_sum(swapped.into_iter()))
+        })
+        .collect();
+    let k = builder.reduce::<Ed25519Scalar>(&BigUintTarget { limbs });
+
+    let g = builder.constant_edwards_point(Ed25519::GENERATOR);
+    let neg_pk = builder.edwards_neg(&pk.0);
+    let point = edwards_msm_circuit(builder, &g, &neg_pk, &s, &k);
+
+    // `r` is canonical, so this also forces `point` to be reduced.
+    builder.connect_edwards_point(&point, &r);
+}
+
+/// Returns the RFC 8032 encoding of `p` as big-endian words. Both coordinates are constrained to
+/// be canonical, so the encoding is unique.
+pub fn compress_point_circuit<F: RichField + Extendable<D>, const D: usize>(
+    builder: &mut CircuitBuilder<F, D>,
+    p: &EdwardsPointTarget<Ed25519>,
+) -> [U32Target; 8] {
+    let max = builder.constant_biguint(&(Ed25519Base::order() - 1u32));
+    for coord in [&p.x, &p.y] {
+        let in_range = builder.cmp_biguint(&coord.value, &max);
+        builder.assert_one(in_range.target);
+    }
+
+    // Splitting also range-checks every limb. As `y < p < 2^255`, its top bit is zero and is
+    // replaced by the parity of `x`.
+    let x_bits = builder.split_nonnative_to_bits(&p.x);
+    let mut bits = builder.split_nonnative_to_bits(&p.y);
+    bits[255] = x_bits[0];
+
+    // The encoding is little-endian, so each word holds four bytes of `y` in reverse order.
+    core::array::from_fn(|i| {
+        let word_bits: Vec<_> = bits[32 * i..32 * (i + 1)]
+            .chunks(8)
+            .rev()
+            .flatten()
+            .copied()
+            .collect();
+        U32Target(builder.le_sum(word_bits.into_iter()))
+    })
+}
+
+pub trait WitnessEdDSA<F: PrimeField64>: Witness<F> {
+    fn set_eddsa_public_key_target(
+        &mut self,
+        target: &EdDSAPublicKeyTarget,
+        value: &EdDSAPublicKey,
+    );
+
+    fn set_eddsa_signature_target(&mut self, target: &EdDSASignatureTarget, value: &EdDSASignature);
+}
+
+impl<T: Witness<F>, F: PrimeField64> WitnessEdDSA<F> for T {
+    fn set_eddsa_public_key_target(
+        &mut self,
+        target: &EdDSAPublicKeyTarget,
+        value: &EdDSAPublicKey,
+    ) {
+        self.set_biguint_target(&target.0.x.value, &value.0.x.to_canonical_biguint());
+        self.set_biguint_target(&target.0.y.value, &value.0.y.to_canonical_biguint());
+    }
+
+    fn set_eddsa_signature_target(
+        &mut self,
+        target: &EdDSASignatureTarget,
+        value: &EdDSASignature,
+    ) {
+        self.set_biguint_target(&target.r.x.value, &value.r.x.to_canonical_biguint());
+        self.set_biguint_target(&target.r.y.value, &value.r.y.to_canonical_biguint());
+        self.set_biguint_target(&target.s.value, &value.s.to_canonical_biguint());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+    use plonky2::iop::witness::PartialWitness;
+    use plonky2::plonk::circuit_data::CircuitConfig;
+    use plonky2::plonk::config::{GenericConfig, PoseidonGoldilocksConfig};
+    use plonky2_u32::gadgets::arithmetic_u32::CircuitBuilderU32;
+    use plonky2_u32::gadgets::sha256::bytes_to_sha256_words;
+    use plonky2_u32::witness::WitnessU32;
+
+    use super::*;
+    use crate::curve::ed25519_test_vectors::{decode_hex, RFC8032_VECTORS};
+    use crate::curve::eddsa::{sign_message, EdDSASecretKey};
+
+    fn test_eddsa_circuit_with(
+        msg: &[u8],
+        sig: &EdDSASignature,
+        pk: &EdDSAPublicKey,
+    ) -> Result<()> {
+        const D: usize = 2;
+        type C = PoseidonGoldilocksConfig;
+        type F = <C as GenericConfig<D>>::F;
+
+        let config = CircuitConfig::wide_ecc_config();
+        let mut pw = PartialWitness::new();
+        let mut builder = CircuitBuilder::<F, D>::new(config);
+
+        let msg_words = bytes_to_sha256_words(msg);
+        let msg_targets = builder.add_virtual_u32_targets(msg_words.len());
+        let pk_target = EdDSAPublicKeyTarget(builder.add_virtual_edwards_point_target());
+        let sig_target = EdDSASignatureTarget {
+            r: builder.add_virtual_edwards_point_target(),
+            s: builder.add_virtual_nonnative_target(),
+        };
+
+        verify_message_circuit(
+            &mut builder,
+            &msg_targets,
+            msg.len(),
+            sig_target.clone(),
+            pk_target.clone(),
+        );
+
+        for (&t, w) in msg_targets.iter().zip(msg_words) {
+            pw.set_u32_target(t, w);
+        }
+        pw.set_eddsa_public_key_target(&pk_target, pk);
+        pw.set_eddsa_signature_target(&sig_target, sig);
+
+        let data = builder.build::<C>();
+        let proof = data.prove(pw)?;
+        data.verify(proof)
+    }
+
+    #[test]
+    #[ignore]
+    fn test_eddsa_circuit_rfc8032_vectors() -> Result<()> {
+        for v in RFC8032_VECTORS.iter() {
+            let pk =
+                EdDSAPublicKey::from_bytes(&decode_hex(v.public_key).try_into().unwrap()).unwrap();
+            let sig =
+                EdDSASignature::from_bytes(&decode_hex(v.signature).try_into().unwrap()).unwrap();
+            test_eddsa_circuit_with(&decode_hex(v.msg), &sig, &pk)?;
+        }
+        Ok(())
+    }
+
+    #[test]
+    #[ignore]
+    fn test_eddsa_circuit_wrong_message() {
+        let v = &RFC8032_VECTORS[1];
+        let pk = EdDSAPublicKey::from_bytes(&decode_hex(v.public_key).try_into().unwrap()).unwrap();
+        let sig = EdDSASignature::from_bytes(&decode_hex(v.signature).try_into().unwrap()).unwrap();
+        assert!(test_eddsa_circuit_with(b"s", &sig, &pk).is_err());
+    }
+
+    #[test]
+    #[ignore]
+    fn test_eddsa_circuit_manifest() -> Result<()> {
+        let sk = EdDSASecretKey([7u8; 32]);
+        let msg = b"vendor firmware manifest v1.2.3";
+        let sig = sign_message(msg, sk);
+        test_eddsa_circuit_with(msg, &sig, &sk.to_public())
+    }
+}
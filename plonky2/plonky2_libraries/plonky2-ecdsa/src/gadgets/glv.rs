@@ -11,9 +11,9 @@ use plonky2::iop::target::{BoolTarget, Target};
 use plonky2::iop::witness::{PartitionWitness, WitnessWrite};
 use plonky2::plonk::circuit_builder::CircuitBuilder;
 
-use plonky2::util::serialization::{Buffer, IoResult};
+use plonky2::util::serialization::{Buffer, IoResult, Read, Write};
 use plonky2::plonk::circuit_data::CommonCircuitData;
-use alloc::string::{String};
+use alloc::string::{String, ToString};
 
 use crate::curve::glv::{decompose_secp256k1_scalar, GLV_BETA, GLV_S};
 use crate::curve::secp256k1::Secp256K1;
@@ -21,6 +21,7 @@ use crate::gadgets::biguint::{GeneratedValuesBigUint, WitnessBigUint};
 use crate::gadgets::curve::{AffinePointTarget, CircuitBuilderCurve};
 use crate::gadgets::curve_msm::curve_msm_circuit;
 use crate::gadgets::nonnative::{CircuitBuilderNonNative, NonNativeTarget};
+use crate::serialization::{ReadNonNative, WriteNonNative};
 
 pub trait CircuitBuilderGlv<F: RichField + Extendable<D>, const D: usize> {
     fn secp256k1_glv_beta(&mut self) -> NonNativeTarget<Secp256K1Base>;
@@ -103,8 +104,8 @@ impl<F: RichField + Extendable<D>, const D: usize> CircuitBuilderGlv<F, D>
     }
 }
 
-#[derive(Debug)]
-struct GLVDecompositionGenerator<F: RichField + Extendable<D>, const D: usize> {
+#[derive(Debug, Default)]
+pub struct GLVDecompositionGenerator<F: RichField + Extendable<D>, const D: usize> {
     k: NonNativeTarget<Secp256K1Scalar>,
     k1: NonNativeTarget<Secp256K1Scalar>,
     k2: NonNativeTarget<Secp256K1Scalar>,
@@ -135,17 +136,31 @@ impl<F: RichField + Extendable<D>, const D: usize> SimpleGenerator<F,D>
     }
     
     fn id(&self) -> String {
-        todo!()
+        "GLVDecompositionGenerator".to_string()
     }
-    
-    fn serialize(&self, _dst: &mut Vec<u8>, _common_data: &CommonCircuitData<F, D>) -> IoResult<()> {
-        todo!()
+
+    fn serialize(&self, dst: &mut Vec<u8>, _common_data: &CommonCircuitData<F, D>) -> IoResult<()> {
+        dst.write_target_nonnative(&self.k)?;
+        dst.write_target_nonnative(&self.k1)?;
+        dst.write_target_nonnative(&self.k2)?;
+        dst.write_target_bool(self.k1_neg)?;
+        dst.write_target_bool(self.k2_neg)
     }
-    
-    fn deserialize(_src: &mut Buffer, _common_data: &CommonCircuitData<F, D>) -> IoResult<Self>
-    where
-        Self: Sized {
-        todo!()
+
+    fn deserialize(src: &mut Buffer, _common_data: &CommonCircuitData<F, D>) -> IoResult<Self> {
+        let k = src.read_target_nonnative()?;
+        let k1 = src.read_target_nonnative()?;
+        let k2 = src.read_target_nonnative()?;
+        let k1_neg = src.read_target_bool()?;
+        let k2_neg = src.read_target_bool()?;
+        Ok(Self {
+            k,
+            k1,
+            k2,
+            k1_neg,
+            k2_neg,
+            _phantom: PhantomData,
+        })
     }
 }
 
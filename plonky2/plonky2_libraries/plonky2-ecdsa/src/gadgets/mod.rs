@@ -1,9 +1,13 @@
 pub mod biguint;
 pub mod curve;
+pub mod curve_edwards;
 pub mod curve_fixed_base;
 pub mod curve_msm;
 pub mod curve_windowed_mul;
 pub mod ecdsa;
+pub mod eddsa;
 pub mod glv;
 pub mod nonnative;
+pub mod rsa;
+pub mod sha512;
 pub mod split_nonnative;
@@ -14,13 +14,17 @@ use plonky2_u32::gadgets::arithmetic_u32::{CircuitBuilderU32, U32Target};
 use plonky2_u32::gadgets::range_check::range_check_u32_circuit;
 use plonky2_u32::witness::GeneratedValuesU32;
 
-use plonky2::util::serialization::{Buffer, IoResult};
+use plonky2::util::serialization::{Buffer, IoResult, Read, Write};
 use plonky2::plonk::circuit_data::CommonCircuitData;
+use plonky2_u32::serialization::{ReadU32, WriteU32};
+use alloc::format;
 use alloc::string::{String};
+use core::any::type_name;
 
 use crate::gadgets::biguint::{
     BigUintTarget, CircuitBuilderBiguint, GeneratedValuesBigUint, WitnessBigUint,
 };
+use crate::serialization::{ReadBigUint, ReadNonNative, WriteBigUint, WriteNonNative};
 
 
 // my own implementation, no guarantee!
@@ -32,7 +36,7 @@ pub fn ceil_div_usize(a: usize, b: usize) -> usize {
 }
 
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Default)]
 pub struct NonNativeTarget<FF: Field> {
     pub(crate) value: BigUintTarget,
     pub(crate) _phantom: PhantomData<FF>,
@@ -99,12 +103,43 @@ pub trait CircuitBuilderNonNative<F: RichField + Extendable<D>, const D: usize>
         b: &NonNativeTarget<FF>,
     ) -> NonNativeTarget<FF>;
 
+    /// Returns `a + b` without reducing it. The result has one limb more than the larger input.
+    fn add_nonnative_unreduced<FF: PrimeField>(
+        &mut self,
+        a: &NonNativeTarget<FF>,
+        b: &NonNativeTarget<FF>,
+    ) -> NonNativeTarget<FF>;
+
+    /// Returns a value congruent to `-x`, without reducing it. This computes `c - x` for the
+    /// smallest multiple `c` of the modulus that is at least `2^(32 * x.num_limbs())`, so it never
+    /// underflows, whatever limbs `x` has.
+    fn neg_nonnative_unreduced<FF: PrimeField>(
+        &mut self,
+        x: &NonNativeTarget<FF>,
+    ) -> NonNativeTarget<FF>;
+
+    /// Returns a value congruent to `a - b`, without reducing it.
+    fn sub_nonnative_unreduced<FF: PrimeField>(
+        &mut self,
+        a: &NonNativeTarget<FF>,
+        b: &NonNativeTarget<FF>,
+    ) -> NonNativeTarget<FF>;
+
     fn mul_nonnative<FF: PrimeField>(
         &mut self,
         a: &NonNativeTarget<FF>,
         b: &NonNativeTarget<FF>,
     ) -> NonNativeTarget<FF>;
 
+    /// Returns `(a * b + c) % |FF|`. The inputs may be unreduced, so this is where the results of
+    /// the `*_unreduced` operations are brought back into `num_nonnative_limbs` limbs.
+    fn mul_add_nonnative<FF: PrimeField>(
+        &mut self,
+        a: &NonNativeTarget<FF>,
+        b: &NonNativeTarget<FF>,
+        c: &NonNativeTarget<FF>,
+    ) -> NonNativeTarget<FF>;
+
     fn mul_many_nonnative<FF: PrimeField>(
         &mut self,
         to_mul: &[NonNativeTarget<FF>],
@@ -241,10 +276,18 @@ impl<F: RichField + Extendable<D>, const D: usize> CircuitBuilderNonNative<F, D>
         x: &NonNativeTarget<FF>,
         y: &NonNativeTarget<FF>,
     ) -> NonNativeTarget<FF> {
-        let not_b = self.not(b);
-        let maybe_x = self.mul_nonnative_by_bool(x, b);
-        let maybe_y = self.mul_nonnative_by_bool(y, not_b);
-        self.add_nonnative(&maybe_x, &maybe_y)
+        let (x, y) = self.pad_biguints(&x.value, &y.value);
+        let limbs = x
+            .limbs
+            .iter()
+            .zip(y.limbs.iter())
+            .map(|(&x_limb, &y_limb)| U32Target(self.select(b, x_limb.0, y_limb.0)))
+            .collect();
+
+        NonNativeTarget {
+            value: BigUintTarget { limbs },
+            _phantom: PhantomData,
+        }
     }
 
     fn add_many_nonnative<FF: PrimeField>(
@@ -319,6 +362,63 @@ impl<F: RichField + Extendable<D>, const D: usize> CircuitBuilderNonNative<F, D>
         diff
     }
 
+    fn add_nonnative_unreduced<FF: PrimeField>(
+        &mut self,
+        a: &NonNativeTarget<FF>,
+        b: &NonNativeTarget<FF>,
+    ) -> NonNativeTarget<FF> {
+        // Adding a constant zero, such as a curve coefficient, would only widen the result.
+        if b.value.num_limbs() == 0 {
+            return a.clone();
+        }
+        if a.value.num_limbs() == 0 {
+            return b.clone();
+        }
+
+        NonNativeTarget {
+            value: self.add_biguint(&a.value, &b.value),
+            _phantom: PhantomData,
+        }
+    }
+
+    fn neg_nonnative_unreduced<FF: PrimeField>(
+        &mut self,
+        x: &NonNativeTarget<FF>,
+    ) -> NonNativeTarget<FF> {
+        let multiple = modulus_multiple_above::<FF>(x.value.num_limbs());
+        let multiple_target = self.constant_biguint(&multiple);
+
+        NonNativeTarget {
+            value: self.sub_biguint(&multiple_target, &x.value),
+            _phantom: PhantomData,
+        }
+    }
+
+    fn sub_nonnative_unreduced<FF: PrimeField>(
+        &mut self,
+        a: &NonNativeTarget<FF>,
+        b: &NonNativeTarget<FF>,
+    ) -> NonNativeTarget<FF> {
+        let neg_b = self.neg_nonnative_unreduced(b);
+        let sum = self.add_biguint(&a.value, &neg_b.value);
+
+        // `neg_b` is at most the multiple of the modulus it was computed from, which usually
+        // leaves the top limbs of the sum provably zero. Dropping them keeps later products small.
+        let max_sum = (BigUint::one() << (32 * a.value.num_limbs()))
+            + modulus_multiple_above::<FF>(b.value.num_limbs());
+        let num_limbs = ceil_div_usize(max_sum.bits() as usize, 32);
+        for &limb in sum.limbs.iter().skip(num_limbs) {
+            self.assert_zero_u32(limb);
+        }
+
+        NonNativeTarget {
+            value: BigUintTarget {
+                limbs: sum.limbs.into_iter().take(num_limbs).collect(),
+            },
+            _phantom: PhantomData,
+        }
+    }
+
     fn mul_nonnative<FF: PrimeField>(
         &mut self,
         a: &NonNativeTarget<FF>,
@@ -350,6 +450,51 @@ impl<F: RichField + Extendable<D>, const D: usize> CircuitBuilderNonNative<F, D>
         prod
     }
 
+    fn mul_add_nonnative<FF: PrimeField>(
+        &mut self,
+        a: &NonNativeTarget<FF>,
+        b: &NonNativeTarget<FF>,
+        c: &NonNativeTarget<FF>,
+    ) -> NonNativeTarget<FF> {
+        let result = self.add_virtual_nonnative_target::<FF>();
+
+        // Size the overflow for the largest value the limbs of `a`, `b` and `c` can hold.
+        let max_value = max_nonnative_value::<FF>(a.value.num_limbs())
+            * max_nonnative_value::<FF>(b.value.num_limbs())
+            + max_nonnative_value::<FF>(c.value.num_limbs());
+        let max_overflow = max_value / FF::order();
+        let overflow = self.add_virtual_biguint_target(ceil_div_usize(
+            max_overflow.bits() as usize,
+            32,
+        ));
+
+        self.add_simple_generator(NonNativeMulAddGenerator::<F, D, FF> {
+            a: a.clone(),
+            b: b.clone(),
+            c: c.clone(),
+            result: result.clone(),
+            overflow: overflow.clone(),
+            _phantom: PhantomData,
+        });
+
+        range_check_u32_circuit(self, result.value.limbs.clone());
+        range_check_u32_circuit(self, overflow.limbs.clone());
+
+        let prod = self.mul_biguint(&a.value, &b.value);
+        let expected = if c.value.num_limbs() == 0 {
+            prod
+        } else {
+            self.add_biguint(&prod, &c.value)
+        };
+
+        let modulus = self.constant_biguint(&FF::order());
+        let mod_times_overflow = self.mul_biguint(&modulus, &overflow);
+        let actual = self.add_biguint(&result.value, &mod_times_overflow);
+        self.connect_biguint(&expected, &actual);
+
+        result
+    }
+
     fn mul_many_nonnative<FF: PrimeField>(
         &mut self,
         to_mul: &[NonNativeTarget<FF>],
@@ -373,9 +518,9 @@ impl<F: RichField + Extendable<D>, const D: usize> CircuitBuilderNonNative<F, D>
     }
 
     fn inv_nonnative<FF: PrimeField>(&mut self, x: &NonNativeTarget<FF>) -> NonNativeTarget<FF> {
-        let num_limbs = x.value.num_limbs();
-        let inv_biguint = self.add_virtual_biguint_target(num_limbs);
-        let div = self.add_virtual_biguint_target(num_limbs);
+        // `x` may be unreduced, but its inverse is always reduced, so `x * inv / |FF| < x`.
+        let inv_biguint = self.add_virtual_biguint_target(Self::num_nonnative_limbs::<FF>());
+        let div = self.add_virtual_biguint_target(x.value.num_limbs());
 
         self.add_simple_generator(NonNativeInverseGenerator::<F, D, FF> {
             x: x.clone(),
@@ -449,17 +594,37 @@ impl<F: RichField + Extendable<D>, const D: usize> CircuitBuilderNonNative<F, D>
         x: &NonNativeTarget<FF>,
         b: BoolTarget,
     ) -> NonNativeTarget<FF> {
-        let not_b = self.not(b);
         let neg = self.neg_nonnative(x);
-        let x_if_true = self.mul_nonnative_by_bool(&neg, b);
-        let x_if_false = self.mul_nonnative_by_bool(x, not_b);
+        self.if_nonnative(b, &neg, x)
+    }
+}
 
-        self.add_nonnative(&x_if_true, &x_if_false)
+/// Returns the largest value of a `NonNativeTarget` with `num_limbs` limbs. Values that fit in
+/// `num_nonnative_limbs` limbs are assumed to be reduced, as in `mul_nonnative`.
+fn max_nonnative_value<FF: PrimeField>(num_limbs: usize) -> BigUint {
+    let limb_bound = (BigUint::one() << (32 * num_limbs)) - BigUint::one();
+    if num_limbs <= ceil_div_usize(FF::BITS, 32) {
+        limb_bound.min(FF::order() - BigUint::one())
+    } else {
+        limb_bound
     }
 }
 
-#[derive(Debug)]
-struct NonNativeAdditionGenerator<F: RichField + Extendable<D>, const D: usize, FF: PrimeField> {
+/// Returns the smallest multiple of `|FF|` that is at least `2^(32 * num_limbs)`.
+fn modulus_multiple_above<FF: PrimeField>(num_limbs: usize) -> BigUint {
+    let modulus = FF::order();
+    let bound = BigUint::one() << (32 * num_limbs);
+    bound.div_ceil(&modulus) * modulus
+}
+
+/// Returns the id of the generator `name` over `FF`. `FF` is part of the id, so that serializers
+/// tell apart the generators of different fields.
+fn generator_id<FF: PrimeField>(name: &str) -> String {
+    format!("{}<{}>", name, type_name::<FF>())
+}
+
+#[derive(Debug, Default)]
+pub struct NonNativeAdditionGenerator<F: RichField + Extendable<D>, const D: usize, FF: PrimeField> {
     a: NonNativeTarget<FF>,
     b: NonNativeTarget<FF>,
     sum: NonNativeTarget<FF>,
@@ -488,7 +653,7 @@ impl<F: RichField + Extendable<D>, const D: usize, FF: PrimeField> SimpleGenerat
         let b_biguint = b.to_canonical_biguint();
         let sum_biguint = a_biguint + b_biguint;
         let modulus = FF::order();
-        let (overflow, sum_reduced) = if sum_biguint > modulus {
+        let (overflow, sum_reduced) = if sum_biguint >= modulus {
             (true, sum_biguint - modulus)
         } else {
             (false, sum_biguint)
@@ -499,22 +664,33 @@ impl<F: RichField + Extendable<D>, const D: usize, FF: PrimeField> SimpleGenerat
     }
     
     fn id(&self) -> String {
-        todo!()
+        generator_id::<FF>("NonNativeAdditionGenerator")
     }
-    
-    fn serialize(&self, _dst: &mut Vec<u8>, _common_data: &CommonCircuitData<F, D>) -> IoResult<()> {
-        todo!()
+
+    fn serialize(&self, dst: &mut Vec<u8>, _common_data: &CommonCircuitData<F, D>) -> IoResult<()> {
+        dst.write_target_nonnative(&self.a)?;
+        dst.write_target_nonnative(&self.b)?;
+        dst.write_target_nonnative(&self.sum)?;
+        dst.write_target_bool(self.overflow)
     }
-    
-    fn deserialize(_src: &mut Buffer, _common_data: &CommonCircuitData<F, D>) -> IoResult<Self>
-    where
-        Self: Sized {
-        todo!()
+
+    fn deserialize(src: &mut Buffer, _common_data: &CommonCircuitData<F, D>) -> IoResult<Self> {
+        let a = src.read_target_nonnative()?;
+        let b = src.read_target_nonnative()?;
+        let sum = src.read_target_nonnative()?;
+        let overflow = src.read_target_bool()?;
+        Ok(Self {
+            a,
+            b,
+            sum,
+            overflow,
+            _phantom: PhantomData,
+        })
     }
 }
 
-#[derive(Debug)]
-struct NonNativeMultipleAddsGenerator<F: RichField + Extendable<D>, const D: usize, FF: PrimeField>
+#[derive(Debug, Default)]
+pub struct NonNativeMultipleAddsGenerator<F: RichField + Extendable<D>, const D: usize, FF: PrimeField>
 {
     summands: Vec<NonNativeTarget<FF>>,
     sum: NonNativeTarget<FF>,
@@ -551,7 +727,7 @@ impl<F: RichField + Extendable<D>, const D: usize, FF: PrimeField> SimpleGenerat
 
         let modulus = FF::order();
         let (overflow_biguint, sum_reduced) = sum_biguint.div_rem(&modulus);
-        let overflow = overflow_biguint.to_u64_digits()[0] as u32;
+        let overflow = overflow_biguint.to_u64_digits().first().copied().unwrap_or(0) as u32;
 
         out_buffer.set_biguint_target(&self.sum.value, &sum_reduced);
         out_buffer.set_u32_target(self.overflow, overflow);
@@ -559,22 +735,36 @@ impl<F: RichField + Extendable<D>, const D: usize, FF: PrimeField> SimpleGenerat
     }
     
     fn id(&self) -> String {
-        todo!()
+        generator_id::<FF>("NonNativeMultipleAddsGenerator")
     }
-    
-    fn serialize(&self, _dst: &mut Vec<u8>, _common_data: &CommonCircuitData<F, D>) -> IoResult<()> {
-        todo!()
+
+    fn serialize(&self, dst: &mut Vec<u8>, _common_data: &CommonCircuitData<F, D>) -> IoResult<()> {
+        dst.write_usize(self.summands.len())?;
+        for summand in self.summands.iter() {
+            dst.write_target_nonnative(summand)?;
+        }
+        dst.write_target_nonnative(&self.sum)?;
+        dst.write_target_u32(self.overflow)
     }
-    
-    fn deserialize(_src: &mut Buffer, _common_data: &CommonCircuitData<F, D>) -> IoResult<Self>
-    where
-        Self: Sized {
-        todo!()
+
+    fn deserialize(src: &mut Buffer, _common_data: &CommonCircuitData<F, D>) -> IoResult<Self> {
+        let num_summands = src.read_usize()?;
+        let summands = (0..num_summands)
+            .map(|_| src.read_target_nonnative())
+            .collect::<IoResult<Vec<_>>>()?;
+        let sum = src.read_target_nonnative()?;
+        let overflow = src.read_target_u32()?;
+        Ok(Self {
+            summands,
+            sum,
+            overflow,
+            _phantom: PhantomData,
+        })
     }
 }
 
-#[derive(Debug)]
-struct NonNativeSubtractionGenerator<F: RichField + Extendable<D>, const D: usize, FF: Field> {
+#[derive(Debug, Default)]
+pub struct NonNativeSubtractionGenerator<F: RichField + Extendable<D>, const D: usize, FF: Field> {
     a: NonNativeTarget<FF>,
     b: NonNativeTarget<FF>,
     diff: NonNativeTarget<FF>,
@@ -614,22 +804,33 @@ impl<F: RichField + Extendable<D>, const D: usize, FF: PrimeField> SimpleGenerat
     }
     
     fn id(&self) -> String {
-        todo!()
+        generator_id::<FF>("NonNativeSubtractionGenerator")
     }
-    
-    fn serialize(&self, _dst: &mut Vec<u8>, _common_data: &CommonCircuitData<F, D>) -> IoResult<()> {
-        todo!()
+
+    fn serialize(&self, dst: &mut Vec<u8>, _common_data: &CommonCircuitData<F, D>) -> IoResult<()> {
+        dst.write_target_nonnative(&self.a)?;
+        dst.write_target_nonnative(&self.b)?;
+        dst.write_target_nonnative(&self.diff)?;
+        dst.write_target_bool(self.overflow)
     }
-    
-    fn deserialize(_src: &mut Buffer, _common_data: &CommonCircuitData<F, D>) -> IoResult<Self>
-    where
-        Self: Sized {
-        todo!()
+
+    fn deserialize(src: &mut Buffer, _common_data: &CommonCircuitData<F, D>) -> IoResult<Self> {
+        let a = src.read_target_nonnative()?;
+        let b = src.read_target_nonnative()?;
+        let diff = src.read_target_nonnative()?;
+        let overflow = src.read_target_bool()?;
+        Ok(Self {
+            a,
+            b,
+            diff,
+            overflow,
+            _phantom: PhantomData,
+        })
     }
 }
 
-#[derive(Debug)]
-struct NonNativeMultiplicationGenerator<F: RichField + Extendable<D>, const D: usize, FF: Field> {
+#[derive(Debug, Default)]
+pub struct NonNativeMultiplicationGenerator<F: RichField + Extendable<D>, const D: usize, FF: Field> {
     a: NonNativeTarget<FF>,
     b: NonNativeTarget<FF>,
     prod: NonNativeTarget<FF>,
@@ -668,22 +869,100 @@ impl<F: RichField + Extendable<D>, const D: usize, FF: PrimeField> SimpleGenerat
     }
     
     fn id(&self) -> String {
-        todo!()
+        generator_id::<FF>("NonNativeMultiplicationGenerator")
     }
-    
-    fn serialize(&self, _dst: &mut Vec<u8>, _common_data: &CommonCircuitData<F, D>) -> IoResult<()> {
-        todo!()
+
+    fn serialize(&self, dst: &mut Vec<u8>, _common_data: &CommonCircuitData<F, D>) -> IoResult<()> {
+        dst.write_target_nonnative(&self.a)?;
+        dst.write_target_nonnative(&self.b)?;
+        dst.write_target_nonnative(&self.prod)?;
+        dst.write_target_biguint(&self.overflow)
     }
-    
-    fn deserialize(_src: &mut Buffer, _common_data: &CommonCircuitData<F, D>) -> IoResult<Self>
-    where
-        Self: Sized {
-        todo!()
+
+    fn deserialize(src: &mut Buffer, _common_data: &CommonCircuitData<F, D>) -> IoResult<Self> {
+        let a = src.read_target_nonnative()?;
+        let b = src.read_target_nonnative()?;
+        let prod = src.read_target_nonnative()?;
+        let overflow = src.read_target_biguint()?;
+        Ok(Self {
+            a,
+            b,
+            prod,
+            overflow,
+            _phantom: PhantomData,
+        })
     }
 }
 
-#[derive(Debug)]
-struct NonNativeInverseGenerator<F: RichField + Extendable<D>, const D: usize, FF: PrimeField> {
+#[derive(Debug, Default)]
+pub struct NonNativeMulAddGenerator<F: RichField + Extendable<D>, const D: usize, FF: PrimeField> {
+    a: NonNativeTarget<FF>,
+    b: NonNativeTarget<FF>,
+    c: NonNativeTarget<FF>,
+    result: NonNativeTarget<FF>,
+    overflow: BigUintTarget,
+    _phantom: PhantomData<F>,
+}
+
+impl<F: RichField + Extendable<D>, const D: usize, FF: PrimeField> SimpleGenerator<F,D>
+    for NonNativeMulAddGenerator<F, D, FF>
+{
+    fn dependencies(&self) -> Vec<Target> {
+        self.a
+            .value
+            .limbs
+            .iter()
+            .chain(self.b.value.limbs.iter())
+            .chain(self.c.value.limbs.iter())
+            .map(|l| l.0)
+            .collect()
+    }
+
+    fn run_once(&self, witness: &PartitionWitness<F>, out_buffer: &mut GeneratedValues<F>) -> Result<(), anyhow::Error> {
+        // The inputs may be unreduced, so the overflow is taken from their integer values.
+        let a = witness.get_biguint_target(self.a.value.clone());
+        let b = witness.get_biguint_target(self.b.value.clone());
+        let c = witness.get_biguint_target(self.c.value.clone());
+
+        let modulus = FF::order();
+        let (overflow, result) = (a * b + c).div_rem(&modulus);
+
+        out_buffer.set_biguint_target(&self.result.value, &result);
+        out_buffer.set_biguint_target(&self.overflow, &overflow);
+        Ok(())
+    }
+
+    fn id(&self) -> String {
+        generator_id::<FF>("NonNativeMulAddGenerator")
+    }
+
+    fn serialize(&self, dst: &mut Vec<u8>, _common_data: &CommonCircuitData<F, D>) -> IoResult<()> {
+        dst.write_target_nonnative(&self.a)?;
+        dst.write_target_nonnative(&self.b)?;
+        dst.write_target_nonnative(&self.c)?;
+        dst.write_target_nonnative(&self.result)?;
+        dst.write_target_biguint(&self.overflow)
+    }
+
+    fn deserialize(src: &mut Buffer, _common_data: &CommonCircuitData<F, D>) -> IoResult<Self> {
+        let a = src.read_target_nonnative()?;
+        let b = src.read_target_nonnative()?;
+        let c = src.read_target_nonnative()?;
+        let result = src.read_target_nonnative()?;
+        let overflow = src.read_target_biguint()?;
+        Ok(Self {
+            a,
+            b,
+            c,
+            result,
+            overflow,
+            _phantom: PhantomData,
+        })
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct NonNativeInverseGenerator<F: RichField + Extendable<D>, const D: usize, FF: PrimeField> {
     x: NonNativeTarget<FF>,
     inv: BigUintTarget,
     div: BigUintTarget,
@@ -698,13 +977,12 @@ impl<F: RichField + Extendable<D>, const D: usize, FF: PrimeField> SimpleGenerat
     }
 
     fn run_once(&self, witness: &PartitionWitness<F>, out_buffer: &mut GeneratedValues<F>) -> Result<(), anyhow::Error> {
-        let x = FF::from_noncanonical_biguint(witness.get_biguint_target(self.x.value.clone()));
-        let inv = x.inverse();
+        let x_biguint = witness.get_biguint_target(self.x.value.clone());
+        let modulus = FF::order();
+        let inv = FF::from_noncanonical_biguint(&x_biguint % &modulus).inverse();
 
-        let x_biguint = x.to_canonical_biguint();
         let inv_biguint = inv.to_canonical_biguint();
         let prod = x_biguint * &inv_biguint;
-        let modulus = FF::order();
         let (div, _rem) = prod.div_rem(&modulus);
 
         out_buffer.set_biguint_target(&self.div, &div);
@@ -713,17 +991,25 @@ impl<F: RichField + Extendable<D>, const D: usize, FF: PrimeField> SimpleGenerat
     }
     
     fn id(&self) -> String {
-        todo!()
+        generator_id::<FF>("NonNativeInverseGenerator")
     }
-    
-    fn serialize(&self, _dst: &mut Vec<u8>, _common_data: &CommonCircuitData<F, D>) -> IoResult<()> {
-        todo!()
+
+    fn serialize(&self, dst: &mut Vec<u8>, _common_data: &CommonCircuitData<F, D>) -> IoResult<()> {
+        dst.write_target_nonnative(&self.x)?;
+        dst.write_target_biguint(&self.inv)?;
+        dst.write_target_biguint(&self.div)
     }
-    
-    fn deserialize(_src: &mut Buffer,_common_data: &CommonCircuitData<F, D>) -> IoResult<Self>
-    where
-        Self: Sized {
-        todo!()
+
+    fn deserialize(src: &mut Buffer, _common_data: &CommonCircuitData<F, D>) -> IoResult<Self> {
+        let x = src.read_target_nonnative()?;
+        let inv = src.read_target_biguint()?;
+        let div = src.read_target_biguint()?;
+        Ok(Self {
+            x,
+            inv,
+            div,
+            _phantom: PhantomData,
+        })
     }
 }
 
@@ -766,6 +1052,33 @@ mod tests {
         data.verify(proof)
     }
 
+    #[test]
+    fn test_nonnative_add_to_zero() -> Result<()> {
+        type FF = Secp256K1Base;
+        const D: usize = 2;
+        type C = PoseidonGoldilocksConfig;
+        type F = <C as GenericConfig<D>>::F;
+
+        // The sum is exactly the modulus, which must wrap around to zero.
+        let x_ff = FF::rand();
+        let y_ff = -x_ff;
+
+        let config = CircuitConfig::standard_ecc_config();
+        let pw = PartialWitness::new();
+        let mut builder = CircuitBuilder::<F, D>::new(config);
+
+        let x = builder.constant_nonnative(x_ff);
+        let y = builder.constant_nonnative(y_ff);
+        let sum = builder.add_nonnative(&x, &y);
+
+        let sum_expected = builder.zero_nonnative();
+        builder.connect_nonnative(&sum, &sum_expected);
+
+        let data = builder.build::<C>();
+        let proof = data.prove(pw).unwrap();
+        data.verify(proof)
+    }
+
     #[test]
     fn test_nonnative_many_adds() -> Result<()> {
         type FF = Secp256K1Base;
@@ -862,6 +1175,39 @@ mod tests {
         data.verify(proof)
     }
 
+    #[test]
+    fn test_nonnative_mul_add_unreduced() -> Result<()> {
+        type FF = Secp256K1Base;
+        const D: usize = 2;
+        type C = PoseidonGoldilocksConfig;
+        type F = <C as GenericConfig<D>>::F;
+        let w_ff = FF::rand();
+        let x_ff = FF::rand();
+        let y_ff = FF::rand();
+        let z_ff = FF::rand();
+        let result_ff = (x_ff - y_ff) * (z_ff + z_ff) - w_ff;
+
+        let config = CircuitConfig::standard_ecc_config();
+        let pw = PartialWitness::new();
+        let mut builder = CircuitBuilder::<F, D>::new(config);
+
+        let w = builder.constant_nonnative(w_ff);
+        let x = builder.constant_nonnative(x_ff);
+        let y = builder.constant_nonnative(y_ff);
+        let z = builder.constant_nonnative(z_ff);
+        let diff = builder.sub_nonnative_unreduced(&x, &y);
+        let double_z = builder.add_nonnative_unreduced(&z, &z);
+        let neg_w = builder.neg_nonnative_unreduced(&w);
+        let result = builder.mul_add_nonnative(&diff, &double_z, &neg_w);
+
+        let result_expected = builder.constant_nonnative(result_ff);
+        builder.connect_nonnative(&result, &result_expected);
+
+        let data = builder.build::<C>();
+        let proof = data.prove(pw).unwrap();
+        data.verify(proof)
+    }
+
     #[test]
     fn test_nonnative_neg() -> Result<()> {
         type FF = Secp256K1Base;
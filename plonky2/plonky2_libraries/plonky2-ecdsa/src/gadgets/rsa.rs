@@ -0,0 +1,409 @@
+use alloc::vec;
+use alloc::vec::Vec;
+
+use num::BigUint;
+use plonky2::field::extension::Extendable;
+use plonky2::hash::hash_types::RichField;
+use plonky2::plonk::circuit_builder::CircuitBuilder;
+use plonky2_u32::gadgets::arithmetic_u32::{CircuitBuilderU32, U32Target};
+use plonky2_u32::gadgets::bitwise_u32::CircuitBuilderBitwiseU32;
+use plonky2_u32::gadgets::sha256::{bytes_to_sha256_words, CircuitBuilderSha256};
+
+use crate::gadgets::biguint::{BigUintTarget, CircuitBuilderBiguint};
+use crate::gadgets::sha512::CircuitBuilderSha512;
+
+/// The public exponent of every RSA key accepted by the verifiers below.
+pub const RSA_PUBLIC_EXPONENT: u32 = 65537;
+
+/// An RSA modulus. Its bit length must be `32 * num_limbs`, as for RSA-2048 and RSA-3072 keys.
+#[derive(Clone, Debug)]
+pub struct RSAPublicKeyTarget(pub BigUintTarget);
+
+/// An RSA signature, with as many limbs as the modulus.
+#[derive(Clone, Debug)]
+pub struct RSASignatureTarget(pub BigUintTarget);
+
+/// A hash function that RSA signatures can be computed over.
+pub trait RSAHash {
+    /// The length of a digest in bytes, which must be a multiple of four.
+    const DIGEST_BYTES: usize;
+
+    /// The DER encoding of the PKCS#1 v1.5 `DigestInfo` up to the digest itself.
+    const DIGEST_INFO_PREFIX: &'static [u8];
+
+    /// Hashes a message of `num_bytes` bytes, given as big-endian words whose unused low bytes
+    /// are zero, and returns the digest as big-endian words.
+    fn hash_circuit<F: RichField + Extendable<D>, const D: usize>(
+        builder: &mut CircuitBuilder<F, D>,
+        msg: &[U32Target],
+        num_bytes: usize,
+    ) -> Vec<U32Target>;
+}
+
+pub struct Sha256;
+
+impl RSAHash for Sha256 {
+    const DIGEST_BYTES: usize = 32;
+
+    const DIGEST_INFO_PREFIX: &'static [u8] = &[
+        0x30, 0x31, 0x30, 0x0d, 0x06, 0x09, 0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02, 0x01,
+        0x05, 0x00, 0x04, 0x20,
+    ];
+
+    fn hash_circuit<F: RichField + Extendable<D>, const D: usize>(
+        builder: &mut CircuitBuilder<F, D>,
+        msg: &[U32Target],
+        num_bytes: usize,
+    ) -> Vec<U32Target> {
+        builder.sha256(msg, num_bytes).to_vec()
+    }
+}
+
+pub struct Sha512;
+
+impl RSAHash for Sha512 {
+    const DIGEST_BYTES: usize = 64;
+
+    const DIGEST_INFO_PREFIX: &'static [u8] = &[
+        0x30, 0x51, 0x30, 0x0d, 0x06, 0x09, 0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02, 0x03,
+        0x05, 0x00, 0x04, 0x40,
+    ];
+
+    fn hash_circuit<F: RichField + Extendable<D>, const D: usize>(
+        builder: &mut CircuitBuilder<F, D>,
+        msg: &[U32Target],
+        num_bytes: usize,
+    ) -> Vec<U32Target> {
+        builder.sha512(msg, num_bytes).to_vec()
+    }
+}
+
+/// Verifies an RSASSA-PKCS1-v1_5 signature (RFC 8017, section 8.2.2) over a message of
+/// `num_bytes` bytes, given as big-endian words as in `RSAHash::hash_circuit`.
+pub fn verify_pkcs1v15_circuit<H: RSAHash, F: RichField + Extendable<D>, const D: usize>(
+    builder: &mut CircuitBuilder<F, D>,
+    msg: &[U32Target],
+    num_bytes: usize,
+    sig: &RSASignatureTarget,
+    pk: &RSAPublicKeyTarget,
+) {
+    let em = encoded_message_words(builder, sig, pk);
+    let em_len = 4 * em.len();
+    let t_len = H::DIGEST_INFO_PREFIX.len() + H::DIGEST_BYTES;
+    assert!(em_len >= t_len + 11, "modulus too short for the digest");
+
+    // EM = 0x00 || 0x01 || PS || 0x00 || T, where PS is 0xff bytes and T is the DigestInfo.
+    let mut expected_prefix = Vec::with_capacity(em_len - H::DIGEST_BYTES);
+    expected_prefix.extend([0x00, 0x01]);
+    expected_prefix.resize(em_len - t_len - 1, 0xff);
+    expected_prefix.push(0x00);
+    expected_prefix.extend_from_slice(H::DIGEST_INFO_PREFIX);
+    assert_bytes(builder, &em, 0, &expected_prefix);
+
+    let digest = H::hash_circuit(builder, msg, num_bytes);
+    let em_digest = bytes_at(builder, &em, expected_prefix.len(), H::DIGEST_BYTES);
+    for (&e, &d) in em_digest.iter().zip(&digest) {
+        builder.connect_u32(e, d);
+    }
+}
+
+/// Verifies an RSASSA-PSS signature (RFC 8017, section 8.1.2) over a message of `num_bytes` bytes,
+/// given as big-endian words as in `RSAHash::hash_circuit`. MGF1 uses the same hash as the
+/// message, and the salt length is fixed when the circuit is built.
+pub fn verify_pss_circuit<H: RSAHash, F: RichField + Extendable<D>, const D: usize>(
+    builder: &mut CircuitBuilder<F, D>,
+    msg: &[U32Target],
+    num_bytes: usize,
+    sig: &RSASignatureTarget,
+    pk: &RSAPublicKeyTarget,
+    salt_len: usize,
+) {
+    let em = encoded_message_words(builder, sig, pk);
+    let em_len = 4 * em.len();
+    let h_len = H::DIGEST_BYTES;
+    assert!(
+        em_len >= h_len + salt_len + 2,
+        "modulus too short for the digest and salt"
+    );
+
+    // The modulus has `8 * em_len` bits, so `emBits = 8 * em_len - 1` and EM has a leading zero bit.
+    let top_bit = builder.shr_u32(em[0], 31);
+    builder.assert_zero_u32(top_bit);
+    assert_bytes(builder, &em, em_len - 1, &[0xbc]);
+
+    let db_len = em_len - h_len - 1;
+    let masked_db = bytes_at(builder, &em, 0, db_len);
+    let h = bytes_at(builder, &em, db_len, h_len);
+
+    // The leftmost bit of DB is cleared rather than unmasked.
+    let mut db_mask = mgf1::<H, F, D>(builder, &h, db_len);
+    let clear_top_bit = builder.constant_u32(u32::MAX >> 1);
+    db_mask[0] = builder.and_u32(db_mask[0], clear_top_bit);
+    let db: Vec<_> = masked_db
+        .iter()
+        .zip(&db_mask)
+        .map(|(&a, &b)| builder.xor_u32(a, b))
+        .collect();
+
+    // DB = PS || 0x01 || salt, where PS is zero.
+    let ps_len = db_len - salt_len - 1;
+    let mut expected_prefix = vec![0x00; ps_len];
+    expected_prefix.push(0x01);
+    assert_bytes(builder, &db, 0, &expected_prefix);
+    let salt = bytes_at(builder, &db, ps_len + 1, salt_len);
+
+    // M' = 0x00 00 00 00 00 00 00 00 || mHash || salt.
+    let m_hash = H::hash_circuit(builder, msg, num_bytes);
+    let zero = builder.zero_u32();
+    let mut m_prime = vec![zero, zero];
+    m_prime.extend(m_hash);
+    m_prime.extend(salt);
+    let h_prime = H::hash_circuit(builder, &m_prime, 8 + h_len + salt_len);
+    for (&a, &b) in h.iter().zip(&h_prime) {
+        builder.connect_u32(a, b);
+    }
+}
+
+/// Returns `sig^e mod n` as the big-endian words of the encoded message, after checking that the
+/// modulus fills its limbs and that the signature is below it.
+fn encoded_message_words<F: RichField + Extendable<D>, const D: usize>(
+    builder: &mut CircuitBuilder<F, D>,
+    sig: &RSASignatureTarget,
+    pk: &RSAPublicKeyTarget,
+) -> Vec<U32Target> {
+    let n = &pk.0;
+    let top_limb_bits = builder.split_le(n.limbs.last().expect("empty modulus").0, 32);
+    builder.assert_one(top_limb_bits[31].target);
+
+    // `cmp_biguint` also range-checks the signature limbs.
+    let n_le_sig = builder.cmp_biguint(n, &sig.0);
+    builder.assert_zero(n_le_sig.target);
+
+    let exponent = BigUint::from(RSA_PUBLIC_EXPONENT);
+    let m = builder.modpow_biguint_const_exponent(&sig.0, &exponent, n);
+    m.limbs.into_iter().rev().collect()
+}
+
+/// Returns the `len` bytes of `words` starting at byte `offset` as big-endian words, with the
+/// unused low bytes of the last word set to zero.
+fn bytes_at<F: RichField + Extendable<D>, const D: usize>(
+    builder: &mut CircuitBuilder<F, D>,
+    words: &[U32Target],
+    offset: usize,
+    len: usize,
+) -> Vec<U32Target> {
+    let (start, shift) = (offset / 4, offset % 4);
+    let num_words = len.div_ceil(4);
+    let mut result = if shift == 0 {
+        words[start..start + num_words].to_vec()
+    } else {
+        // Multiplying by `2^(8 * shift)` leaves the bytes a word keeps in the low half of the
+        // product, and those that move into the previous word in the high half.
+        let factor = builder.constant_u32(1 << (8 * shift));
+        let end = (start + num_words + 1).min(words.len());
+        let parts: Vec<_> = words[start..end]
+            .iter()
+            .map(|&w| builder.mul_u32(w, factor))
+            .collect();
+        (0..num_words)
+            .map(|i| match parts.get(i + 1) {
+                Some(next) => U32Target(builder.add(parts[i].0 .0, next.1 .0)),
+                None => parts[i].0,
+            })
+            .collect()
+    };
+
+    let partial_bytes = len % 4;
+    if partial_bytes > 0 {
+        let last = result.pop().unwrap();
+        let mask = builder.constant_u32(u32::MAX << (8 * (4 - partial_bytes)));
+        result.push(builder.and_u32(last, mask));
+    }
+    result
+}
+
+/// MGF1 (RFC 8017, appendix B.2.1), returning `len` bytes of mask as big-endian words.
+fn mgf1<H: RSAHash, F: RichField + Extendable<D>, const D: usize>(
+    builder: &mut CircuitBuilder<F, D>,
+    seed: &[U32Target],
+    len: usize,
+) -> Vec<U32Target> {
+    let mut mask = Vec::with_capacity(len.div_ceil(4) + H::DIGEST_BYTES / 4);
+    let mut counter = 0u32;
+    while 4 * mask.len() < len {
+        let mut input = seed.to_vec();
+        input.push(builder.constant_u32(counter));
+        mask.extend(H::hash_circuit(builder, &input, H::DIGEST_BYTES + 4));
+        counter += 1;
+    }
+    bytes_at(builder, &mask, 0, len)
+}
+
+/// Asserts that the bytes of `words` starting at byte `offset` are `bytes`.
+fn assert_bytes<F: RichField + Extendable<D>, const D: usize>(
+    builder: &mut CircuitBuilder<F, D>,
+    words: &[U32Target],
+    offset: usize,
+    bytes: &[u8],
+) {
+    let actual = bytes_at(builder, words, offset, bytes.len());
+    for (a, e) in actual.into_iter().zip(bytes_to_sha256_words(bytes)) {
+        let e = builder.constant_u32(e);
+        builder.connect_u32(a, e);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+    use num::Num;
+    use plonky2::iop::witness::PartialWitness;
+    use plonky2::plonk::circuit_data::CircuitConfig;
+    use plonky2::plonk::config::{GenericConfig, PoseidonGoldilocksConfig};
+    use plonky2_u32::witness::WitnessU32;
+
+    use super::*;
+    use crate::gadgets::biguint::WitnessBigUint;
+
+    const MODULUS: &str = "c5b074e5181c9244fbad397bae549bd0b4b4be0d67918b9e0cae2711167ea8f3\
+    a046e58ddbad4d2dbbee7e0fca4a3000d6a3bee5fbdd1a428318bc81956149e7\
+    2f7cff0245ddf7d7bd9d56afc04cf65123fa52392f75317826637e94dd2ff038\
+    c5fbe4dd3fb239e8922bcd5a1f14aaa4922b13e362eb89f03a0c24fe2daa652c\
+    e385333425ee13aacf1355fb6700c29cc9d0a2317f11aa1e166663b99c397ab2\
+    aace455febffc31ac47b705e4b264a389dfa2df970a445a2e5e39c5c1af49816\
+    a534e41e922e54c0a774f45bad69c481bd152eee98cd92f686bc5d90180e1762\
+    d48aa6558047ea666bb1c69e0ae94fd77f86166dc5c1cec7d26bb9b81e6e3349";
+
+    const MSG: &[u8] = b"PIRANHAS legacy device certificate";
+
+    /// `MSG` signed with SHA-512 under `MODULUS`.
+    const SHA512_PKCS1V15_SIGNATURE: &str =
+        "7ee2c2944fb3649ff5fd2f443444e8e578c8055f97870764e295f2b90734ef2b\
+    7adf7148643f737000d388f8c670677a32aea66ffb8a7398e288043abbd09e58\
+    1b718e1af91361dbb45a70d2115d98186cb1e71e6e6d67483832cdb30915cdaf\
+    726948f41973306a5e55b41a3e35e8cb2234d9eb6c2310e4be6f78e84e3fd7d7\
+    3d3e593a5640c05c5bb4451290cea90fb2747dfd5a60d2723f0f0e37c227efe9\
+    80f6365e5f5db5c2ab27af17f05a0e81ed1304e1fa2f4341bf5ca040f6426b00\
+    15ee4eb52bd830fa12b35e6be0562d7e7c2f9380fd078ec6a13808c18eab0913\
+    91332fccf3eb4fe00689cbd0cb120ec01d4f8417111a39a7d1ed8f7dac8d7e23";
+
+    /// `MSG` signed with SHA-512, MGF1-SHA-512 and a 64-byte salt under `MODULUS`.
+    const SHA512_PSS_SIGNATURE: &str =
+        "c349c54be82b779db004b6dfd6ca4dae9f66423b8e4dacf9f4482f07efb8479d\
+    2c4ea442681909094e0f2a785a65d1e50a2648c379188081744fa45d3958ee12\
+    c0603e236f1de49c9f9f1591d6706a2c32421627524b33f2b13e230a94f0534e\
+    9fe7db4dc1e47c4c46d0c3dd797adc78b888d3324995827d7dff8e1558db037e\
+    c67b9ee8ac421481b5a029add82849f5abaee92e27d842adb06a6822b5f9eb06\
+    e0688ecee5adedb0c5f14e3b51d540a92c9b489936a8c333cfeca8b223ef50d0\
+    5dd8cb3fb52443dddabf5e77052b47b03f77daa22ca6b3fc5071fd486de83721\
+    9f1be3416d1258d29960f79d135efcb7b873b6030e2fa9302a8a9c1aa6823d3f";
+
+    /// `MSG` signed with SHA-256 under `MODULUS`.
+    const SHA256_PKCS1V15_SIGNATURE: &str =
+        "57e4ba09d84f9cf6f1875a38edf16ff0abcb4d1d4cb9dba98945d71ee7585dca\
+    794357795d4ed1ed6b06f4319e8827f3613b6e07d9f926307c1d0f8555525128\
+    929f5e9fa6d1b516f0336b407d25e658d468cb5cb447b9a6c5773e330e2f5253\
+    7f02a986b725d272c9dfdfad9ff09f8819ce2183510d470663fd91fd38cbb7e0\
+    850a775b1fe1c506df06bbd0b02ac8bbb326d4f9b6b59eb33677f6ec79a3ace5\
+    ace150076dde155f98f2cf0fd6579e98e58b301c702128cc94326ac7d9173188\
+    7da0a879e63d809609a279a5c07c57e88f21a7851f79dff13d5f07bf7d62035b\
+    065ccee9d281c2455db3dcaf1e73dbc7bb88a8b37ad5439d9f619f1582d89ab3";
+
+    /// `MSG` signed with SHA-256, MGF1-SHA-256 and a 32-byte salt under `MODULUS`.
+    const SHA256_PSS_SIGNATURE: &str =
+        "18ff9bb6a2073f6b107c63ef9552cced11ec4a0952058a81a52cdc4839860457\
+    788da583072f406d630b825afc606cac2e809a57a09fd3e77a16d602ef974e9f\
+    29457cef96033667c676d9883340d3b938b9bff6c4b4511f75a3efeaa001c277\
+    fb7fac090963b640e41214bd5f0f6e2730a0317e1a9f89ae12467b87c20a74ad\
+    62bb790f1b22802847cdba4d683c2e7854ef30359c6c974f4f0eb6afabf09db0\
+    afa9f9a42f5f05b0e0b7e4d0241168454a0ce495c0e87356f7853d1a1435cc39\
+    f42277c111886a64060cdddc40110bbc909162d59b1f9a680b5840e5fe19fe71\
+    7ef5050d4b966f9cca041f0d45525754874129fe80c997924f207bbd41db2eca";
+
+    enum Padding {
+        Pkcs1v15,
+        Pss { salt_len: usize },
+    }
+
+    fn test_rsa_circuit_with<H: RSAHash>(msg: &[u8], sig: &str, padding: Padding) -> Result<()> {
+        const D: usize = 2;
+        type C = PoseidonGoldilocksConfig;
+        type F = <C as GenericConfig<D>>::F;
+
+        let config = CircuitConfig::standard_recursion_config();
+        let mut pw = PartialWitness::new();
+        let mut builder = CircuitBuilder::<F, D>::new(config);
+
+        let msg_words = bytes_to_sha256_words(msg);
+        let msg_targets = builder.add_virtual_u32_targets(msg_words.len());
+        let pk_target = RSAPublicKeyTarget(builder.add_virtual_biguint_target(64));
+        let sig_target = RSASignatureTarget(builder.add_virtual_biguint_target(64));
+
+        match padding {
+            Padding::Pkcs1v15 => verify_pkcs1v15_circuit::<H, F, D>(
+                &mut builder,
+                &msg_targets,
+                msg.len(),
+                &sig_target,
+                &pk_target,
+            ),
+            Padding::Pss { salt_len } => verify_pss_circuit::<H, F, D>(
+                &mut builder,
+                &msg_targets,
+                msg.len(),
+                &sig_target,
+                &pk_target,
+                salt_len,
+            ),
+        }
+
+        for (&t, w) in msg_targets.iter().zip(msg_words) {
+            pw.set_u32_target(t, w);
+        }
+        pw.set_biguint_target(&pk_target.0, &BigUint::from_str_radix(MODULUS, 16).unwrap());
+        pw.set_biguint_target(&sig_target.0, &BigUint::from_str_radix(sig, 16).unwrap());
+
+        let data = builder.build::<C>();
+        let proof = data.prove(pw)?;
+        data.verify(proof)
+    }
+
+    #[test]
+    #[ignore]
+    fn test_rsa_sha512_pkcs1v15_circuit() -> Result<()> {
+        test_rsa_circuit_with::<Sha512>(MSG, SHA512_PKCS1V15_SIGNATURE, Padding::Pkcs1v15)
+    }
+
+    #[test]
+    #[ignore]
+    fn test_rsa_sha512_pss_circuit() -> Result<()> {
+        test_rsa_circuit_with::<Sha512>(MSG, SHA512_PSS_SIGNATURE, Padding::Pss { salt_len: 64 })
+    }
+
+    #[test]
+    #[ignore]
+    fn test_rsa_sha256_pkcs1v15_circuit() -> Result<()> {
+        test_rsa_circuit_with::<Sha256>(MSG, SHA256_PKCS1V15_SIGNATURE, Padding::Pkcs1v15)
+    }
+
+    #[test]
+    #[ignore]
+    fn test_rsa_sha256_pss_circuit() -> Result<()> {
+        test_rsa_circuit_with::<Sha256>(MSG, SHA256_PSS_SIGNATURE, Padding::Pss { salt_len: 32 })
+    }
+
+    #[test]
+    #[ignore]
+    fn test_rsa_circuit_wrong_message() {
+        let msg = b"PIRANHAS forged device certificate";
+        let pkcs1v15 =
+            test_rsa_circuit_with::<Sha256>(msg, SHA256_PKCS1V15_SIGNATURE, Padding::Pkcs1v15);
+        assert!(pkcs1v15.is_err());
+        let pss = test_rsa_circuit_with::<Sha512>(
+            msg,
+            SHA512_PSS_SIGNATURE,
+            Padding::Pss { salt_len: 64 },
+        );
+        assert!(pss.is_err());
+    }
+}
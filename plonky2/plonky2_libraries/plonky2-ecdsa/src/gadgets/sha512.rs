@@ -0,0 +1,350 @@
+use alloc::vec::Vec;
+
+use plonky2::field::extension::Extendable;
+use plonky2::hash::hash_types::RichField;
+use plonky2::plonk::circuit_builder::CircuitBuilder;
+use plonky2_u32::gadgets::arithmetic_u32::{CircuitBuilderU32, U32Target};
+use plonky2_u32::gadgets::arithmetic_uint::{CircuitBuilderUInt, U64Target, UIntTarget};
+use plonky2_u32::gadgets::bitwise_u32::CircuitBuilderBitwiseU32;
+
+const ROUND_CONSTANTS: [u64; 80] = [
+    0x428a2f98d728ae22,
+    0x7137449123ef65cd,
+    0xb5c0fbcfec4d3b2f,
+    0xe9b5dba58189dbbc,
+    0x3956c25bf348b538,
+    0x59f111f1b605d019,
+    0x923f82a4af194f9b,
+    0xab1c5ed5da6d8118,
+    0xd807aa98a3030242,
+    0x12835b0145706fbe,
+    0x243185be4ee4b28c,
+    0x550c7dc3d5ffb4e2,
+    0x72be5d74f27b896f,
+    0x80deb1fe3b1696b1,
+    0x9bdc06a725c71235,
+    0xc19bf174cf692694,
+    0xe49b69c19ef14ad2,
+    0xefbe4786384f25e3,
+    0x0fc19dc68b8cd5b5,
+    0x240ca1cc77ac9c65,
+    0x2de92c6f592b0275,
+    0x4a7484aa6ea6e483,
+    0x5cb0a9dcbd41fbd4,
+    0x76f988da831153b5,
+    0x983e5152ee66dfab,
+    0xa831c66d2db43210,
+    0xb00327c898fb213f,
+    0xbf597fc7beef0ee4,
+    0xc6e00bf33da88fc2,
+    0xd5a79147930aa725,
+    0x06ca6351e003826f,
+    0x142929670a0e6e70,
+    0x27b70a8546d22ffc,
+    0x2e1b21385c26c926,
+    0x4d2c6dfc5ac42aed,
+    0x53380d139d95b3df,
+    0x650a73548baf63de,
+    0x766a0abb3c77b2a8,
+    0x81c2c92e47edaee6,
+    0x92722c851482353b,
+    0xa2bfe8a14cf10364,
+    0xa81a664bbc423001,
+    0xc24b8b70d0f89791,
+    0xc76c51a30654be30,
+    0xd192e819d6ef5218,
+    0xd69906245565a910,
+    0xf40e35855771202a,
+    0x106aa07032bbd1b8,
+    0x19a4c116b8d2d0c8,
+    0x1e376c085141ab53,
+    0x2748774cdf8eeb99,
+    0x34b0bcb5e19b48a8,
+    0x391c0cb3c5c95a63,
+    0x4ed8aa4ae3418acb,
+    0x5b9cca4f7763e373,
+    0x682e6ff3d6b2b8a3,
+    0x748f82ee5defb2fc,
+    0x78a5636f43172f60,
+    0x84c87814a1f0ab72,
+    0x8cc702081a6439ec,
+    0x90befffa23631e28,
+    0xa4506cebde82bde9,
+    0xbef9a3f7b2c67915,
+    0xc67178f2e372532b,
+    0xca273eceea26619c,
+    0xd186b8c721c0c207,
+    0xeada7dd6cde0eb1e,
+    0xf57d4f7fee6ed178,
+    0x06f067aa72176fba,
+    0x0a637dc5a2c898a6,
+    0x113f9804bef90dae,
+    0x1b710b35131c471b,
+    0x28db77f523047d84,
+    0x32caab7b40c72493,
+    0x3c9ebe0a15c9bebc,
+    0x431d67c49c100d4c,
+    0x4cc5d4becb3e42b6,
+    0x597f299cfc657e2a,
+    0x5fcb6fab3ad6faec,
+    0x6c44198c4a475817,
+];
+
+const INITIAL_HASH: [u64; 8] = [
+    0x6a09e667f3bcc908,
+    0xbb67ae8584caa73b,
+    0x3c6ef372fe94f82b,
+    0xa54ff53a5f1d36f1,
+    0x510e527fade682d1,
+    0x9b05688c2b3e6c1f,
+    0x1f83d9abfb41bd6b,
+    0x5be0cd19137e2179,
+];
+
+pub trait CircuitBuilderSha512<F: RichField + Extendable<D>, const D: usize> {
+    /// Computes SHA-512 of a message of `num_bytes` bytes, given as big-endian 32-bit words as in
+    /// `sha256`. The unused low bytes of the last word must be zero. Returns the digest as
+    /// big-endian 32-bit words.
+    fn sha512(&mut self, msg: &[U32Target], num_bytes: usize) -> [U32Target; 16];
+
+    /// Applies the SHA-512 compression function to `state` and a 16-word block.
+    fn sha512_compress(&mut self, state: &[U64Target; 8], block: &[U64Target]) -> [U64Target; 8];
+}
+
+impl<F: RichField + Extendable<D>, const D: usize> CircuitBuilderSha512<F, D>
+    for CircuitBuilder<F, D>
+{
+    fn sha512(&mut self, msg: &[U32Target], num_bytes: usize) -> [U32Target; 16] {
+        assert_eq!(
+            msg.len(),
+            num_bytes.div_ceil(4),
+            "SHA-512 input must have one word per four bytes"
+        );
+
+        let mut padded = msg.to_vec();
+        let partial_bytes = num_bytes % 4;
+        if partial_bytes == 0 {
+            padded.push(self.constant_u32(0x80000000));
+        } else {
+            let last = padded.pop().unwrap();
+            let unused = self.shl_u32(last, 8 * partial_bytes);
+            self.assert_zero_u32(unused);
+            let marker = self.constant_u32(0x80 << (8 * (3 - partial_bytes)));
+            padded.push(U32Target(self.add(last.0, marker.0)));
+        }
+        // The length takes 128 bits, of which the top 64 are always zero here.
+        while padded.len() % 32 != 28 {
+            padded.push(self.zero_u32());
+        }
+        padded.push(self.zero_u32());
+        padded.push(self.zero_u32());
+        let num_bits = 8 * num_bytes as u64;
+        padded.push(self.constant_u32((num_bits >> 32) as u32));
+        padded.push(self.constant_u32(num_bits as u32));
+
+        let words: Vec<U64Target> = padded
+            .chunks(2)
+            .map(|pair| UIntTarget {
+                limbs: [pair[1], pair[0]],
+            })
+            .collect();
+        let mut state = INITIAL_HASH.map(|h| self.constant_u64(h));
+        for block in words.chunks(16) {
+            state = self.sha512_compress(&state, block);
+        }
+
+        let mut digest = [self.zero_u32(); 16];
+        for (i, word) in state.iter().enumerate() {
+            digest[2 * i] = word.limbs[1];
+            digest[2 * i + 1] = word.limbs[0];
+        }
+        digest
+    }
+
+    fn sha512_compress(&mut self, state: &[U64Target; 8], block: &[U64Target]) -> [U64Target; 8] {
+        assert_eq!(block.len(), 16, "SHA-512 blocks have 16 words");
+
+        let mut w = block.to_vec();
+        for t in 16..80 {
+            let s0 = {
+                let x = w[t - 15];
+                let (r1, r8, s7) = (rotr(self, x, 1), rotr(self, x, 8), self.shr_uint(x, 7));
+                xor3(self, r1, r8, s7)
+            };
+            let s1 = {
+                let x = w[t - 2];
+                let (r19, r61, s6) = (rotr(self, x, 19), rotr(self, x, 61), self.shr_uint(x, 6));
+                xor3(self, r19, r61, s6)
+            };
+            let next = add_many(self, &[s1, w[t - 7], s0, w[t - 16]]);
+            w.push(next);
+        }
+
+        let [mut a, mut b, mut c, mut d, mut e, mut f, mut g, mut h] = *state;
+        for t in 0..80 {
+            let big_s1 = {
+                let (r14, r18, r41) = (rotr(self, e, 14), rotr(self, e, 18), rotr(self, e, 41));
+                xor3(self, r14, r18, r41)
+            };
+            // As in SHA-256, the two sides of `ch` and `maj` never share a set bit, so they are
+            // added rather than XORed.
+            let ch = limbwise(self, [e, f, g], |builder, [e, f, g]| {
+                let e_and_f = builder.and_u32(e, f);
+                let not_e = builder.not_u32(e);
+                let not_e_and_g = builder.and_u32(not_e, g);
+                U32Target(builder.add(e_and_f.0, not_e_and_g.0))
+            });
+            let k = self.constant_u64(ROUND_CONSTANTS[t]);
+            let t1 = add_many(self, &[h, big_s1, ch, k, w[t]]);
+
+            let big_s0 = {
+                let (r28, r34, r39) = (rotr(self, a, 28), rotr(self, a, 34), rotr(self, a, 39));
+                xor3(self, r28, r34, r39)
+            };
+            let maj = limbwise(self, [a, b, c], |builder, [a, b, c]| {
+                let b_xor_c = builder.xor_u32(b, c);
+                let a_and_b_xor_c = builder.and_u32(a, b_xor_c);
+                let b_and_c = builder.and_u32(b, c);
+                U32Target(builder.add(a_and_b_xor_c.0, b_and_c.0))
+            });
+
+            h = g;
+            g = f;
+            f = e;
+            e = add_many(self, &[d, t1]);
+            d = c;
+            c = b;
+            b = a;
+            a = add_many(self, &[t1, big_s0, maj]);
+        }
+
+        let updated = [a, b, c, d, e, f, g, h];
+        core::array::from_fn(|i| add_many(self, &[state[i], updated[i]]))
+    }
+}
+
+/// Applies `op` to the low limbs of `words`, and then to their high limbs.
+fn limbwise<F: RichField + Extendable<D>, const D: usize, const K: usize>(
+    builder: &mut CircuitBuilder<F, D>,
+    words: [U64Target; K],
+    mut op: impl FnMut(&mut CircuitBuilder<F, D>, [U32Target; K]) -> U32Target,
+) -> U64Target {
+    UIntTarget {
+        limbs: core::array::from_fn(|i| op(builder, words.map(|w| w.limbs[i]))),
+    }
+}
+
+fn xor3<F: RichField + Extendable<D>, const D: usize>(
+    builder: &mut CircuitBuilder<F, D>,
+    x: U64Target,
+    y: U64Target,
+    z: U64Target,
+) -> U64Target {
+    limbwise(builder, [x, y, z], |builder, [x, y, z]| {
+        let x_xor_y = builder.xor_u32(x, y);
+        builder.xor_u32(x_xor_y, z)
+    })
+}
+
+/// Rotates `x` right by `n` bits. Multiplying a limb by `2^(32 - n)` splits it into the bits that
+/// move down, in the high half of the product, and those that wrap into the other limb, in the
+/// low half, so each rotation takes two multiplications.
+fn rotr<F: RichField + Extendable<D>, const D: usize>(
+    builder: &mut CircuitBuilder<F, D>,
+    x: U64Target,
+    n: usize,
+) -> U64Target {
+    let [mut lo, mut hi] = x.limbs;
+    if n >= 32 {
+        (lo, hi) = (hi, lo);
+    }
+    let n = n % 32;
+    if n == 0 {
+        return UIntTarget { limbs: [lo, hi] };
+    }
+
+    let factor = builder.constant_u32(1 << (32 - n));
+    let (lo_wrapped, lo_kept) = builder.mul_u32(lo, factor);
+    let (hi_wrapped, hi_kept) = builder.mul_u32(hi, factor);
+    UIntTarget {
+        limbs: [
+            U32Target(builder.add(lo_kept.0, hi_wrapped.0)),
+            U32Target(builder.add(hi_kept.0, lo_wrapped.0)),
+        ],
+    }
+}
+
+/// Adds words modulo `2^64`.
+fn add_many<F: RichField + Extendable<D>, const D: usize>(
+    builder: &mut CircuitBuilder<F, D>,
+    words: &[U64Target],
+) -> U64Target {
+    let lo_terms: Vec<_> = words.iter().map(|w| w.limbs[0]).collect();
+    let (lo, carry) = builder.add_many_u32(&lo_terms);
+    let hi_terms: Vec<_> = words.iter().map(|w| w.limbs[1]).collect();
+    let (hi, _) = builder.add_u32s_with_carry(&hi_terms, carry);
+    UIntTarget { limbs: [lo, hi] }
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+    use plonky2::iop::witness::PartialWitness;
+    use plonky2::plonk::circuit_builder::CircuitBuilder;
+    use plonky2::plonk::circuit_data::CircuitConfig;
+    use plonky2::plonk::config::{GenericConfig, PoseidonGoldilocksConfig};
+    use plonky2_u32::gadgets::arithmetic_u32::CircuitBuilderU32;
+    use plonky2_u32::gadgets::sha256::bytes_to_sha256_words;
+    use plonky2_u32::witness::WitnessU32;
+    use sha2::{Digest, Sha512};
+
+    use crate::gadgets::sha512::CircuitBuilderSha512;
+
+    fn test_sha512_with(msg: &[u8]) -> Result<()> {
+        const D: usize = 2;
+        type C = PoseidonGoldilocksConfig;
+        type F = <C as GenericConfig<D>>::F;
+
+        let config = CircuitConfig::standard_recursion_config();
+
+        let mut pw = PartialWitness::new();
+        let mut builder = CircuitBuilder::<F, D>::new(config);
+
+        let words = bytes_to_sha256_words(msg);
+        let msg_targets = builder.add_virtual_u32_targets(words.len());
+        let digest = builder.sha512(&msg_targets, msg.len());
+
+        let expected = bytes_to_sha256_words(&Sha512::digest(msg));
+        for (&d, e) in digest.iter().zip(expected) {
+            let e = builder.constant_u32(e);
+            builder.connect_u32(d, e);
+        }
+
+        for (&t, w) in msg_targets.iter().zip(words) {
+            pw.set_u32_target(t, w);
+        }
+
+        let data = builder.build::<C>();
+        let proof = data.prove(pw).unwrap();
+
+        data.verify(proof)
+    }
+
+    #[test]
+    fn test_sha512_empty() -> Result<()> {
+        test_sha512_with(b"")
+    }
+
+    #[test]
+    fn test_sha512_abc() -> Result<()> {
+        test_sha512_with(b"abc")
+    }
+
+    #[test]
+    fn test_sha512_two_blocks() -> Result<()> {
+        test_sha512_with(
+            b"abcdefghbcdefghicdefghijdefghijkefghijklfghijklmghijklmnhijklmno\
+              ijklmnopjklmnopqklmnopqrlmnopqrsmnopqrstnopqrstu",
+        )
+    }
+}
@@ -0,0 +1,325 @@
+use alloc::vec::Vec;
+use core::marker::PhantomData;
+
+use plonky2::field::extension::Extendable;
+use plonky2::field::p256_base::P256Base;
+use plonky2::field::p256_scalar::P256Scalar;
+use plonky2::field::secp256k1_base::Secp256K1Base;
+use plonky2::field::secp256k1_scalar::Secp256K1Scalar;
+use plonky2::field::types::Field;
+use plonky2::gadgets::arithmetic::EqualityGenerator;
+use plonky2::gadgets::arithmetic_extension::QuotientGeneratorExtension;
+use plonky2::gadgets::range_check::LowHighGenerator;
+use plonky2::gadgets::split_base::BaseSumGenerator;
+use plonky2::gadgets::split_join::{SplitGenerator, WireSplitGenerator};
+use plonky2::gates::arithmetic_base::{ArithmeticBaseGenerator, ArithmeticGate};
+use plonky2::gates::arithmetic_extension::{ArithmeticExtensionGate, ArithmeticExtensionGenerator};
+use plonky2::gates::base_sum::{BaseSplitGenerator, BaseSumGate};
+use plonky2::gates::constant::ConstantGate;
+use plonky2::gates::coset_interpolation::{CosetInterpolationGate, InterpolationGenerator};
+use plonky2::gates::exponentiation::{ExponentiationGate, ExponentiationGenerator};
+use plonky2::gates::lookup::{LookupGate, LookupGenerator};
+use plonky2::gates::lookup_table::{LookupTableGate, LookupTableGenerator};
+use plonky2::gates::multiplication_extension::{MulExtensionGate, MulExtensionGenerator};
+use plonky2::gates::noop::NoopGate;
+use plonky2::gates::poseidon::{PoseidonGate, PoseidonGenerator};
+use plonky2::gates::poseidon_mds::{PoseidonMdsGate, PoseidonMdsGenerator};
+use plonky2::gates::public_input::PublicInputGate;
+use plonky2::gates::random_access::{RandomAccessGate, RandomAccessGenerator};
+use plonky2::gates::reducing::{ReducingGate, ReducingGenerator};
+use plonky2::gates::reducing_extension::{
+    ReducingExtensionGate, ReducingGenerator as ReducingExtensionGenerator,
+};
+use plonky2::hash::hash_types::RichField;
+use plonky2::iop::generator::{
+    ConstantGenerator, CopyGenerator, NonzeroTestGenerator, RandomValueGenerator,
+};
+use plonky2::plonk::config::{AlgebraicHasher, GenericConfig};
+use plonky2::recursion::dummy_circuit::DummyProofGenerator;
+use plonky2::util::serialization::gate_serialization::log;
+use plonky2::util::serialization::{
+    Buffer, GateSerializer, IoResult, Read, WitnessGeneratorSerializer, Write,
+};
+use plonky2::{
+    get_gate_tag_impl, get_generator_tag_impl, impl_gate_serializer, impl_generator_serializer,
+    read_gate_impl, read_generator_impl,
+};
+use plonky2_u32::gates::add_many_u32::{U32AddManyGate, U32AddManyGenerator};
+use plonky2_u32::gates::arithmetic_u32::{U32ArithmeticGate, U32ArithmeticGenerator};
+use plonky2_u32::gates::bitwise_u32::{U32BitwiseGate, U32BitwiseGenerator};
+use plonky2_u32::gates::comparison::{ComparisonGate, ComparisonGenerator};
+use plonky2_u32::gates::range_check_u32::{U32RangeCheckGate, U32RangeCheckGenerator};
+use plonky2_u32::gates::subtraction_u32::{U32SubtractionGate, U32SubtractionGenerator};
+use plonky2_u32::serialization::{ReadU32, WriteU32};
+
+use crate::gadgets::biguint::{BigUintDivRemGenerator, BigUintTarget};
+use crate::gadgets::glv::GLVDecompositionGenerator;
+use crate::gadgets::nonnative::{
+    NonNativeAdditionGenerator, NonNativeInverseGenerator, NonNativeMulAddGenerator,
+    NonNativeMultipleAddsGenerator, NonNativeMultiplicationGenerator,
+    NonNativeSubtractionGenerator, NonNativeTarget,
+};
+
+pub trait WriteBigUint {
+    fn write_target_biguint(&mut self, x: &BigUintTarget) -> IoResult<()>;
+}
+
+impl WriteBigUint for Vec<u8> {
+    #[inline]
+    fn write_target_biguint(&mut self, x: &BigUintTarget) -> IoResult<()> {
+        self.write_usize(x.num_limbs())?;
+        for &limb in x.limbs.iter() {
+            self.write_target_u32(limb)?;
+        }
+        Ok(())
+    }
+}
+
+pub trait ReadBigUint {
+    fn read_target_biguint(&mut self) -> IoResult<BigUintTarget>;
+}
+
+impl ReadBigUint for Buffer<'_> {
+    #[inline]
+    fn read_target_biguint(&mut self) -> IoResult<BigUintTarget> {
+        let num_limbs = self.read_usize()?;
+        let limbs = (0..num_limbs)
+            .map(|_| self.read_target_u32())
+            .collect::<IoResult<Vec<_>>>()?;
+        Ok(BigUintTarget { limbs })
+    }
+}
+
+pub trait WriteNonNative {
+    fn write_target_nonnative<FF: Field>(&mut self, x: &NonNativeTarget<FF>) -> IoResult<()>;
+}
+
+impl WriteNonNative for Vec<u8> {
+    #[inline]
+    fn write_target_nonnative<FF: Field>(&mut self, x: &NonNativeTarget<FF>) -> IoResult<()> {
+        self.write_target_biguint(&x.value)
+    }
+}
+
+pub trait ReadNonNative {
+    fn read_target_nonnative<FF: Field>(&mut self) -> IoResult<NonNativeTarget<FF>>;
+}
+
+impl ReadNonNative for Buffer<'_> {
+    #[inline]
+    fn read_target_nonnative<FF: Field>(&mut self) -> IoResult<NonNativeTarget<FF>> {
+        Ok(NonNativeTarget {
+            value: self.read_target_biguint()?,
+            _phantom: PhantomData,
+        })
+    }
+}
+
+/// Serializes the default `plonky2` gates together with the `u32` gates and the base-4 `BaseSumGate`
+/// used by the gadgets of this crate.
+#[derive(Debug, Default)]
+pub struct EcdsaGateSerializer;
+
+impl<F: RichField + Extendable<D>, const D: usize> GateSerializer<F, D> for EcdsaGateSerializer {
+    impl_gate_serializer! {
+        EcdsaGateSerializer,
+        ArithmeticGate,
+        ArithmeticExtensionGate<D>,
+        BaseSumGate<2>,
+        BaseSumGate<4>,
+        ConstantGate,
+        CosetInterpolationGate<F, D>,
+        ExponentiationGate<F, D>,
+        LookupGate,
+        LookupTableGate,
+        MulExtensionGate<D>,
+        NoopGate,
+        PoseidonMdsGate<F, D>,
+        PoseidonGate<F, D>,
+        PublicInputGate,
+        RandomAccessGate<F, D>,
+        ReducingExtensionGate<D>,
+        ReducingGate<D>,
+        ComparisonGate<F, D>,
+        U32AddManyGate<F, D>,
+        U32ArithmeticGate<F, D>,
+        U32BitwiseGate<F, D>,
+        U32RangeCheckGate<F, D>,
+        U32SubtractionGate<F, D>
+    }
+}
+
+/// Serializes the default `plonky2` generators together with the `u32`, `BigUint`, GLV and
+/// nonnative generators over the secp256k1 and P-256 base and scalar fields.
+#[derive(Debug, Default)]
+pub struct EcdsaGeneratorSerializer<C: GenericConfig<D>, const D: usize> {
+    pub _phantom: PhantomData<C>,
+}
+
+impl<F, C, const D: usize> WitnessGeneratorSerializer<F, D> for EcdsaGeneratorSerializer<C, D>
+where
+    F: RichField + Extendable<D>,
+    C: GenericConfig<D, F = F> + 'static,
+    C::Hasher: AlgebraicHasher<F>,
+{
+    impl_generator_serializer! {
+        EcdsaGeneratorSerializer,
+        ArithmeticBaseGenerator<F, D>,
+        ArithmeticExtensionGenerator<F, D>,
+        BaseSplitGenerator<2>,
+        BaseSplitGenerator<4>,
+        BaseSumGenerator<2>,
+        BaseSumGenerator<4>,
+        ConstantGenerator<F>,
+        CopyGenerator,
+        DummyProofGenerator<F, C, D>,
+        EqualityGenerator,
+        ExponentiationGenerator<F, D>,
+        InterpolationGenerator<F, D>,
+        LookupGenerator,
+        LookupTableGenerator,
+        LowHighGenerator,
+        MulExtensionGenerator<F, D>,
+        NonzeroTestGenerator,
+        PoseidonGenerator<F, D>,
+        PoseidonMdsGenerator<D>,
+        QuotientGeneratorExtension<D>,
+        RandomAccessGenerator<F, D>,
+        RandomValueGenerator,
+        ReducingGenerator<D>,
+        ReducingExtensionGenerator<D>,
+        SplitGenerator,
+        WireSplitGenerator,
+        ComparisonGenerator<F, D>,
+        U32AddManyGenerator<F, D>,
+        U32ArithmeticGenerator<F, D>,
+        U32BitwiseGenerator<F, D>,
+        U32RangeCheckGenerator<F, D>,
+        U32SubtractionGenerator<F, D>,
+        BigUintDivRemGenerator<F, D>,
+        GLVDecompositionGenerator<F, D>,
+        NonNativeAdditionGenerator<F, D, Secp256K1Base>,
+        NonNativeMultipleAddsGenerator<F, D, Secp256K1Base>,
+        NonNativeSubtractionGenerator<F, D, Secp256K1Base>,
+        NonNativeMultiplicationGenerator<F, D, Secp256K1Base>,
+        NonNativeMulAddGenerator<F, D, Secp256K1Base>,
+        NonNativeInverseGenerator<F, D, Secp256K1Base>,
+        NonNativeAdditionGenerator<F, D, Secp256K1Scalar>,
+        NonNativeMultipleAddsGenerator<F, D, Secp256K1Scalar>,
+        NonNativeSubtractionGenerator<F, D, Secp256K1Scalar>,
+        NonNativeMultiplicationGenerator<F, D, Secp256K1Scalar>,
+        NonNativeMulAddGenerator<F, D, Secp256K1Scalar>,
+        NonNativeInverseGenerator<F, D, Secp256K1Scalar>,
+        NonNativeAdditionGenerator<F, D, P256Base>,
+        NonNativeMultipleAddsGenerator<F, D, P256Base>,
+        NonNativeSubtractionGenerator<F, D, P256Base>,
+        NonNativeMultiplicationGenerator<F, D, P256Base>,
+        NonNativeMulAddGenerator<F, D, P256Base>,
+        NonNativeInverseGenerator<F, D, P256Base>,
+        NonNativeAdditionGenerator<F, D, P256Scalar>,
+        NonNativeMultipleAddsGenerator<F, D, P256Scalar>,
+        NonNativeSubtractionGenerator<F, D, P256Scalar>,
+        NonNativeMultiplicationGenerator<F, D, P256Scalar>,
+        NonNativeMulAddGenerator<F, D, P256Scalar>,
+        NonNativeInverseGenerator<F, D, P256Scalar>
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+    use num::Integer;
+    use plonky2::field::types::{PrimeField, Sample};
+    use plonky2::iop::witness::PartialWitness;
+    use plonky2::plonk::circuit_builder::CircuitBuilder;
+    use plonky2::plonk::circuit_data::{CircuitConfig, CircuitData};
+    use plonky2::plonk::config::PoseidonGoldilocksConfig;
+
+    use super::*;
+    use crate::curve::glv::decompose_secp256k1_scalar;
+    use crate::gadgets::biguint::{CircuitBuilderBiguint, WitnessBigUint};
+    use crate::gadgets::glv::CircuitBuilderGlv;
+    use crate::gadgets::nonnative::CircuitBuilderNonNative;
+
+    #[test]
+    fn test_circuit_data_round_trip() -> Result<()> {
+        const D: usize = 2;
+        type C = PoseidonGoldilocksConfig;
+        type F = <C as GenericConfig<D>>::F;
+        type FF = Secp256K1Base;
+
+        let x = FF::rand();
+        let y = FF::rand();
+        let k = Secp256K1Scalar::rand();
+        let (k1, k2, k1_neg, k2_neg) = decompose_secp256k1_scalar(k);
+
+        let config = CircuitConfig::standard_ecc_config();
+        let mut builder = CircuitBuilder::<F, D>::new(config);
+
+        let num_limbs = CircuitBuilder::<F, D>::num_nonnative_limbs::<FF>();
+        let x_limbs = builder.add_virtual_biguint_target(num_limbs);
+        let y_limbs = builder.add_virtual_biguint_target(num_limbs);
+        let k_limbs = builder.add_virtual_biguint_target(num_limbs);
+        let x_target = builder.biguint_to_nonnative::<FF>(&x_limbs);
+        let y_target = builder.biguint_to_nonnative::<FF>(&y_limbs);
+        let k_target = builder.biguint_to_nonnative::<Secp256K1Scalar>(&k_limbs);
+
+        // one instance of every generator of this crate
+        let results = [
+            (builder.add_nonnative(&x_target, &y_target), x + y),
+            (builder.sub_nonnative(&x_target, &y_target), x - y),
+            (builder.mul_nonnative(&x_target, &y_target), x * y),
+            (
+                builder.mul_add_nonnative(&x_target, &y_target, &x_target),
+                x * y + x,
+            ),
+            (builder.inv_nonnative(&x_target), x.inverse()),
+            (
+                builder.add_many_nonnative(&[x_target.clone(), y_target.clone(), x_target.clone()]),
+                x + y + x,
+            ),
+        ];
+        for (target, value) in results.iter() {
+            let expected = builder.constant_nonnative(*value);
+            builder.connect_nonnative(target, &expected);
+        }
+
+        let (div, rem) = builder.div_rem_biguint(&x_limbs, &y_limbs);
+        let (div_value, rem_value) = x.to_canonical_biguint().div_rem(&y.to_canonical_biguint());
+        let expected_div = builder.constant_biguint(&div_value);
+        let expected_rem = builder.constant_biguint(&rem_value);
+        builder.connect_biguint(&div, &expected_div);
+        builder.connect_biguint(&rem, &expected_rem);
+
+        let (k1_target, k2_target, k1_neg_target, k2_neg_target) =
+            builder.decompose_secp256k1_scalar(&k_target);
+        let expected_k1 = builder.constant_nonnative(k1);
+        let expected_k2 = builder.constant_nonnative(k2);
+        builder.connect_nonnative(&k1_target, &expected_k1);
+        builder.connect_nonnative(&k2_target, &expected_k2);
+        let expected_k1_neg = builder.constant_bool(k1_neg);
+        let expected_k2_neg = builder.constant_bool(k2_neg);
+        builder.connect(k1_neg_target.target, expected_k1_neg.target);
+        builder.connect(k2_neg_target.target, expected_k2_neg.target);
+
+        let data = builder.build::<C>();
+
+        let gate_serializer = EcdsaGateSerializer;
+        let generator_serializer = EcdsaGeneratorSerializer::<C, D>::default();
+        let bytes = data
+            .to_bytes(&gate_serializer, &generator_serializer)
+            .unwrap();
+        let data_from_bytes =
+            CircuitData::<F, C, D>::from_bytes(&bytes, &gate_serializer, &generator_serializer)
+                .unwrap();
+        assert_eq!(data, data_from_bytes);
+
+        let mut pw = PartialWitness::new();
+        pw.set_biguint_target(&x_limbs, &x.to_canonical_biguint());
+        pw.set_biguint_target(&y_limbs, &y.to_canonical_biguint());
+        pw.set_biguint_target(&k_limbs, &k.to_canonical_biguint());
+
+        let proof = data_from_bytes.prove(pw)?;
+        data.verify(proof)
+    }
+}
@@ -0,0 +1,91 @@
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use plonky2::iop::target::Target;
+use plonky2::iop::witness::{PartialWitness, WitnessWrite};
+use plonky2::plonk::circuit_builder::CircuitBuilder;
+use plonky2::plonk::circuit_data::{CircuitConfig, CircuitData};
+use plonky2::plonk::config::{GenericConfig, PoseidonGoldilocksConfig};
+use plonky2_ecgfp5::schnorr::{
+    CircuitBuilderSchnorr, PartialWitnessSchnorr, SecretKey, SchnorrPublicKeyTarget, SchnorrSignatureTarget,
+};
+use plonky2_field::types::Sample;
+use rand::thread_rng;
+
+const D: usize = 2;
+type C = PoseidonGoldilocksConfig;
+type F = <C as GenericConfig<D>>::F;
+
+const MSG_LEN: usize = 8;
+
+struct SchnorrCircuit {
+    data: CircuitData<F, C, D>,
+    pks: Vec<SchnorrPublicKeyTarget>,
+    msgs: Vec<Vec<Target>>,
+    sigs: Vec<SchnorrSignatureTarget>,
+}
+
+fn build(n: usize, batch: bool) -> SchnorrCircuit {
+    let config = CircuitConfig::standard_recursion_config();
+    let mut builder = CircuitBuilder::<F, D>::new(config);
+
+    let pks: Vec<_> = (0..n).map(|_| builder.add_virtual_schnorr_public_key_target()).collect();
+    let msgs: Vec<_> = (0..n).map(|_| builder.add_virtual_targets(MSG_LEN)).collect();
+    let sigs: Vec<_> = (0..n).map(|_| builder.add_virtual_schnorr_signature_target()).collect();
+
+    if batch {
+        builder.verify_schnorr_batch(&pks, &msgs, &sigs);
+    } else {
+        for i in 0..n {
+            builder.verify_schnorr(pks[i], &msgs[i], &sigs[i]);
+        }
+    }
+
+    let num_gates = builder.num_gates();
+    let data = builder.build::<C>();
+    println!(
+        "{} verification of {} signatures: {} gates, 2^{} rows",
+        if batch { "batch" } else { "single" },
+        n,
+        num_gates,
+        data.common.degree_bits()
+    );
+
+    SchnorrCircuit { data, pks, msgs, sigs }
+}
+
+fn witness(circuit: &SchnorrCircuit) -> PartialWitness<F> {
+    let mut rng = thread_rng();
+    let mut pw = PartialWitness::new();
+    for i in 0..circuit.pks.len() {
+        let sk = SecretKey::sample(&mut rng);
+        let msg = F::rand_vec(MSG_LEN);
+        pw.set_schnorr_public_key_target(circuit.pks[i], sk.public_key());
+        pw.set_schnorr_signature_target(&circuit.sigs[i], &sk.sign(&msg));
+        for (&t, &m) in circuit.msgs[i].iter().zip(msg.iter()) {
+            pw.set_target(t, m).unwrap();
+        }
+    }
+    pw
+}
+
+pub fn bench_schnorr(c: &mut Criterion) {
+    let mut group = c.benchmark_group("schnorr_prove");
+    group.sample_size(10);
+
+    for n in [2, 4, 8] {
+        for (name, batch) in [("single", false), ("batch", true)] {
+            let circuit = build(n, batch);
+            group.bench_with_input(BenchmarkId::new(name, n), &circuit, |b, circuit| {
+                b.iter_batched(
+                    || witness(circuit),
+                    |pw| circuit.data.prove(pw).unwrap(),
+                    criterion::BatchSize::LargeInput,
+                )
+            });
+        }
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_schnorr);
+criterion_main!(benches);
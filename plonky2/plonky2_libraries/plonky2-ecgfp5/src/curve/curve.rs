@@ -5,13 +5,15 @@
 use std::ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign};
 
 use alloc::vec::Vec;
+use plonky2::hash::hashing::hash_n_to_m_no_pad;
+use plonky2::hash::poseidon::PoseidonPermutation;
 use plonky2_field::extension::quintic::QuinticExtension;
 use plonky2_field::goldilocks_field::GoldilocksField;
 use plonky2_field::ops::Square;
 use plonky2_field::types::{Field, Sample};
 use rand::RngCore;
 
-use crate::curve::base_field::{Legendre, SquareRoot};
+use crate::curve::base_field::{Legendre, Sgn0, SquareRoot};
 use crate::curve::mul_table::*;
 use crate::curve::scalar_field::Scalar;
 use crate::curve::{GFp, GFp5};
@@ -38,6 +40,9 @@ pub(crate) struct AffinePoint {
     pub(crate) u: GFp5,
 }
 
+// Domain separator for `hash_to_curve`, distinct from the ones used in `schnorr`.
+pub(crate) const HASH_TO_CURVE_DOMAIN: u64 = 4;
+
 /// A curve point in short Weirstrass form (x, y). This is used by the in-circuit representation
 #[derive(Clone, Copy, Debug)]
 pub struct WeierstrassPoint {
@@ -120,6 +125,62 @@ impl WeierstrassPoint {
             None
         }
     }
+
+    // Non-square `Z` of the simplified SWU map: the smallest integer such that `Z != -1`,
+    // `g(x) - Z` is irreducible and `g(B / (Z*A))` is a square, where `g(x) = x^3 + A*x + B`.
+    pub const SSWU_Z: GFp5 = QuinticExtension([
+        GoldilocksField(14),
+        GFp::ZERO,
+        GFp::ZERO,
+        GFp::ZERO,
+        GFp::ZERO,
+    ]);
+
+    /// Maps a field element to a point of the group, using the simplified SWU map of the
+    /// IETF hash-to-curve document on the short Weierstrass form. The double-odd curve has
+    /// order `2*n`; when the SWU output lies outside the subgroup of order `n` (i.e. its `x`
+    /// coordinate in the original form is not a square), we add the point `N` of order 2.
+    pub fn map_to_curve(u: GFp5) -> Self {
+        let (a, b, z) = (Self::A, Self::B, Self::SSWU_Z);
+        let g = |x: GFp5| x.square() * x + a * x + b;
+
+        let zu2 = z * u.square();
+        let tv1 = (zu2.square() + zu2).inverse_or_zero();
+        let x1 = if tv1 == GFp5::ZERO {
+            b / (z * a)
+        } else {
+            -b / a * (GFp5::ONE + tv1)
+        };
+        let x2 = zu2 * x1;
+
+        let gx1 = g(x1);
+        let (x, gx) = if gx1.legendre() != -GFp::ONE { (x1, gx1) } else { (x2, g(x2)) };
+        let mut y = gx.canonical_sqrt().unwrap();
+        if y.sgn0() != u.sgn0() {
+            y = -y;
+        }
+
+        let adiv3 = Point::A / GFp5::from_canonical_u16(3);
+        let x0 = x - adiv3;
+        if x0 == GFp5::ZERO {
+            // the SWU output is N itself
+            Self::NEUTRAL
+        } else if x0.legendre() == GFp::ONE {
+            Self { x, y, is_inf: false }
+        } else {
+            // (x0, y) + N = (b/x0, -y*b/x0^2) in the original form
+            let b_div_x0 = Point::B / x0;
+            Self {
+                x: b_div_x0 + adiv3,
+                y: -y * b_div_x0 / x0,
+                is_inf: false,
+            }
+        }
+    }
+
+    pub fn to_point(&self) -> Point {
+        Point::decode(self.encode()).unwrap()
+    }
 }
 
 impl PartialEq for WeierstrassPoint {
@@ -276,6 +337,27 @@ impl Point {
         WeierstrassPoint::decode(w).unwrap()
     }
 
+    /// Maps a field element to a point, see `WeierstrassPoint::map_to_curve`.
+    pub fn map_to_curve(u: GFp5) -> Self {
+        WeierstrassPoint::map_to_curve(u).to_point()
+    }
+
+    /// Hashes a message to a point as `map_to_curve(u0) + map_to_curve(u1)`, where `u0` and `u1`
+    /// are squeezed from Poseidon. The message length is hashed as well, so messages of different
+    /// lengths never collide.
+    pub fn hash_to_curve(msg: &[GFp]) -> Self {
+        let mut preimage = vec![
+            GFp::from_canonical_u64(HASH_TO_CURVE_DOMAIN),
+            GFp::from_canonical_usize(msg.len()),
+        ];
+        preimage.extend_from_slice(msg);
+
+        let u = hash_n_to_m_no_pad::<GFp, PoseidonPermutation<GFp>>(&preimage, 10);
+        let u0 = QuinticExtension(u[..5].try_into().unwrap());
+        let u1 = QuinticExtension(u[5..].try_into().unwrap());
+        Self::map_to_curve(u0) + Self::map_to_curve(u1)
+    }
+
     // General point addition. formulas are complete (no special case).
     fn set_add(&mut self, rhs: &Self) {
         // cost: 10M
@@ -1697,4 +1779,57 @@ mod tests {
             assert!(!q.verify_muladd_vartime(s, k, r2));
         }
     }
+
+    // encodings of map_to_curve(u) for u = 0, 1, 1 + 2a + 3a^2 + 4a^3 + 5a^4, cross-checked
+    // against an independent implementation of the simplified SWU map
+    fn map_to_curve_vectors() -> [(GFp5, GFp5); 3] {
+        let u = |c: [u64; 5]| QuinticExtension(c.map(GoldilocksField));
+        [
+            (
+                u([0, 0, 0, 0, 0]),
+                u([16156920739150151075, 4672561271679594560, 2677574612538408142, 14519624481493405287, 8645560883534634694]),
+            ),
+            (
+                u([1, 0, 0, 0, 0]),
+                u([12021367875511189312, 15660469376456400679, 10863940053504293221, 15729168055026717537, 15722749482694026886]),
+            ),
+            (
+                u([1, 2, 3, 4, 5]),
+                u([12340171478867534861, 4221872650745642436, 17887688001405875392, 8236827668032714011, 17609138348430141474]),
+            ),
+        ]
+    }
+
+    #[test]
+    fn test_map_to_curve() {
+        for (u, w) in map_to_curve_vectors() {
+            assert_eq!(Point::map_to_curve(u).encode(), w);
+        }
+
+        let mut rng = thread_rng();
+        for _ in 0..30 {
+            let u = GFp5::sample(&mut rng);
+            let p = WeierstrassPoint::map_to_curve(u);
+
+            // on the curve, and the representative in the subgroup of order n
+            assert_eq!(p.y * p.y, p.x * p.x * p.x + WeierstrassPoint::A * p.x + WeierstrassPoint::B);
+            assert_eq!(p.to_point().to_weierstrass(), p);
+        }
+    }
+
+    #[test]
+    fn test_hash_to_curve() {
+        let w = |c: [u64; 5]| QuinticExtension(c.map(GoldilocksField));
+        let vectors = [
+            (vec![], w([13689885529473577871, 263176540405900324, 17984156814275292570, 6820890795261432597, 2674450469030178196])),
+            (vec![GFp::ZERO], w([2038038312123824996, 15168058770147865823, 11376148230515964742, 749773211882933525, 5448134120669094232])),
+            (
+                vec![GFp::ONE, GFp::TWO, GFp::from_canonical_u64(3)],
+                w([17879247571178507752, 13473671340863166701, 2105240204821249178, 17016851886355722328, 10736882840848300142]),
+            ),
+        ];
+        for (msg, expected) in vectors {
+            assert_eq!(Point::hash_to_curve(&msg).encode(), expected);
+        }
+    }
 }
@@ -2,7 +2,6 @@
 /// Most of this is copied from Thomas Pornin's implementation
 /// with some modifications to make it play more nicely with plonky2 primitives
 /// His implementation can be found here: https://github.com/pornin/ecgfp5
-use alloc::vec::Vec;
 use plonky2_field::extension::quintic::QuinticExtension;
 use rand::RngCore;
 use core::fmt::{self, Debug, Display, Formatter};
@@ -10,7 +9,6 @@ use core::hash::{Hash, Hasher};
 use core::iter::{Product, Sum};
 use core::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
 
-use itertools::Itertools;
 use num::bigint::BigUint;
 use num::One;
 use serde::{Deserialize, Serialize};
@@ -196,15 +194,7 @@ impl Field for Scalar {
     }
 
     fn from_noncanonical_biguint(val: BigUint) -> Self {
-        let val = val % Self::order();
-        Self(
-            val.to_u64_digits()
-                .into_iter()
-                .pad_using(5, |_| 0)
-                .collect::<Vec<_>>()[..]
-                .try_into()
-                .expect("error converting to u64 array"),
-        )
+        Self::from_noncanonical_bytes(&val.to_bytes_le())
     }
 
     #[inline]
@@ -221,13 +211,19 @@ impl Field for Scalar {
     fn from_noncanonical_u96(n: (u64, u32)) -> Self {
         Self([n.0, n.1 as u64, 0, 0, 0])
     }
-    
-    fn from_noncanonical_u64(_n: u64) -> Self {
-        todo!()
+
+    #[inline]
+    fn from_noncanonical_u64(n: u64) -> Self {
+        Self::from_canonical_u64(n)
     }
-    
-    fn from_noncanonical_i64(_n: i64) -> Self {
-        todo!()
+
+    fn from_noncanonical_i64(n: i64) -> Self {
+        let f = Self::from_canonical_u64(n.unsigned_abs());
+        if n < 0 {
+            -f
+        } else {
+            f
+        }
     }
 }
 
@@ -973,11 +969,14 @@ impl Signed640 {
 
 #[cfg(test)]
 mod tests {
-    use crate::test_field_arithmetic;
+    use crate::{test_field_arithmetic, test_prime_field_arithmetic};
 
-    use super::Scalar;
+    use num::BigUint;
+    use plonky2_field::types::{Field, PrimeField};
     use rand::{thread_rng, Rng};
 
+    use super::Scalar;
+
     #[test]
     fn test_scalar_ops() {
         let buf1: [u8; 50] = [
@@ -1094,5 +1093,25 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_wide_reduction() {
+        let mut rng = thread_rng();
+        // cover every chunk boundary of from_noncanonical_bytes() up to four 39-byte chunks
+        for len in 0..=160 {
+            let mut buf = vec![0u8; len];
+            rng.fill(&mut buf[..]);
+            let expected = BigUint::from_bytes_le(&buf) % Scalar::order();
+            let s = Scalar::from_noncanonical_bytes(&buf);
+            assert_eq!(s.to_canonical_biguint(), expected);
+            assert_eq!(Scalar::from_noncanonical_biguint(BigUint::from_bytes_le(&buf)), s);
+
+            buf.fill(0xFF);
+            let expected = BigUint::from_bytes_le(&buf) % Scalar::order();
+            let s = Scalar::from_noncanonical_bytes(&buf);
+            assert_eq!(s.to_canonical_biguint(), expected);
+        }
+    }
+
     test_field_arithmetic!(crate::curve::scalar_field::Scalar);
+    test_prime_field_arithmetic!(crate::curve::scalar_field::Scalar);
 }
@@ -0,0 +1,123 @@
+//! ElGamal encryption over EcGFp5.
+//!
+//! A message point `M` is encrypted under the public key `Q = sk*G` with randomness `r` as
+//! `(C1, C2) = (r*G, M + r*Q)`, and decrypted as `M = C2 - sk*C1`. Public keys are never the
+//! neutral point, under which ciphertexts would reveal the message. Ciphertexts can be
+//! re-randomized without the secret key. Arbitrary messages, such as device identifiers, are
+//! mapped to points with `message_to_point`, so decryption recovers `M` and the key holder
+//! identifies the message by recomputing `message_to_point` over the candidates.
+//! The circuit counterpart lives in `gadgets::elgamal` and is re-exported here.
+
+use plonky2_field::types::{Field, Sample};
+use rand::RngCore;
+
+use crate::curve::curve::Point;
+use crate::curve::scalar_field::Scalar;
+use crate::curve::{GFp, GFp5};
+use crate::keys::define_keys;
+
+pub use crate::gadgets::elgamal::{
+    CircuitBuilderElGamal, ElGamalCiphertextTarget, ElGamalPublicKeyTarget, PartialWitnessElGamal,
+};
+
+// domain separator for messages, distinct from the ones used in `schnorr`, `pedersen` and `curve`
+pub(crate) const ELGAMAL_DOMAIN: u64 = 6;
+
+/// Maps `msg` to a curve point whose discrete logarithm is unknown.
+pub fn message_to_point(msg: &[GFp]) -> Point {
+    let mut preimage = vec![GFp::from_canonical_u64(ELGAMAL_DOMAIN)];
+    preimage.extend_from_slice(msg);
+    Point::hash_to_curve(&preimage)
+}
+
+define_keys!();
+
+#[derive(Clone, Copy, Debug)]
+pub struct Ciphertext {
+    pub c1: Point,
+    pub c2: Point,
+}
+
+impl SecretKey {
+    pub fn decrypt(&self, ct: &Ciphertext) -> Point {
+        ct.c2 - ct.c1 * self.0
+    }
+}
+
+impl PublicKey {
+    /// Encrypts `msg` with the given randomness `r`, which must be uniformly random and
+    /// never reused. Proving correct encryption in-circuit needs `r` as a witness.
+    pub fn encrypt_with_randomness(&self, msg: Point, r: Scalar) -> Ciphertext {
+        Ciphertext {
+            c1: Point::mulgen(r),
+            c2: msg + self.0 * r,
+        }
+    }
+
+    pub fn encrypt<R: RngCore>(&self, msg: Point, rng: &mut R) -> Ciphertext {
+        self.encrypt_with_randomness(msg, Scalar::sample(rng))
+    }
+
+    /// Returns a fresh encryption of the same message, unlinkable to `ct` for anyone but the
+    /// secret key holder.
+    pub fn rerandomize(&self, ct: &Ciphertext, r: Scalar) -> Ciphertext {
+        Ciphertext {
+            c1: ct.c1 + Point::mulgen(r),
+            c2: ct.c2 + self.0 * r,
+        }
+    }
+}
+
+impl Ciphertext {
+    pub fn equals(&self, rhs: &Self) -> bool {
+        self.c1.equals(rhs.c1) && self.c2.equals(rhs.c2)
+    }
+
+    pub fn encode(&self) -> [GFp5; 2] {
+        [self.c1.encode(), self.c2.encode()]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::thread_rng;
+
+    use super::*;
+
+    #[test]
+    fn test_encrypt_decrypt() {
+        let mut rng = thread_rng();
+        let sk = SecretKey::sample(&mut rng);
+        let pk = sk.public_key();
+        let msg = message_to_point(&GFp::rand_vec(4));
+
+        let ct = pk.encrypt(msg, &mut rng);
+        assert!(sk.decrypt(&ct).equals(msg));
+
+        let other_sk = SecretKey::sample(&mut rng);
+        assert!(!other_sk.decrypt(&ct).equals(msg));
+    }
+
+    #[test]
+    fn test_rerandomize() {
+        let mut rng = thread_rng();
+        let sk = SecretKey::sample(&mut rng);
+        let pk = sk.public_key();
+        let msg = message_to_point(&GFp::rand_vec(4));
+        let (r, s) = (Scalar::sample(&mut rng), Scalar::sample(&mut rng));
+
+        let ct = pk.encrypt_with_randomness(msg, r);
+        let rerandomized = pk.rerandomize(&ct, s);
+        assert!(!rerandomized.equals(&ct));
+        assert!(sk.decrypt(&rerandomized).equals(msg));
+        assert!(rerandomized.equals(&pk.encrypt_with_randomness(msg, r + s)));
+    }
+
+    #[test]
+    fn test_message_to_point() {
+        let id = GFp::rand_vec(4);
+        assert!(message_to_point(&id).equals(message_to_point(&id)));
+        assert!(!message_to_point(&id).equals(message_to_point(&id[..3])));
+        assert!(!message_to_point(&id).equals(Point::hash_to_curve(&id)));
+    }
+}
@@ -9,6 +9,8 @@ use plonky2::iop::generator::{GeneratedValues, SimpleGenerator};
 use plonky2::iop::target::{BoolTarget, Target};
 use plonky2::iop::witness::{PartitionWitness, Witness, WitnessWrite};
 use plonky2::plonk::circuit_builder::CircuitBuilder;
+use plonky2::plonk::circuit_data::CommonCircuitData;
+use plonky2::util::serialization::{Buffer, IoResult, Read, Write};
 use plonky2_field::extension::{Extendable, FieldExtension};
 use plonky2_field::types::Field;
 use plonky2_ecdsa::gadgets::biguint::BigUintTarget;
@@ -18,11 +20,12 @@ use plonky2_u32::gadgets::arithmetic_u32::U32Target;
 use crate::curve::base_field::SquareRoot;
 use crate::curve::scalar_field::Scalar;
 use crate::curve::{GFp, GFp5};
+use crate::serialization::{ReadQuinticExt, WriteQuinticExt};
 
 const SIX: GFp = GoldilocksField(6);
 const THREE: GFp = GoldilocksField(3);
 
-#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, Default)]
 #[repr(transparent)]
 pub struct QuinticExtensionTarget(pub [Target; 5]);
 
@@ -608,9 +611,10 @@ macro_rules! impl_circuit_builder_for_extension_degree {
 
                     // sign = sign || (is_zero && sign_i)
                     // is_zero = is_zero && is_zero_i
+                    // where is_zero tracks whether all previous limbs are zero, as in `quintic_ext_sgn0`
 
                     // x or y = x + y - xy
-                    let is_zero_and_sign_i = self.and(is_zero_i, sign_i);
+                    let is_zero_and_sign_i = self.and(is_zero, sign_i);
                     let sign_and_is_zero_and_sign_i = self.and(sign, is_zero_and_sign_i);
                     let tmp = self.mul_const_add(
                         -GFp::ONE,
@@ -805,7 +809,7 @@ impl_circuit_builder_for_extension_degree!(2);
 impl_circuit_builder_for_extension_degree!(4);
 impl_circuit_builder_for_extension_degree!(5);
 
-#[derive(Debug)]
+#[derive(Debug, Default)]
 pub struct QuinticQuotientGenerator {
     numerator: QuinticExtensionTarget,
     denominator: QuinticExtensionTarget,
@@ -861,21 +865,24 @@ impl<const D: usize, F: RichField + Extendable<5> + Extendable<D>> SimpleGenerat
     }
     
     fn id(&self) -> String {
-        todo!()
+        "QuinticQuotientGenerator".to_string()
     }
-    
-    fn serialize(&self, _dst: &mut Vec<u8>, _common_data: &plonky2::plonk::circuit_data::CommonCircuitData<F, D>) -> plonky2::util::serialization::IoResult<()> {
-        todo!()
+
+    fn serialize(&self, dst: &mut Vec<u8>, _common_data: &CommonCircuitData<F, D>) -> IoResult<()> {
+        dst.write_target_quintic_ext(self.numerator)?;
+        dst.write_target_quintic_ext(self.denominator)?;
+        dst.write_target_quintic_ext(self.quotient)
     }
-    
-    fn deserialize(_src: &mut plonky2::util::serialization::Buffer, _common_data: &plonky2::plonk::circuit_data::CommonCircuitData<F, D>) -> plonky2::util::serialization::IoResult<Self>
-    where
-        Self: Sized {
-        todo!()
+
+    fn deserialize(src: &mut Buffer, _common_data: &CommonCircuitData<F, D>) -> IoResult<Self> {
+        let numerator = src.read_target_quintic_ext()?;
+        let denominator = src.read_target_quintic_ext()?;
+        let quotient = src.read_target_quintic_ext()?;
+        Ok(Self::new(numerator, denominator, quotient))
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Default)]
 pub struct QuinticSqrtGenerator {
     x: QuinticExtensionTarget,
     root_x: QuinticExtensionTarget,
@@ -890,6 +897,19 @@ impl QuinticSqrtGenerator {
     ) -> Self {
         QuinticSqrtGenerator { x, root_x, is_sqrt }
     }
+
+    fn serialize_targets(&self, dst: &mut Vec<u8>) -> IoResult<()> {
+        dst.write_target_quintic_ext(self.x)?;
+        dst.write_target_quintic_ext(self.root_x)?;
+        dst.write_target_bool(self.is_sqrt)
+    }
+
+    fn deserialize_targets(src: &mut Buffer) -> IoResult<Self> {
+        let x = src.read_target_quintic_ext()?;
+        let root_x = src.read_target_quintic_ext()?;
+        let is_sqrt = src.read_target_bool()?;
+        Ok(Self::new(x, root_x, is_sqrt))
+    }
 }
 
 impl SimpleGenerator<GFp, 1> for QuinticSqrtGenerator {
@@ -924,17 +944,15 @@ impl SimpleGenerator<GFp, 1> for QuinticSqrtGenerator {
     }
     
     fn id(&self) -> String {
-        todo!()
+        "QuinticSqrtGenerator".to_string()
     }
-    
-    fn serialize(&self, _dst: &mut Vec<u8>, _common_data: &plonky2::plonk::circuit_data::CommonCircuitData<GFp, 1>) -> plonky2::util::serialization::IoResult<()> {
-        todo!()
+
+    fn serialize(&self, dst: &mut Vec<u8>, _common_data: &CommonCircuitData<GFp, 1>) -> IoResult<()> {
+        self.serialize_targets(dst)
     }
-    
-    fn deserialize(_src: &mut plonky2::util::serialization::Buffer, _common_data: &plonky2::plonk::circuit_data::CommonCircuitData<GFp, 1>) -> plonky2::util::serialization::IoResult<Self>
-    where
-        Self: Sized {
-        todo!()
+
+    fn deserialize(src: &mut Buffer, _common_data: &CommonCircuitData<GFp, 1>) -> IoResult<Self> {
+        Self::deserialize_targets(src)
     }
 }
 
@@ -970,17 +988,15 @@ impl SimpleGenerator<GFp, 2> for QuinticSqrtGenerator {
     }
     
     fn id(&self) -> String {
-        todo!()
+        "QuinticSqrtGenerator".to_string()
     }
-    
-    fn serialize(&self, _dst: &mut Vec<u8>, _common_data: &plonky2::plonk::circuit_data::CommonCircuitData<GFp, 2>) -> plonky2::util::serialization::IoResult<()> {
-        todo!()
+
+    fn serialize(&self, dst: &mut Vec<u8>, _common_data: &CommonCircuitData<GFp, 2>) -> IoResult<()> {
+        self.serialize_targets(dst)
     }
-    
-    fn deserialize(_src: &mut plonky2::util::serialization::Buffer, _common_data: &plonky2::plonk::circuit_data::CommonCircuitData<GFp, 2>) -> plonky2::util::serialization::IoResult<Self>
-    where
-        Self: Sized {
-        todo!()
+
+    fn deserialize(src: &mut Buffer, _common_data: &CommonCircuitData<GFp, 2>) -> IoResult<Self> {
+        Self::deserialize_targets(src)
     }
 }
 
@@ -1017,17 +1033,15 @@ impl SimpleGenerator<GFp, 4> for QuinticSqrtGenerator {
     }
     
     fn id(&self) -> String {
-        todo!()
+        "QuinticSqrtGenerator".to_string()
     }
-    
-    fn serialize(&self, _dst: &mut Vec<u8>, _common_data: &plonky2::plonk::circuit_data::CommonCircuitData<GFp, 4>) -> plonky2::util::serialization::IoResult<()> {
-        todo!()
+
+    fn serialize(&self, dst: &mut Vec<u8>, _common_data: &CommonCircuitData<GFp, 4>) -> IoResult<()> {
+        self.serialize_targets(dst)
     }
-    
-    fn deserialize(_src: &mut plonky2::util::serialization::Buffer, _common_data: &plonky2::plonk::circuit_data::CommonCircuitData<GFp, 4>) -> plonky2::util::serialization::IoResult<Self>
-    where
-        Self: Sized {
-        todo!()
+
+    fn deserialize(src: &mut Buffer, _common_data: &CommonCircuitData<GFp, 4>) -> IoResult<Self> {
+        Self::deserialize_targets(src)
     }
 }
 
@@ -1064,17 +1078,15 @@ impl SimpleGenerator<GFp, 5> for QuinticSqrtGenerator {
     }
     
     fn id(&self) -> String {
-        todo!()
+        "QuinticSqrtGenerator".to_string()
     }
-    
-    fn serialize(&self, _dst: &mut Vec<u8>, _common_data: &plonky2::plonk::circuit_data::CommonCircuitData<GFp, 5>) -> plonky2::util::serialization::IoResult<()> {
-        todo!()
+
+    fn serialize(&self, dst: &mut Vec<u8>, _common_data: &CommonCircuitData<GFp, 5>) -> IoResult<()> {
+        self.serialize_targets(dst)
     }
-    
-    fn deserialize(_src: &mut plonky2::util::serialization::Buffer, _common_data: &plonky2::plonk::circuit_data::CommonCircuitData<GFp, 5>) -> plonky2::util::serialization::IoResult<Self>
-    where
-        Self: Sized {
-        todo!()
+
+    fn deserialize(src: &mut Buffer, _common_data: &CommonCircuitData<GFp, 5>) -> IoResult<Self> {
+        Self::deserialize_targets(src)
     }
 }
 
@@ -1091,6 +1103,7 @@ mod tests {
     use rand::thread_rng;
 
     use super::*;
+    use crate::curve::base_field::Sgn0;
     use crate::curve::scalar_field::biguint_from_array;
     use crate::curve::test_utils::{gfp5_random_non_square, gfp5_random_sgn0_eq_0};
     use crate::gadgets::scalar_field::{CircuitBuilderScalar, PartialWitnessScalar};
@@ -1286,6 +1299,35 @@ mod tests {
         circuit.verify(proof)
     }
 
+    #[test]
+    fn test_sgn0_quintic_ext() -> Result<()> {
+        const D: usize = 2;
+        type C = PoseidonGoldilocksConfig;
+        type F = <C as GenericConfig<D>>::F;
+
+        let mut rng = thread_rng();
+
+        let config = CircuitConfig::standard_recursion_config();
+        let mut builder = CircuitBuilder::<F, D>::new(config);
+
+        // leading zero limbs defer the sign to the next limb
+        let mut inputs: Vec<GFp5> = (0..8).map(|_| GFp5::sample(&mut rng)).collect();
+        inputs.push(GFp5::ZERO);
+        inputs.push(QuinticExtension([GFp::ZERO, GFp::ONE, GFp::ZERO, GFp::ZERO, GFp::ZERO]));
+        inputs.push(QuinticExtension([GFp::ZERO, GFp::TWO, GFp::ZERO, GFp::ZERO, GFp::ZERO]));
+
+        for x in inputs {
+            let x_target = builder.constant_quintic_ext(x);
+            let sign = builder.sgn0_quintic_ext(x_target);
+            let expected = builder.constant_bool(x.sgn0());
+            builder.connect(sign.target, expected.target);
+        }
+
+        let circuit = builder.build::<C>();
+        let proof = circuit.prove(PartialWitness::new())?;
+        circuit.verify(proof)
+    }
+
     #[test]
     fn test_try_any_sqrt_quintic_ext() -> Result<()> {
         const D: usize = 2;
@@ -1,8 +1,9 @@
 use crate::curve::scalar_field::Scalar;
-use crate::curve::{curve::{Point, WeierstrassPoint}, GFp, GFp5};
+use crate::curve::{curve::{Point, WeierstrassPoint, HASH_TO_CURVE_DOMAIN}, GFp, GFp5};
 use crate::gadgets::base_field::{CircuitBuilderGFp5, QuinticExtensionTarget};
 use plonky2::field::types::Field; 
 use plonky2::hash::hash_types::RichField;
+use plonky2::hash::poseidon::PoseidonHash;
 use plonky2::iop::target::BoolTarget;
 use plonky2::iop::target::Target;
 use plonky2::iop::witness::Witness;
@@ -38,6 +39,7 @@ pub trait CircuitBuilderEcGFp5 {
     fn curve_add(&mut self, a: CurveTarget, b: CurveTarget) -> CurveTarget;
     fn curve_add_spec(&mut self, a: CurveTarget, b: CurveTarget) -> CurveTarget;
     fn curve_double(&mut self, a: CurveTarget) -> CurveTarget;
+    fn curve_neg(&mut self, a: CurveTarget) -> CurveTarget;
 
     fn precompute_window(&mut self, a: CurveTarget, window_bits: usize) -> Vec<CurveTarget>;
     fn curve_scalar_mul(&mut self, a: CurveTarget, scalar: &NonNativeTarget<Scalar>) -> CurveTarget;
@@ -48,7 +50,19 @@ pub trait CircuitBuilderEcGFp5 {
     fn curve_encode_to_quintic_ext(&mut self, a: CurveTarget) -> QuinticExtensionTarget;
     fn curve_decode_from_quintic_ext(&mut self, w: QuinticExtensionTarget) -> CurveTarget;
 
+    /// In-circuit counterpart of `WeierstrassPoint::map_to_curve`.
+    fn map_to_curve(&mut self, u: QuinticExtensionTarget) -> CurveTarget;
+    /// In-circuit counterpart of `Point::hash_to_curve`.
+    fn hash_to_curve(&mut self, msg: &[Target]) -> CurveTarget;
+
     fn curve_muladd_2(&mut self, a: CurveTarget, b: CurveTarget, scalar_a: &NonNativeTarget<Scalar>, scalar_b: &NonNativeTarget<Scalar>) -> CurveTarget;
+
+    /// Computes `sum scalars[i]*points[i]` using Straus' method with 4-bit windows, so that all
+    /// terms share the same doublings. Scalars may have different numbers of limbs.
+    fn curve_msm(&mut self, points: &[CurveTarget], scalars: &[NonNativeTarget<Scalar>]) -> CurveTarget;
+    /// Like `curve_msm`, but takes the 4-bit windows of the points as computed by
+    /// `precompute_window` or `precompute_window_const`, so constant and variable points can be mixed.
+    fn curve_msm_precomputed(&mut self, windows: &[Vec<CurveTarget>], scalars: &[NonNativeTarget<Scalar>]) -> CurveTarget;
 }
 
 macro_rules! impl_circuit_builder_for_extension_degree {
@@ -220,6 +234,12 @@ macro_rules! impl_circuit_builder_for_extension_degree {
                 CurveTarget(([x2, y2], is_inf))
             }
 
+            fn curve_neg(&mut self, a: CurveTarget) -> CurveTarget {
+                let CurveTarget(([x, y], is_inf)) = a;
+                let y = self.neg_quintic_ext(y);
+                CurveTarget(([x, y], is_inf))
+            }
+
             fn precompute_window(&mut self, a: CurveTarget, window_bits: usize) -> Vec<CurveTarget> {
                 debug_assert!(window_bits > 1);
                 let mut multiples = vec![self.curve_zero()];
@@ -340,32 +360,127 @@ macro_rules! impl_circuit_builder_for_extension_degree {
                 CurveTarget(([x, y], is_inf))
             }
 
-            fn curve_muladd_2(&mut self, a: CurveTarget, b: CurveTarget, scalar_a: &NonNativeTarget<Scalar>, scalar_b: &NonNativeTarget<Scalar>) -> CurveTarget {
-                let a_window = self.precompute_window(a, 4);
-                let a_four_bit_limbs = self.split_nonnative_to_4_bit_limbs(&scalar_a);
+            fn map_to_curve(&mut self, u: QuinticExtensionTarget) -> CurveTarget {
+                let (a, b, z) = (WeierstrassPoint::A, WeierstrassPoint::B, WeierstrassPoint::SSWU_Z);
+                let zero = self.zero_quintic_ext();
+                let neg_one = self.neg_one();
+
+                // x1 = -B/A * (1 + 1/(Z^2*u^4 + Z*u^2)), or B/(Z*A) if the denominator is zero
+                let u2 = self.square_quintic_ext(u);
+                let zu2 = self.mul_const_quintic_ext(z, u2);
+                let zu2_sq = self.square_quintic_ext(zu2);
+                let den = self.add_quintic_ext(zu2_sq, zu2);
+                let den_is_zero = self.is_equal_quintic_ext(den, zero);
+                let one = self.one_quintic_ext();
+                let tv1 = self.div_or_zero_quintic_ext(one, den);
+                let tv1 = self.add_const_quintic_ext(tv1, GFp5::ONE);
+                let x1 = self.mul_const_quintic_ext(-b / a, tv1);
+                let x1_exceptional = self.constant_quintic_ext(b / (z * a));
+                let x1 = self.select_quintic_ext(den_is_zero, x1_exceptional, x1);
+                let x2 = self.mul_quintic_ext(zu2, x1);
+
+                let mut g = |x: QuinticExtensionTarget| {
+                    let x2 = self.square_quintic_ext(x);
+                    let x2_plus_a = self.add_const_quintic_ext(x2, a);
+                    let x3_plus_ax = self.mul_quintic_ext(x2_plus_a, x);
+                    self.add_const_quintic_ext(x3_plus_ax, b)
+                };
+                let gx1 = g(x1);
+                let gx2 = g(x2);
+
+                // exactly one of gx1, gx2 is a square; the Legendre symbol decides which, so
+                // the prover cannot pick the other one
+                let gx1_legendre = self.legendre_sym_quintic_ext(gx1);
+                let gx1_is_not_square = self.is_equal(gx1_legendre, neg_one);
+                let x = self.select_quintic_ext(gx1_is_not_square, x2, x1);
+                let gx = self.select_quintic_ext(gx1_is_not_square, gx2, gx1);
+                let (y, gx_is_sqrt) = self.try_any_sqrt_quintic_ext(gx);
+                self.assert_one(gx_is_sqrt.target);
+
+                // fix the sign of y so that sgn0(y) == sgn0(u)
+                let sgn0_u = self.sgn0_quintic_ext(u);
+                let sgn0_y = self.sgn0_quintic_ext(y);
+                let same_sign = self.is_equal(sgn0_u.target, sgn0_y.target);
+                let neg_y = self.neg_quintic_ext(y);
+                let y = self.select_quintic_ext(same_sign, y, neg_y);
+
+                // move into the subgroup of order n by adding N when x0 = x - a/3 is not a square:
+                // (x0, y) + N = (b/x0, -y*b/x0^2) in the original form
+                let adiv3 = Point::A / GFp5::from_canonical_u16(3);
+                let x0 = self.add_const_quintic_ext(x, -adiv3);
+                let x0_is_zero = self.is_equal_quintic_ext(x0, zero);
+                let x0_legendre = self.legendre_sym_quintic_ext(x0);
+                let one = self.one();
+                let in_subgroup = self.is_equal(x0_legendre, one);
+
+                let b_orig = self.constant_quintic_ext(Point::B);
+                let b_div_x0 = self.div_or_zero_quintic_ext(b_orig, x0);
+                let nx = self.add_const_quintic_ext(b_div_x0, adiv3);
+                let ny = self.mul_quintic_ext(y, b_div_x0);
+                let ny = self.div_or_zero_quintic_ext(ny, x0);
+                let ny = self.neg_quintic_ext(ny);
+
+                let x = self.select_quintic_ext(in_subgroup, x, nx);
+                let y = self.select_quintic_ext(in_subgroup, y, ny);
+                CurveTarget(([x, y], x0_is_zero))
+            }
 
-                let b_window = self.precompute_window(b, 4);
-                let b_four_bit_limbs = self.split_nonnative_to_4_bit_limbs(&scalar_b);
+            fn hash_to_curve(&mut self, msg: &[Target]) -> CurveTarget {
+                let mut preimage = vec![
+                    self.constant(GFp::from_canonical_u64(HASH_TO_CURVE_DOMAIN)),
+                    self.constant(GFp::from_canonical_usize(msg.len())),
+                ];
+                preimage.extend_from_slice(msg);
+
+                let u = self.hash_n_to_m_no_pad::<PoseidonHash>(preimage, 10);
+                let u0 = QuinticExtensionTarget(u[..5].try_into().unwrap());
+                let u1 = QuinticExtensionTarget(u[5..].try_into().unwrap());
+                let p0 = self.map_to_curve(u0);
+                let p1 = self.map_to_curve(u1);
+                self.curve_add(p0, p1)
+            }
 
-                debug_assert!(a_four_bit_limbs.len() == b_four_bit_limbs.len());
+            fn curve_muladd_2(&mut self, a: CurveTarget, b: CurveTarget, scalar_a: &NonNativeTarget<Scalar>, scalar_b: &NonNativeTarget<Scalar>) -> CurveTarget {
+                self.curve_msm(&[a, b], &[scalar_a.clone(), scalar_b.clone()])
+            }
 
-                let num_limbs = a_four_bit_limbs.len();
-                let a_start = self.curve_random_access(a_four_bit_limbs[num_limbs - 1], &a_window);
-                let b_start = self.curve_random_access(b_four_bit_limbs[num_limbs - 1], &b_window);
-                let mut res = self.curve_add(a_start, b_start);
+            fn curve_msm(&mut self, points: &[CurveTarget], scalars: &[NonNativeTarget<Scalar>]) -> CurveTarget {
+                let windows: Vec<_> = points.iter().map(|&p| self.precompute_window(p, 4)).collect();
+                self.curve_msm_precomputed(&windows, scalars)
+            }
 
-                for (a_limb, b_limb) in a_four_bit_limbs.into_iter().zip(b_four_bit_limbs).rev().skip(1) {
-                    for _ in 0..4 {
-                        res = self.curve_double(res);
+            fn curve_msm_precomputed(&mut self, windows: &[Vec<CurveTarget>], scalars: &[NonNativeTarget<Scalar>]) -> CurveTarget {
+                assert_eq!(windows.len(), scalars.len());
+                debug_assert!(windows.iter().all(|w| w.len() == 16));
+
+                let four_bit_limbs: Vec<Vec<Target>> = scalars
+                    .iter()
+                    .map(|s| self.split_nonnative_to_4_bit_limbs(s))
+                    .collect();
+                let num_limbs = four_bit_limbs.iter().map(|l| l.len()).max().unwrap_or(0);
+
+                let mut res: Option<CurveTarget> = None;
+                for i in (0..num_limbs).rev() {
+                    if let Some(mut acc) = res {
+                        for _ in 0..4 {
+                            acc = self.curve_double(acc);
+                        }
+                        res = Some(acc);
                     }
 
-                    let a_addend = self.curve_random_access(a_limb, &a_window);
-                    let b_addend = self.curve_random_access(b_limb, &b_window);
-                    let addend = self.curve_add(a_addend, b_addend);
-                    res = self.curve_add(res, addend);
+                    // shorter scalars only enter the lowest windows
+                    for (window, limbs) in windows.iter().zip(four_bit_limbs.iter()) {
+                        if i < limbs.len() {
+                            let addend = self.curve_random_access(limbs[i], window);
+                            res = Some(match res {
+                                Some(acc) => self.curve_add(acc, addend),
+                                None => addend,
+                            });
+                        }
+                    }
                 }
 
-                res
+                res.unwrap_or_else(|| self.curve_zero())
             }
         }
     };
@@ -427,7 +542,7 @@ impl<W: PartialWitnessQuinticExt<GFp>> PartialWitnessCurve<GFp> for W {
 #[cfg(test)]
 mod tests {
     use anyhow::Result;
-    use plonky2::{field::types::Sample, plonk::{config::{PoseidonGoldilocksConfig, GenericConfig}, circuit_data::CircuitConfig}, iop::witness::PartialWitness};
+    use plonky2::{field::types::Sample, plonk::{config::{PoseidonGoldilocksConfig, GenericConfig}, circuit_data::CircuitConfig}, iop::witness::{PartialWitness, WitnessWrite}};
     use plonky2_ecdsa::gadgets::nonnative::CircuitBuilderNonNative;
     use rand::thread_rng;
 
@@ -491,6 +606,38 @@ mod tests {
         circuit.verify(proof)
     }
 
+    #[test]
+    fn test_curve_neg() -> Result<()> {
+        const D: usize = 2;
+        type C = PoseidonGoldilocksConfig;
+        type F = <C as GenericConfig<D>>::F;
+
+        let mut rng = thread_rng();
+
+        let config = CircuitConfig::standard_recursion_config();
+        let mut builder = CircuitBuilder::<F, D>::new(config);
+
+        let p = Point::sample(&mut rng);
+        let neg_expected = -p;
+
+        let p = builder.curve_constant(p.to_weierstrass());
+        let neg = builder.curve_neg(p);
+        builder.register_curve_public_input(neg);
+
+        let sum = builder.curve_add(p, neg);
+        let zero = builder.curve_zero();
+        let is_zero = builder.curve_eq(sum, zero);
+        builder.assert_one(is_zero.target);
+
+        let circuit = builder.build::<C>();
+
+        let mut pw = PartialWitness::new();
+        pw.set_curve_target(neg, neg_expected.to_weierstrass());
+
+        let proof = circuit.prove(pw)?;
+        circuit.verify(proof)
+    }
+
     #[test]
     fn test_curve_scalar_mul() -> Result<()> {
         const D: usize = 2;
@@ -638,4 +785,100 @@ mod tests {
         let proof = circuit.prove(pw)?;
         circuit.verify(proof)
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_curve_msm() -> Result<()> {
+        const D: usize = 2;
+        type C = PoseidonGoldilocksConfig;
+        type F = <C as GenericConfig<D>>::F;
+
+        let mut rng = thread_rng();
+
+        let config = CircuitConfig::standard_recursion_config();
+        let mut builder = CircuitBuilder::<F, D>::new(config);
+
+        // includes a short scalar, which only spans the lowest windows
+        let points: Vec<Point> = (0..4).map(|_| Point::sample(&mut rng)).collect();
+        let mut scalars: Vec<Scalar> = (0..3).map(|_| Scalar::sample(&mut rng)).collect();
+        scalars.push(Scalar::from_canonical_u64(5));
+        let expected = points
+            .iter()
+            .zip(scalars.iter())
+            .fold(Point::NEUTRAL, |acc, (&p, &s)| acc + p * s);
+
+        let point_targets: Vec<_> = points.iter().map(|p| builder.curve_constant(p.to_weierstrass())).collect();
+        let scalar_targets: Vec<_> = scalars.iter().map(|&s| builder.constant_nonnative(s)).collect();
+
+        let msm = builder.curve_msm(&point_targets, &scalar_targets);
+        builder.register_curve_public_input(msm);
+
+        let circuit = builder.build::<C>();
+
+        let mut pw = PartialWitness::new();
+        pw.set_curve_target(msm, expected.to_weierstrass());
+
+        let proof = circuit.prove(pw)?;
+        circuit.verify(proof)
+    }
+
+    #[test]
+    fn test_map_to_curve() -> Result<()> {
+        const D: usize = 2;
+        type C = PoseidonGoldilocksConfig;
+        type F = <C as GenericConfig<D>>::F;
+
+        let mut rng = thread_rng();
+
+        let config = CircuitConfig::standard_recursion_config();
+        let mut builder = CircuitBuilder::<F, D>::new(config);
+
+        // the exceptional case u = 0, and random inputs
+        let mut inputs = vec![GFp5::ZERO];
+        inputs.extend((0..4).map(|_| GFp5::sample(&mut rng)));
+
+        let mut targets = Vec::new();
+        for _ in inputs.iter() {
+            let u_target = builder.add_virtual_quintic_ext_target();
+            let p = builder.map_to_curve(u_target);
+            builder.register_curve_public_input(p);
+            targets.push((u_target, p));
+        }
+
+        let circuit = builder.build::<C>();
+
+        let mut pw = PartialWitness::new();
+        for (&u, &(u_target, p)) in inputs.iter().zip(targets.iter()) {
+            pw.set_quintic_ext_target(u_target, u);
+            pw.set_curve_target(p, WeierstrassPoint::map_to_curve(u));
+        }
+
+        let proof = circuit.prove(pw)?;
+        circuit.verify(proof)
+    }
+
+    #[test]
+    fn test_hash_to_curve() -> Result<()> {
+        const D: usize = 2;
+        type C = PoseidonGoldilocksConfig;
+        type F = <C as GenericConfig<D>>::F;
+
+        let config = CircuitConfig::standard_recursion_config();
+        let mut builder = CircuitBuilder::<F, D>::new(config);
+
+        let msg = F::rand_vec(7);
+        let msg_target = builder.add_virtual_targets(msg.len());
+        let p = builder.hash_to_curve(&msg_target);
+        builder.register_curve_public_input(p);
+
+        let circuit = builder.build::<C>();
+
+        let mut pw = PartialWitness::new();
+        for (&t, &m) in msg_target.iter().zip(msg.iter()) {
+            pw.set_target(t, m)?;
+        }
+        pw.set_curve_target(p, Point::hash_to_curve(&msg).to_weierstrass());
+
+        let proof = circuit.prove(pw)?;
+        circuit.verify(proof)
+    }
+}
@@ -0,0 +1,220 @@
+use plonky2::field::types::Field;
+use plonky2::hash::hash_types::RichField;
+use plonky2::iop::target::Target;
+use plonky2::iop::witness::Witness;
+use plonky2::plonk::circuit_builder::CircuitBuilder;
+use plonky2_ecdsa::gadgets::nonnative::NonNativeTarget;
+use plonky2_field::extension::Extendable;
+
+use crate::curve::curve::Point;
+use crate::curve::scalar_field::Scalar;
+use crate::curve::GFp;
+use crate::elgamal::{PublicKey, ELGAMAL_DOMAIN};
+use crate::gadgets::base_field::{
+    CircuitBuilderGFp5, PartialWitnessQuinticExt, QuinticExtensionTarget,
+};
+use crate::gadgets::curve::{CircuitBuilderEcGFp5, CurveTarget};
+
+/// An ElGamal public key, held in its encoded form so that decoding checks it is a valid curve point.
+#[derive(Copy, Clone, Debug)]
+pub struct ElGamalPublicKeyTarget(pub QuinticExtensionTarget);
+
+#[derive(Copy, Clone, Debug)]
+pub struct ElGamalCiphertextTarget {
+    pub c1: CurveTarget,
+    pub c2: CurveTarget,
+}
+
+pub trait CircuitBuilderElGamal {
+    fn add_virtual_elgamal_public_key_target(&mut self) -> ElGamalPublicKeyTarget;
+    fn constant_elgamal_public_key(&mut self, pk: PublicKey) -> ElGamalPublicKeyTarget;
+
+    /// Registers the encodings of `C1` and `C2` as public inputs, matching `Ciphertext::encode`.
+    fn register_elgamal_ciphertext_public_input(&mut self, ct: ElGamalCiphertextTarget);
+
+    /// In-circuit counterpart of `elgamal::message_to_point`.
+    fn elgamal_message_to_point(&mut self, msg: &[Target]) -> CurveTarget;
+
+    /// Computes the encryption `(r*G, M + r*Q)` of `msg` under `pk`, so that exposing the result
+    /// proves that it encrypts `msg` without revealing it.
+    fn elgamal_encrypt(
+        &mut self,
+        pk: ElGamalPublicKeyTarget,
+        msg: CurveTarget,
+        r: &NonNativeTarget<Scalar>,
+    ) -> ElGamalCiphertextTarget;
+}
+
+macro_rules! impl_circuit_builder_for_extension_degree {
+    ($degree:literal) => {
+        impl CircuitBuilderElGamal for CircuitBuilder<GFp, $degree> {
+            fn add_virtual_elgamal_public_key_target(&mut self) -> ElGamalPublicKeyTarget {
+                ElGamalPublicKeyTarget(self.add_virtual_quintic_ext_target())
+            }
+
+            fn constant_elgamal_public_key(&mut self, pk: PublicKey) -> ElGamalPublicKeyTarget {
+                ElGamalPublicKeyTarget(self.constant_quintic_ext(pk.encode()))
+            }
+
+            fn register_elgamal_ciphertext_public_input(&mut self, ct: ElGamalCiphertextTarget) {
+                for p in [ct.c1, ct.c2] {
+                    let w = self.curve_encode_to_quintic_ext(p);
+                    self.register_quintic_ext_public_input(w);
+                }
+            }
+
+            fn elgamal_message_to_point(&mut self, msg: &[Target]) -> CurveTarget {
+                let mut preimage = vec![self.constant(GFp::from_canonical_u64(ELGAMAL_DOMAIN))];
+                preimage.extend_from_slice(msg);
+                self.hash_to_curve(&preimage)
+            }
+
+            fn elgamal_encrypt(
+                &mut self,
+                pk: ElGamalPublicKeyTarget,
+                msg: CurveTarget,
+                r: &NonNativeTarget<Scalar>,
+            ) -> ElGamalCiphertextTarget {
+                // decoding fails on invalid encodings, and the neutral point (encoded as zero) is
+                // rejected since it would leave the message in the clear
+                let q = self.curve_decode_from_quintic_ext(pk.0);
+                let zero = self.zero_quintic_ext();
+                let pk_is_zero = self.is_equal_quintic_ext(pk.0, zero);
+                self.assert_zero(pk_is_zero.target);
+
+                let c1 = self.curve_scalar_mul_const(Point::GENERATOR, r);
+                let rq = self.curve_scalar_mul(q, r);
+                let c2 = self.curve_add(msg, rq);
+
+                ElGamalCiphertextTarget { c1, c2 }
+            }
+        }
+    };
+}
+
+impl_circuit_builder_for_extension_degree!(1);
+impl_circuit_builder_for_extension_degree!(2);
+impl_circuit_builder_for_extension_degree!(4);
+impl_circuit_builder_for_extension_degree!(5);
+
+pub trait PartialWitnessElGamal<F: RichField + Extendable<5>>: Witness<F> {
+    fn set_elgamal_public_key_target(&mut self, target: ElGamalPublicKeyTarget, value: PublicKey);
+}
+
+impl<W: PartialWitnessQuinticExt<GFp>> PartialWitnessElGamal<GFp> for W {
+    fn set_elgamal_public_key_target(&mut self, target: ElGamalPublicKeyTarget, value: PublicKey) {
+        self.set_quintic_ext_target(target.0, value.encode());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+    use plonky2::iop::witness::{PartialWitness, WitnessWrite};
+    use plonky2::plonk::circuit_data::CircuitConfig;
+    use plonky2::plonk::config::{GenericConfig, PoseidonGoldilocksConfig};
+    use plonky2_ecdsa::gadgets::biguint::{CircuitBuilderBiguint, WitnessBigUint};
+    use plonky2_ecdsa::gadgets::nonnative::CircuitBuilderNonNative;
+    use plonky2_field::types::{PrimeField, Sample};
+    use rand::thread_rng;
+
+    use crate::elgamal::{message_to_point, Ciphertext, SecretKey};
+
+    use super::*;
+
+    const D: usize = 2;
+    type C = PoseidonGoldilocksConfig;
+    type F = <C as GenericConfig<D>>::F;
+
+    /// Encrypts `id` under `pk` with randomness `r` in-circuit and checks the result against the
+    /// ciphertext `ct` the verifier is given.
+    fn elgamal_encrypt_circuit_with(pk: PublicKey, id: &[F], r: Scalar, ct: &Ciphertext) -> Result<()> {
+        let config = CircuitConfig::standard_recursion_config();
+        let mut builder = CircuitBuilder::<F, D>::new(config);
+
+        let pk_target = builder.add_virtual_elgamal_public_key_target();
+        let id_target = builder.add_virtual_targets(id.len());
+        let r_limbs = builder
+            .add_virtual_biguint_target(CircuitBuilder::<F, D>::num_nonnative_limbs::<Scalar>());
+        let r_target = builder.biguint_to_nonnative(&r_limbs);
+
+        let msg = builder.elgamal_message_to_point(&id_target);
+        let ct_target = builder.elgamal_encrypt(pk_target, msg, &r_target);
+
+        let expected = [
+            builder.add_virtual_quintic_ext_target(),
+            builder.add_virtual_quintic_ext_target(),
+        ];
+        for (&e, p) in expected.iter().zip([ct_target.c1, ct_target.c2]) {
+            let w = builder.curve_encode_to_quintic_ext(p);
+            builder.connect_quintic_ext(w, e);
+        }
+        builder.register_elgamal_ciphertext_public_input(ct_target);
+
+        let mut pw = PartialWitness::new();
+        pw.set_elgamal_public_key_target(pk_target, pk);
+        pw.set_target_arr(&id_target, id)?;
+        pw.set_biguint_target(&r_limbs, &r.to_canonical_biguint());
+        pw.set_quintic_ext_targets(&expected, &ct.encode());
+
+        let circuit = builder.build::<C>();
+        let proof = circuit.prove(pw)?;
+        let encoded: Vec<F> = ct.encode().iter().flat_map(|w| w.0).collect();
+        assert_eq!(proof.public_inputs, encoded);
+        circuit.verify(proof)
+    }
+
+    #[test]
+    fn test_elgamal_encrypt() -> Result<()> {
+        let mut rng = thread_rng();
+        let pk = SecretKey::sample(&mut rng).public_key();
+        let id = F::rand_vec(4);
+        let r = Scalar::sample(&mut rng);
+        let ct = pk.encrypt_with_randomness(message_to_point(&id), r);
+        elgamal_encrypt_circuit_with(pk, &id, r, &ct)
+    }
+
+    #[test]
+    fn test_elgamal_encrypt_wrong_message() {
+        let mut rng = thread_rng();
+        let pk = SecretKey::sample(&mut rng).public_key();
+        let mut id = F::rand_vec(4);
+        let r = Scalar::sample(&mut rng);
+        let ct = pk.encrypt_with_randomness(message_to_point(&id), r);
+        id[0] += F::ONE;
+        assert!(elgamal_encrypt_circuit_with(pk, &id, r, &ct).is_err());
+    }
+
+    #[test]
+    fn test_elgamal_encrypt_wrong_public_key() {
+        let mut rng = thread_rng();
+        let pk = SecretKey::sample(&mut rng).public_key();
+        let id = F::rand_vec(4);
+        let r = Scalar::sample(&mut rng);
+        let ct = pk.encrypt_with_randomness(message_to_point(&id), r);
+        let other_pk = SecretKey::sample(&mut rng).public_key();
+        assert!(elgamal_encrypt_circuit_with(other_pk, &id, r, &ct).is_err());
+    }
+
+    #[test]
+    fn test_elgamal_encrypt_wrong_randomness() {
+        let mut rng = thread_rng();
+        let pk = SecretKey::sample(&mut rng).public_key();
+        let id = F::rand_vec(4);
+        let r = Scalar::sample(&mut rng);
+        let ct = pk.encrypt_with_randomness(message_to_point(&id), r);
+        assert!(elgamal_encrypt_circuit_with(pk, &id, r + Scalar::ONE, &ct).is_err());
+    }
+
+    #[test]
+    fn test_elgamal_encrypt_rerandomized_ciphertext() {
+        // a rerandomized ciphertext decrypts to the same message, but was not made with `r`
+        let mut rng = thread_rng();
+        let pk = SecretKey::sample(&mut rng).public_key();
+        let id = F::rand_vec(4);
+        let r = Scalar::sample(&mut rng);
+        let ct = pk.encrypt_with_randomness(message_to_point(&id), r);
+        let ct = pk.rerandomize(&ct, Scalar::sample(&mut rng));
+        assert!(elgamal_encrypt_circuit_with(pk, &id, r, &ct).is_err());
+    }
+}
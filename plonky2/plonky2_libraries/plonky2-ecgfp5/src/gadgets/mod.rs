@@ -1,3 +1,7 @@
 pub mod base_field;
 pub mod scalar_field;
 pub mod curve;
+pub mod elgamal;
+pub mod pedersen;
+pub mod schnorr;
+pub mod vrf;
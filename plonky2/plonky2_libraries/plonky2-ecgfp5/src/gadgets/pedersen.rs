@@ -0,0 +1,94 @@
+use plonky2::plonk::circuit_builder::CircuitBuilder;
+use plonky2_ecdsa::gadgets::nonnative::NonNativeTarget;
+
+use crate::curve::scalar_field::Scalar;
+use crate::curve::GFp;
+use crate::gadgets::curve::{CircuitBuilderEcGFp5, CurveTarget};
+use crate::pedersen::PedersenGenerators;
+
+pub trait CircuitBuilderPedersen {
+    /// Computes the commitment `r*H + sum msg[i]*G_i` for constant generators, matching
+    /// `PedersenGenerators::commit`.
+    fn pedersen_commit(
+        &mut self,
+        gens: &PedersenGenerators,
+        msg: &[NonNativeTarget<Scalar>],
+        r: &NonNativeTarget<Scalar>,
+    ) -> CurveTarget;
+}
+
+macro_rules! impl_circuit_builder_for_extension_degree {
+    ($degree:literal) => {
+        impl CircuitBuilderPedersen for CircuitBuilder<GFp, $degree> {
+            fn pedersen_commit(
+                &mut self,
+                gens: &PedersenGenerators,
+                msg: &[NonNativeTarget<Scalar>],
+                r: &NonNativeTarget<Scalar>,
+            ) -> CurveTarget {
+                assert!(
+                    msg.len() <= gens.g.len(),
+                    "message longer than the number of generators"
+                );
+
+                // the generators are constants, so their windows cost nothing to precompute
+                let mut windows = vec![self.precompute_window_const(gens.h, 4)];
+                let mut scalars = vec![r.clone()];
+                for (m, &g) in msg.iter().zip(gens.g.iter()) {
+                    windows.push(self.precompute_window_const(g, 4));
+                    scalars.push(m.clone());
+                }
+
+                self.curve_msm_precomputed(&windows, &scalars)
+            }
+        }
+    };
+}
+
+impl_circuit_builder_for_extension_degree!(1);
+impl_circuit_builder_for_extension_degree!(2);
+impl_circuit_builder_for_extension_degree!(4);
+impl_circuit_builder_for_extension_degree!(5);
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+    use plonky2::iop::witness::PartialWitness;
+    use plonky2::plonk::circuit_data::CircuitConfig;
+    use plonky2::plonk::config::{GenericConfig, PoseidonGoldilocksConfig};
+    use plonky2_field::types::Sample;
+    use rand::thread_rng;
+
+    use crate::gadgets::curve::PartialWitnessCurve;
+    use crate::gadgets::scalar_field::CircuitBuilderScalar;
+
+    use super::*;
+
+    #[test]
+    fn test_pedersen_commit() -> Result<()> {
+        const D: usize = 2;
+        type C = PoseidonGoldilocksConfig;
+        type F = <C as GenericConfig<D>>::F;
+
+        let mut rng = thread_rng();
+        let gens = PedersenGenerators::new(4);
+        let msg: Vec<Scalar> = (0..3).map(|_| Scalar::sample(&mut rng)).collect();
+        let r = Scalar::sample(&mut rng);
+
+        let config = CircuitConfig::standard_recursion_config();
+        let mut builder = CircuitBuilder::<F, D>::new(config);
+
+        let msg_targets: Vec<_> = msg.iter().map(|&m| builder.constant_scalar(m)).collect();
+        let r_target = builder.constant_scalar(r);
+        let c = builder.pedersen_commit(&gens, &msg_targets, &r_target);
+        builder.register_curve_public_input(c);
+
+        let circuit = builder.build::<C>();
+
+        let mut pw = PartialWitness::new();
+        pw.set_curve_target(c, gens.commit(&msg, r).to_weierstrass());
+
+        let proof = circuit.prove(pw)?;
+        circuit.verify(proof)
+    }
+}
@@ -1,13 +1,33 @@
-use num::BigUint;
+use num::{BigUint, One};
 use plonky2::{hash::hash_types::RichField, plonk::circuit_builder::CircuitBuilder, iop::witness::{PartialWitness, WitnessWrite, Witness}};
-use plonky2_ecdsa::gadgets::biguint::BigUintTarget;
+use plonky2_ecdsa::gadgets::biguint::{BigUintTarget, CircuitBuilderBiguint};
+use plonky2_ecdsa::gadgets::nonnative::{CircuitBuilderNonNative, NonNativeTarget};
 use plonky2_field::{extension::Extendable, types::{Field, PrimeField}};
 
 use crate::curve::scalar_field::Scalar;
 
 
+/// Arithmetic modulo the curve order `n`. Unlike the generic `CircuitBuilderNonNative` gadgets, every
+/// scalar returned here is range-checked to be canonical, i.e. made of `u32` limbs and less than `n`,
+/// so results can be compared with `connect_scalar` directly.
 pub trait CircuitBuilderScalar<F: RichField + Extendable<D>, const D: usize> {
 	fn register_scalar_public_input(&mut self, scalar: &BigUintTarget);
+
+	fn add_virtual_scalar_target(&mut self) -> NonNativeTarget<Scalar>;
+	fn constant_scalar(&mut self, x: Scalar) -> NonNativeTarget<Scalar>;
+	fn connect_scalar(&mut self, a: &NonNativeTarget<Scalar>, b: &NonNativeTarget<Scalar>);
+
+	/// Asserts that `x` has `u32` limbs and is less than `n`.
+	fn assert_canonical_scalar(&mut self, x: &NonNativeTarget<Scalar>);
+
+	fn add_scalar(&mut self, a: &NonNativeTarget<Scalar>, b: &NonNativeTarget<Scalar>) -> NonNativeTarget<Scalar>;
+	fn sub_scalar(&mut self, a: &NonNativeTarget<Scalar>, b: &NonNativeTarget<Scalar>) -> NonNativeTarget<Scalar>;
+	fn mul_scalar(&mut self, a: &NonNativeTarget<Scalar>, b: &NonNativeTarget<Scalar>) -> NonNativeTarget<Scalar>;
+	fn neg_scalar(&mut self, x: &NonNativeTarget<Scalar>) -> NonNativeTarget<Scalar>;
+	/// The circuit is unsatisfiable if `x` is zero.
+	fn inverse_scalar(&mut self, x: &NonNativeTarget<Scalar>) -> NonNativeTarget<Scalar>;
+	/// Returns `x mod n` for an integer `x` of any size.
+	fn reduce_scalar(&mut self, x: &BigUintTarget) -> NonNativeTarget<Scalar>;
 }
 
 impl<F: RichField + Extendable<D>, const D: usize> CircuitBuilderScalar<F, D> for CircuitBuilder<F, D> {
@@ -16,6 +36,71 @@ impl<F: RichField + Extendable<D>, const D: usize> CircuitBuilderScalar<F, D> fo
 			self.register_public_input(limb.0);
 		}
 	}
+
+	fn add_virtual_scalar_target(&mut self) -> NonNativeTarget<Scalar> {
+		let x = self.add_virtual_nonnative_target::<Scalar>();
+		self.assert_canonical_scalar(&x);
+		x
+	}
+
+	fn constant_scalar(&mut self, x: Scalar) -> NonNativeTarget<Scalar> {
+		self.constant_nonnative(x)
+	}
+
+	fn connect_scalar(&mut self, a: &NonNativeTarget<Scalar>, b: &NonNativeTarget<Scalar>) {
+		self.connect_nonnative(a, b);
+	}
+
+	fn assert_canonical_scalar(&mut self, x: &NonNativeTarget<Scalar>) {
+		// `cmp_biguint` is a `<=` comparison that also range-checks the limbs of both sides
+		let x = self.nonnative_to_canonical_biguint(x);
+		let max = self.constant_biguint(&(Scalar::order() - BigUint::one()));
+		let is_canonical = self.cmp_biguint(&x, &max);
+		self.assert_one(is_canonical.target);
+	}
+
+	fn add_scalar(&mut self, a: &NonNativeTarget<Scalar>, b: &NonNativeTarget<Scalar>) -> NonNativeTarget<Scalar> {
+		// `add_nonnative` only checks the sum against `n` inclusively
+		let sum = self.add_nonnative(a, b);
+		self.assert_canonical_scalar(&sum);
+		sum
+	}
+
+	fn sub_scalar(&mut self, a: &NonNativeTarget<Scalar>, b: &NonNativeTarget<Scalar>) -> NonNativeTarget<Scalar> {
+		let diff = self.sub_nonnative(a, b);
+		self.assert_canonical_scalar(&diff);
+		diff
+	}
+
+	fn mul_scalar(&mut self, a: &NonNativeTarget<Scalar>, b: &NonNativeTarget<Scalar>) -> NonNativeTarget<Scalar> {
+		let prod = self.mul_nonnative(a, b);
+		self.assert_canonical_scalar(&prod);
+		prod
+	}
+
+	fn neg_scalar(&mut self, x: &NonNativeTarget<Scalar>) -> NonNativeTarget<Scalar> {
+		let neg = self.neg_nonnative(x);
+		self.assert_canonical_scalar(&neg);
+		neg
+	}
+
+	fn inverse_scalar(&mut self, x: &NonNativeTarget<Scalar>) -> NonNativeTarget<Scalar> {
+		// `inv_nonnative` leaves its witness limbs unchecked, so we only use it to compute the
+		// inverse and check `x * inv = 1` with a range-checked multiplication
+		let inv = self.inv_nonnative(x);
+		self.assert_canonical_scalar(&inv);
+
+		let prod = self.mul_scalar(x, &inv);
+		let one = self.constant_scalar(Scalar::ONE);
+		self.connect_scalar(&prod, &one);
+		inv
+	}
+
+	fn reduce_scalar(&mut self, x: &BigUintTarget) -> NonNativeTarget<Scalar> {
+		let reduced = self.reduce::<Scalar>(x);
+		self.assert_canonical_scalar(&reduced);
+		reduced
+	}
 }
 
 pub trait PartialWitnessScalar<F: RichField> {
@@ -42,3 +127,86 @@ impl<F: RichField> PartialWitnessScalar<F> for PartialWitness<F> {
 	}
 }
 
+#[cfg(test)]
+mod tests {
+	use anyhow::Result;
+	use plonky2::iop::witness::PartialWitness;
+	use plonky2::plonk::circuit_data::CircuitConfig;
+	use plonky2::plonk::config::{GenericConfig, PoseidonGoldilocksConfig};
+	use plonky2_field::types::Sample;
+	use rand::thread_rng;
+
+	use super::*;
+
+	const D: usize = 2;
+	type C = PoseidonGoldilocksConfig;
+	type F = <C as GenericConfig<D>>::F;
+
+	#[test]
+	fn test_scalar_arithmetic() -> Result<()> {
+		let mut rng = thread_rng();
+		let a = Scalar::sample(&mut rng);
+		let b = Scalar::sample(&mut rng);
+
+		let config = CircuitConfig::standard_recursion_config();
+		let mut builder = CircuitBuilder::<F, D>::new(config);
+
+		let a_target = builder.add_virtual_scalar_target();
+		let b_target = builder.add_virtual_scalar_target();
+		let a_limbs = builder.nonnative_to_canonical_biguint(&a_target);
+		let b_limbs = builder.nonnative_to_canonical_biguint(&b_target);
+
+		let sum = builder.add_scalar(&a_target, &b_target);
+		let diff = builder.sub_scalar(&a_target, &b_target);
+		let prod = builder.mul_scalar(&a_target, &b_target);
+		let neg = builder.neg_scalar(&a_target);
+		let inv = builder.inverse_scalar(&a_target);
+
+		// a 640-bit integer, as when deriving a scalar from a wide hash output
+		let wide = builder.add_virtual_biguint_target(20);
+		let reduced = builder.reduce_scalar(&wide);
+		let wide_value = BigUint::from_slice(&[u32::MAX; 20]);
+
+		for (target, expected) in [
+			(sum, a + b),
+			(diff, a - b),
+			(prod, a * b),
+			(neg, -a),
+			(inv, a.inverse()),
+			(reduced, Scalar::from_noncanonical_biguint(wide_value.clone())),
+		] {
+			let expected = builder.constant_scalar(expected);
+			builder.connect_scalar(&target, &expected);
+		}
+
+		let circuit = builder.build::<C>();
+
+		let mut pw = PartialWitness::new();
+		pw.set_scalar_target(&a_limbs, a);
+		pw.set_scalar_target(&b_limbs, b);
+		for (&limb, &value) in wide.limbs.iter().zip(wide_value.to_u32_digits().iter()) {
+			pw.set_target(limb.0, F::from_canonical_u32(value))?;
+		}
+
+		let proof = circuit.prove(pw)?;
+		circuit.verify(proof)
+	}
+
+	#[test]
+	fn test_non_canonical_scalar() {
+		let config = CircuitConfig::standard_recursion_config();
+		let mut builder = CircuitBuilder::<F, D>::new(config);
+
+		let x = builder.add_virtual_scalar_target();
+		let x = builder.nonnative_to_canonical_biguint(&x);
+		let circuit = builder.build::<C>();
+
+		// n itself is the non-canonical encoding of zero
+		let mut pw = PartialWitness::new();
+		for (&limb, value) in x.limbs.iter().zip(Scalar::order().to_u32_digits()) {
+			pw.set_target(limb.0, F::from_canonical_u32(value)).unwrap();
+		}
+
+		assert!(circuit.prove(pw).is_err());
+	}
+}
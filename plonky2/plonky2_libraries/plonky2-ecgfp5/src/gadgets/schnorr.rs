@@ -0,0 +1,458 @@
+use alloc::vec::Vec;
+use num::{BigUint, One};
+use plonky2::field::types::Field;
+use plonky2::hash::hash_types::RichField;
+use plonky2::hash::hashing::PlonkyPermutation;
+use plonky2::hash::poseidon::{PoseidonHash, PoseidonPermutation};
+use plonky2::iop::generator::{GeneratedValues, SimpleGenerator};
+use plonky2::iop::target::Target;
+use plonky2::iop::witness::{PartitionWitness, Witness, WitnessWrite};
+use plonky2::plonk::circuit_builder::CircuitBuilder;
+use plonky2::plonk::circuit_data::CommonCircuitData;
+use plonky2::util::serialization::{Buffer, IoResult};
+use plonky2_ecdsa::gadgets::biguint::{BigUintTarget, CircuitBuilderBiguint, WitnessBigUint};
+use plonky2_ecdsa::gadgets::nonnative::{CircuitBuilderNonNative, NonNativeTarget};
+use plonky2_ecdsa::serialization::{ReadBigUint, WriteBigUint};
+use plonky2_field::extension::Extendable;
+use plonky2_field::extension::quintic::QuinticExtension;
+use plonky2_field::types::PrimeField;
+use plonky2_u32::gadgets::arithmetic_u32::U32Target;
+use plonky2_u32::gadgets::range_check::range_check_u32_circuit;
+
+use crate::curve::curve::Point;
+use crate::curve::scalar_field::Scalar;
+use crate::curve::GFp;
+use crate::gadgets::base_field::{CircuitBuilderGFp5, PartialWitnessQuinticExt, QuinticExtensionTarget};
+use crate::gadgets::curve::CircuitBuilderEcGFp5;
+use crate::schnorr::{PublicKey, Signature, BATCH_DOMAIN, CHALLENGE_DOMAIN};
+use crate::serialization::{ReadQuinticExt, WriteQuinticExt};
+
+/// A Schnorr public key, held in its encoded form so that it is both hashed and decoded
+/// (and thereby checked to be a valid curve point) in-circuit.
+#[derive(Copy, Clone, Debug)]
+pub struct SchnorrPublicKeyTarget(pub QuinticExtensionTarget);
+
+#[derive(Clone, Debug, Default)]
+pub struct SchnorrSignatureTarget {
+    pub s: BigUintTarget,
+    pub e: BigUintTarget,
+}
+
+pub trait CircuitBuilderSchnorr {
+    fn add_virtual_schnorr_public_key_target(&mut self) -> SchnorrPublicKeyTarget;
+    fn constant_schnorr_public_key(&mut self, pk: PublicKey) -> SchnorrPublicKeyTarget;
+    /// Returns a signature target whose limbs are range-checked and whose `s` is less than `n`,
+    /// so that every signature has a single encoding.
+    fn add_virtual_schnorr_signature_target(&mut self) -> SchnorrSignatureTarget;
+
+    /// Computes `e = H(R || Q || m)` from the encodings of `R` and `Q`, matching
+    /// `schnorr::schnorr_challenge`.
+    fn schnorr_challenge(
+        &mut self,
+        r: QuinticExtensionTarget,
+        pk: QuinticExtensionTarget,
+        msg: &[Target],
+    ) -> NonNativeTarget<Scalar>;
+
+    /// Asserts that `sig` is a valid signature on `msg` under `pk`.
+    fn verify_schnorr(&mut self, pk: SchnorrPublicKeyTarget, msg: &[Target], sig: &SchnorrSignatureTarget);
+
+    /// Asserts that every `sigs[i]` is a valid signature on `msgs[i]` under `pks[i]`.
+    ///
+    /// Instead of one `curve_muladd_2` per signature, this checks the random linear combination
+    /// `sum z_i*(s_i*G + e_i*Q_i - R_i) = 0` with a single multi-scalar multiplication, so that all
+    /// signatures share the same doublings. The 128-bit randomizers `z_i` are derived in-circuit
+    /// from all keys, nonces and signatures.
+    fn verify_schnorr_batch(
+        &mut self,
+        pks: &[SchnorrPublicKeyTarget],
+        msgs: &[Vec<Target>],
+        sigs: &[SchnorrSignatureTarget],
+    );
+}
+
+/// In-circuit counterpart of `schnorr::hash_padded`, with the same `1 0* 1` padding as `schnorr::pad`.
+pub(crate) fn hash_padded_circuit<const D: usize>(
+    builder: &mut CircuitBuilder<GFp, D>,
+    mut input: Vec<Target>,
+    num_outputs: usize,
+) -> Vec<Target>
+where
+    GFp: Extendable<D>,
+{
+    let rate = PoseidonPermutation::<GFp>::RATE;
+    let zero = builder.zero();
+    let one = builder.one();
+    input.push(one);
+    while !(input.len() + 1).is_multiple_of(rate) {
+        input.push(zero);
+    }
+    input.push(one);
+
+    builder.hash_n_to_m_no_pad::<PoseidonHash>(input, num_outputs)
+}
+
+macro_rules! impl_circuit_builder_for_extension_degree {
+    ($degree:literal) => {
+        impl CircuitBuilderSchnorr for CircuitBuilder<GFp, $degree> {
+            fn add_virtual_schnorr_public_key_target(&mut self) -> SchnorrPublicKeyTarget {
+                SchnorrPublicKeyTarget(self.add_virtual_quintic_ext_target())
+            }
+
+            fn constant_schnorr_public_key(&mut self, pk: PublicKey) -> SchnorrPublicKeyTarget {
+                SchnorrPublicKeyTarget(self.constant_quintic_ext(pk.encode()))
+            }
+
+            fn add_virtual_schnorr_signature_target(&mut self) -> SchnorrSignatureTarget {
+                let num_limbs = Self::num_nonnative_limbs::<Scalar>();
+                let s = self.add_virtual_biguint_target(num_limbs);
+                let e = self.add_virtual_biguint_target(num_limbs);
+                range_check_u32_circuit(self, s.limbs.clone());
+                range_check_u32_circuit(self, e.limbs.clone());
+
+                // e is bound to the challenge hash, but s + n would verify just like s
+                let max = self.constant_biguint(&(Scalar::order() - BigUint::one()));
+                let s_is_canonical = self.cmp_biguint(&s, &max);
+                self.assert_one(s_is_canonical.target);
+
+                SchnorrSignatureTarget { s, e }
+            }
+
+            fn schnorr_challenge(
+                &mut self,
+                r: QuinticExtensionTarget,
+                pk: QuinticExtensionTarget,
+                msg: &[Target],
+            ) -> NonNativeTarget<Scalar> {
+                let mut preimage = vec![self.constant(GFp::from_canonical_u64(CHALLENGE_DOMAIN))];
+                preimage.extend(r.0);
+                preimage.extend(pk.0);
+                preimage.extend_from_slice(msg);
+
+                let e = hash_padded_circuit(self, preimage, 5);
+                let e = QuinticExtensionTarget(e.try_into().unwrap());
+                self.encode_quintic_ext_as_scalar(e)
+            }
+
+            fn verify_schnorr(&mut self, pk: SchnorrPublicKeyTarget, msg: &[Target], sig: &SchnorrSignatureTarget) {
+                // decoding fails on invalid encodings, and the neutral point (encoded as zero) is rejected
+                let q = self.curve_decode_from_quintic_ext(pk.0);
+                let zero = self.zero_quintic_ext();
+                let pk_is_zero = self.is_equal_quintic_ext(pk.0, zero);
+                self.assert_zero(pk_is_zero.target);
+
+                let s = self.biguint_to_nonnative(&sig.s);
+                let e = self.biguint_to_nonnative(&sig.e);
+
+                // R = s*G + e*Q
+                let g = self.curve_generator();
+                let r = self.curve_muladd_2(g, q, &s, &e);
+                let r = self.curve_encode_to_quintic_ext(r);
+
+                let e_v = self.schnorr_challenge(r, pk.0, msg);
+                self.connect_nonnative(&e, &e_v);
+            }
+
+            fn verify_schnorr_batch(
+                &mut self,
+                pks: &[SchnorrPublicKeyTarget],
+                msgs: &[Vec<Target>],
+                sigs: &[SchnorrSignatureTarget],
+            ) {
+                assert_eq!(pks.len(), msgs.len());
+                assert_eq!(pks.len(), sigs.len());
+                assert!(!pks.is_empty());
+
+                let zero = self.zero_quintic_ext();
+                let mut transcript = vec![self.constant(GFp::from_canonical_u64(BATCH_DOMAIN))];
+                let mut qs = Vec::with_capacity(pks.len());
+                let mut rs = Vec::with_capacity(pks.len());
+                for ((pk, msg), sig) in pks.iter().zip(msgs).zip(sigs) {
+                    let q = self.curve_decode_from_quintic_ext(pk.0);
+                    let pk_is_zero = self.is_equal_quintic_ext(pk.0, zero);
+                    self.assert_zero(pk_is_zero.target);
+
+                    // the nonce point is not part of the signature, so the prover supplies it as a
+                    // hint and we check that it hashes to e
+                    let r = self.add_virtual_quintic_ext_target();
+                    self.add_simple_generator(SchnorrNonceGenerator::new(pk.0, sig.clone(), r));
+                    let e = self.biguint_to_nonnative(&sig.e);
+                    let e_v = self.schnorr_challenge(r, pk.0, msg);
+                    self.connect_nonnative(&e, &e_v);
+
+                    transcript.extend(r.0);
+                    transcript.extend(pk.0 .0);
+                    transcript.extend(sig.s.limbs.iter().map(|l| l.0));
+                    transcript.extend(sig.e.limbs.iter().map(|l| l.0));
+
+                    qs.push(q);
+                    rs.push(self.curve_decode_from_quintic_ext(r));
+                }
+
+                // one 128-bit randomizer per signature, built from two 64-bit halves
+                let z_halves = self.hash_n_to_m_no_pad::<PoseidonHash>(transcript, 2 * pks.len());
+                let zs: Vec<NonNativeTarget<Scalar>> = z_halves
+                    .chunks(2)
+                    .map(|halves| {
+                        let mut limbs = Vec::with_capacity(4);
+                        for &half in halves {
+                            let (lo, hi) = self.split_low_high(half, 32, 64);
+                            limbs.push(U32Target(lo));
+                            limbs.push(U32Target(hi));
+                        }
+                        self.biguint_to_nonnative(&BigUintTarget { limbs })
+                    })
+                    .collect();
+
+                // (sum z_i*s_i)*G + sum (z_i*e_i)*Q_i + sum z_i*(-R_i)
+                let mut s_sum = self.zero_nonnative();
+                let mut windows = Vec::with_capacity(2 * pks.len() + 1);
+                let mut scalars = Vec::with_capacity(2 * pks.len() + 1);
+                for (((sig, &q), &r), z) in sigs.iter().zip(&qs).zip(&rs).zip(zs) {
+                    let s = self.biguint_to_nonnative(&sig.s);
+                    let e = self.biguint_to_nonnative(&sig.e);
+
+                    let zs_i = self.mul_nonnative(&z, &s);
+                    s_sum = self.add_nonnative(&s_sum, &zs_i);
+
+                    windows.push(self.precompute_window(q, 4));
+                    scalars.push(self.mul_nonnative(&z, &e));
+
+                    let neg_r = self.curve_neg(r);
+                    windows.push(self.precompute_window(neg_r, 4));
+                    scalars.push(z);
+                }
+                windows.push(self.precompute_window_const(Point::GENERATOR, 4));
+                scalars.push(s_sum);
+
+                let res = self.curve_msm_precomputed(&windows, &scalars);
+                let neutral = self.curve_zero();
+                let is_neutral = self.curve_eq(res, neutral);
+                self.assert_one(is_neutral.target);
+            }
+        }
+    };
+}
+
+impl_circuit_builder_for_extension_degree!(1);
+impl_circuit_builder_for_extension_degree!(2);
+impl_circuit_builder_for_extension_degree!(4);
+impl_circuit_builder_for_extension_degree!(5);
+
+pub trait PartialWitnessSchnorr<F: RichField + Extendable<5>>: Witness<F> {
+    fn set_schnorr_public_key_target(&mut self, target: SchnorrPublicKeyTarget, value: PublicKey);
+    fn set_schnorr_signature_target(&mut self, target: &SchnorrSignatureTarget, value: &Signature);
+}
+
+impl<W: PartialWitnessQuinticExt<GFp>> PartialWitnessSchnorr<GFp> for W {
+    fn set_schnorr_public_key_target(&mut self, target: SchnorrPublicKeyTarget, value: PublicKey) {
+        self.set_quintic_ext_target(target.0, value.encode());
+    }
+
+    fn set_schnorr_signature_target(&mut self, target: &SchnorrSignatureTarget, value: &Signature) {
+        self.set_biguint_target(&target.s, &value.s.to_canonical_biguint());
+        self.set_biguint_target(&target.e, &value.e.to_canonical_biguint());
+    }
+}
+
+/// Computes the encoding of the nonce point `R = s*G + e*Q` of a signature, which batch
+/// verification needs as a hint.
+#[derive(Debug, Default)]
+pub struct SchnorrNonceGenerator {
+    pk: QuinticExtensionTarget,
+    sig: SchnorrSignatureTarget,
+    r: QuinticExtensionTarget,
+}
+
+impl SchnorrNonceGenerator {
+    pub fn new(pk: QuinticExtensionTarget, sig: SchnorrSignatureTarget, r: QuinticExtensionTarget) -> Self {
+        SchnorrNonceGenerator { pk, sig, r }
+    }
+}
+
+impl<const D: usize> SimpleGenerator<GFp, D> for SchnorrNonceGenerator
+where
+    GFp: Extendable<D>,
+{
+    fn dependencies(&self) -> Vec<Target> {
+        let mut deps = self.pk.to_target_array().to_vec();
+        deps.extend(self.sig.s.limbs.iter().map(|l| l.0));
+        deps.extend(self.sig.e.limbs.iter().map(|l| l.0));
+        deps
+    }
+
+    fn run_once(&self, witness: &PartitionWitness<GFp>, out_buffer: &mut GeneratedValues<GFp>) -> Result<(), anyhow::Error> {
+        let pk = QuinticExtension(self.pk.to_target_array().map(|t| witness.get_target(t)));
+        let s = Scalar::from_noncanonical_biguint(witness.get_biguint_target(self.sig.s.clone()));
+        let e = Scalar::from_noncanonical_biguint(witness.get_biguint_target(self.sig.e.clone()));
+
+        // an invalid key fails in-circuit anyway, any value will do here
+        let r = match Point::decode(pk) {
+            Some(q) => Point::mulgen(s) + q * e,
+            None => Point::NEUTRAL,
+        };
+
+        for (lhs, rhs) in self.r.to_target_array().into_iter().zip(r.encode().0) {
+            out_buffer.set_target(lhs, rhs)?;
+        }
+        Ok(())
+    }
+
+    fn id(&self) -> String {
+        "SchnorrNonceGenerator".to_string()
+    }
+
+    fn serialize(&self, dst: &mut Vec<u8>, _common_data: &CommonCircuitData<GFp, D>) -> IoResult<()> {
+        dst.write_target_quintic_ext(self.pk)?;
+        dst.write_target_biguint(&self.sig.s)?;
+        dst.write_target_biguint(&self.sig.e)?;
+        dst.write_target_quintic_ext(self.r)
+    }
+
+    fn deserialize(src: &mut Buffer, _common_data: &CommonCircuitData<GFp, D>) -> IoResult<Self> {
+        let pk = src.read_target_quintic_ext()?;
+        let s = src.read_target_biguint()?;
+        let e = src.read_target_biguint()?;
+        let r = src.read_target_quintic_ext()?;
+        Ok(Self::new(pk, SchnorrSignatureTarget { s, e }, r))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+    use plonky2::iop::witness::{PartialWitness, WitnessWrite};
+    use plonky2::plonk::circuit_data::CircuitConfig;
+    use plonky2::plonk::config::{GenericConfig, PoseidonGoldilocksConfig};
+    use plonky2_field::types::Sample;
+    use rand::thread_rng;
+
+    use crate::schnorr::SecretKey;
+
+    use super::*;
+
+    const D: usize = 2;
+    type C = PoseidonGoldilocksConfig;
+    type F = <C as GenericConfig<D>>::F;
+
+    fn verify_schnorr_circuit_with(pk: PublicKey, msg: &[F], s: &BigUint, e: &BigUint) -> Result<()> {
+        let config = CircuitConfig::standard_recursion_config();
+        let mut builder = CircuitBuilder::<F, D>::new(config);
+
+        let pk_target = builder.add_virtual_schnorr_public_key_target();
+        let msg_target = builder.add_virtual_targets(msg.len());
+        let sig_target = builder.add_virtual_schnorr_signature_target();
+        builder.verify_schnorr(pk_target, &msg_target, &sig_target);
+
+        let mut pw = PartialWitness::new();
+        pw.set_schnorr_public_key_target(pk_target, pk);
+        pw.set_target_arr(&msg_target, msg)?;
+        pw.set_biguint_target(&sig_target.s, s);
+        pw.set_biguint_target(&sig_target.e, e);
+
+        let circuit = builder.build::<C>();
+        let proof = circuit.prove(pw)?;
+        circuit.verify(proof)
+    }
+
+    #[test]
+    fn test_verify_schnorr() -> Result<()> {
+        for msg_len in [4, 11] {
+            let sk = SecretKey::sample(&mut thread_rng());
+            let msg = F::rand_vec(msg_len);
+            let sig = sk.sign(&msg);
+            verify_schnorr_circuit_with(sk.public_key(), &msg, &sig.s.to_canonical_biguint(), &sig.e.to_canonical_biguint())?;
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_verify_schnorr_wrong_message() {
+        let sk = SecretKey::sample(&mut thread_rng());
+        let mut msg = F::rand_vec(4);
+        let sig = sk.sign(&msg);
+        msg[0] += F::ONE;
+        assert!(verify_schnorr_circuit_with(sk.public_key(), &msg, &sig.s.to_canonical_biguint(), &sig.e.to_canonical_biguint()).is_err());
+    }
+
+    #[test]
+    fn test_verify_schnorr_wrong_public_key() {
+        let mut rng = thread_rng();
+        let sk = SecretKey::sample(&mut rng);
+        let msg = F::rand_vec(4);
+        let sig = sk.sign(&msg);
+        let pk = SecretKey::sample(&mut rng).public_key();
+        assert!(verify_schnorr_circuit_with(pk, &msg, &sig.s.to_canonical_biguint(), &sig.e.to_canonical_biguint()).is_err());
+    }
+
+    #[test]
+    fn test_verify_schnorr_wrong_s() {
+        let sk = SecretKey::sample(&mut thread_rng());
+        let msg = F::rand_vec(4);
+        let sig = sk.sign(&msg);
+        let s = (sig.s + Scalar::ONE).to_canonical_biguint();
+        assert!(verify_schnorr_circuit_with(sk.public_key(), &msg, &s, &sig.e.to_canonical_biguint()).is_err());
+    }
+
+    #[test]
+    fn test_verify_schnorr_wrong_e() {
+        let sk = SecretKey::sample(&mut thread_rng());
+        let msg = F::rand_vec(4);
+        let sig = sk.sign(&msg);
+        let e = (sig.e + Scalar::ONE).to_canonical_biguint();
+        assert!(verify_schnorr_circuit_with(sk.public_key(), &msg, &sig.s.to_canonical_biguint(), &e).is_err());
+    }
+
+    #[test]
+    fn test_verify_schnorr_non_canonical_s() {
+        // s + n satisfies the verification equation, but is not a canonical scalar
+        let sk = SecretKey::sample(&mut thread_rng());
+        let msg = F::rand_vec(4);
+        let sig = sk.sign(&msg);
+        let s = sig.s.to_canonical_biguint() + Scalar::order();
+        assert!(verify_schnorr_circuit_with(sk.public_key(), &msg, &s, &sig.e.to_canonical_biguint()).is_err());
+    }
+
+    fn verify_schnorr_batch_circuit(msg_lens: &[usize], tamper: Option<usize>) -> Result<()> {
+        let mut rng = thread_rng();
+        let config = CircuitConfig::standard_recursion_config();
+        let mut builder = CircuitBuilder::<F, D>::new(config);
+
+        let pk_targets: Vec<_> = msg_lens.iter().map(|_| builder.add_virtual_schnorr_public_key_target()).collect();
+        let msg_targets: Vec<_> = msg_lens.iter().map(|&len| builder.add_virtual_targets(len)).collect();
+        let sig_targets: Vec<_> = msg_lens.iter().map(|_| builder.add_virtual_schnorr_signature_target()).collect();
+        builder.verify_schnorr_batch(&pk_targets, &msg_targets, &sig_targets);
+
+        let circuit = builder.build::<C>();
+
+        let mut pw = PartialWitness::new();
+        for (i, &len) in msg_lens.iter().enumerate() {
+            let sk = SecretKey::sample(&mut rng);
+            let mut msg = F::rand_vec(len);
+            let sig = sk.sign(&msg);
+            if tamper == Some(i) {
+                msg[0] += F::ONE;
+            }
+
+            pw.set_schnorr_public_key_target(pk_targets[i], sk.public_key());
+            pw.set_schnorr_signature_target(&sig_targets[i], &sig);
+            for (&t, &m) in msg_targets[i].iter().zip(msg.iter()) {
+                pw.set_target(t, m)?;
+            }
+        }
+
+        let proof = circuit.prove(pw)?;
+        circuit.verify(proof)
+    }
+
+    #[test]
+    fn test_verify_schnorr_batch() -> Result<()> {
+        verify_schnorr_batch_circuit(&[4], None)?;
+        verify_schnorr_batch_circuit(&[4, 11, 1], None)
+    }
+
+    #[test]
+    fn test_verify_schnorr_batch_one_invalid() {
+        assert!(verify_schnorr_batch_circuit(&[4, 4, 4], Some(1)).is_err());
+    }
+}
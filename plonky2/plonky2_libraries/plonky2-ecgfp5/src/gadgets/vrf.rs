@@ -0,0 +1,270 @@
+use num::{BigUint, One};
+use plonky2::field::types::Field;
+use plonky2::hash::hash_types::{HashOutTarget, RichField};
+use plonky2::iop::target::Target;
+use plonky2::iop::witness::Witness;
+use plonky2::plonk::circuit_builder::CircuitBuilder;
+use plonky2_ecdsa::gadgets::biguint::{BigUintTarget, CircuitBuilderBiguint, WitnessBigUint};
+use plonky2_ecdsa::gadgets::nonnative::CircuitBuilderNonNative;
+use plonky2_field::extension::Extendable;
+use plonky2_field::types::PrimeField;
+use plonky2_u32::gadgets::range_check::range_check_u32_circuit;
+
+use crate::curve::scalar_field::Scalar;
+use crate::curve::GFp;
+use crate::gadgets::base_field::{
+    CircuitBuilderGFp5, PartialWitnessQuinticExt, QuinticExtensionTarget,
+};
+use crate::gadgets::curve::CircuitBuilderEcGFp5;
+use crate::gadgets::schnorr::hash_padded_circuit;
+use crate::vrf::{Proof, PublicKey, VRF_CHALLENGE_DOMAIN, VRF_INPUT_DOMAIN, VRF_OUTPUT_DOMAIN};
+
+/// A VRF public key, held in its encoded form so that it is both hashed and decoded
+/// (and thereby checked to be a valid curve point) in-circuit.
+#[derive(Copy, Clone, Debug)]
+pub struct VrfPublicKeyTarget(pub QuinticExtensionTarget);
+
+#[derive(Clone, Debug)]
+pub struct VrfProofTarget {
+    pub gamma: QuinticExtensionTarget,
+    pub c: BigUintTarget,
+    pub s: BigUintTarget,
+}
+
+pub trait CircuitBuilderVrf {
+    fn add_virtual_vrf_public_key_target(&mut self) -> VrfPublicKeyTarget;
+    fn constant_vrf_public_key(&mut self, pk: PublicKey) -> VrfPublicKeyTarget;
+    /// Returns a proof target whose limbs are range-checked and whose `s` is less than `n`, so
+    /// that every proof has a single encoding.
+    fn add_virtual_vrf_proof_target(&mut self) -> VrfProofTarget;
+
+    /// Asserts that `proof` is a valid VRF proof for `alpha` under `pk`, and returns the output
+    /// `beta`, matching `vrf::PublicKey::verify`.
+    fn verify_vrf(
+        &mut self,
+        pk: VrfPublicKeyTarget,
+        alpha: &[Target],
+        proof: &VrfProofTarget,
+    ) -> HashOutTarget;
+}
+
+macro_rules! impl_circuit_builder_for_extension_degree {
+    ($degree:literal) => {
+        impl CircuitBuilderVrf for CircuitBuilder<GFp, $degree> {
+            fn add_virtual_vrf_public_key_target(&mut self) -> VrfPublicKeyTarget {
+                VrfPublicKeyTarget(self.add_virtual_quintic_ext_target())
+            }
+
+            fn constant_vrf_public_key(&mut self, pk: PublicKey) -> VrfPublicKeyTarget {
+                VrfPublicKeyTarget(self.constant_quintic_ext(pk.encode()))
+            }
+
+            fn add_virtual_vrf_proof_target(&mut self) -> VrfProofTarget {
+                let num_limbs = Self::num_nonnative_limbs::<Scalar>();
+                let c = self.add_virtual_biguint_target(num_limbs);
+                let s = self.add_virtual_biguint_target(num_limbs);
+                range_check_u32_circuit(self, c.limbs.clone());
+                range_check_u32_circuit(self, s.limbs.clone());
+
+                // c is bound to the challenge hash, but s + n would verify just like s
+                let max = self.constant_biguint(&(Scalar::order() - BigUint::one()));
+                let s_is_canonical = self.cmp_biguint(&s, &max);
+                self.assert_one(s_is_canonical.target);
+
+                VrfProofTarget {
+                    gamma: self.add_virtual_quintic_ext_target(),
+                    c,
+                    s,
+                }
+            }
+
+            fn verify_vrf(
+                &mut self,
+                pk: VrfPublicKeyTarget,
+                alpha: &[Target],
+                proof: &VrfProofTarget,
+            ) -> HashOutTarget {
+                // decoding fails on invalid encodings, and the neutral point (encoded as zero) is
+                // rejected for both the key and gamma
+                let q = self.curve_decode_from_quintic_ext(pk.0);
+                let gamma = self.curve_decode_from_quintic_ext(proof.gamma);
+                let zero = self.zero_quintic_ext();
+                for w in [pk.0, proof.gamma] {
+                    let is_zero = self.is_equal_quintic_ext(w, zero);
+                    self.assert_zero(is_zero.target);
+                }
+
+                let mut preimage = vec![self.constant(GFp::from_canonical_u64(VRF_INPUT_DOMAIN))];
+                preimage.extend(pk.0 .0);
+                preimage.extend_from_slice(alpha);
+                let h = self.hash_to_curve(&preimage);
+
+                let c = self.biguint_to_nonnative(&proof.c);
+                let s = self.biguint_to_nonnative(&proof.s);
+
+                // U = s*G - c*Q and V = s*H - c*Gamma
+                let g = self.curve_generator();
+                let neg_q = self.curve_neg(q);
+                let u = self.curve_muladd_2(g, neg_q, &s, &c);
+                let neg_gamma = self.curve_neg(gamma);
+                let v = self.curve_muladd_2(h, neg_gamma, &s, &c);
+
+                let h = self.curve_encode_to_quintic_ext(h);
+                let u = self.curve_encode_to_quintic_ext(u);
+                let v = self.curve_encode_to_quintic_ext(v);
+                let mut preimage =
+                    vec![self.constant(GFp::from_canonical_u64(VRF_CHALLENGE_DOMAIN))];
+                for w in [pk.0, h, proof.gamma, u, v] {
+                    preimage.extend(w.0);
+                }
+
+                let c_v = hash_padded_circuit(self, preimage, 5);
+                let c_v = self
+                    .encode_quintic_ext_as_scalar(QuinticExtensionTarget(c_v.try_into().unwrap()));
+                self.connect_nonnative(&c, &c_v);
+
+                let mut preimage = vec![self.constant(GFp::from_canonical_u64(VRF_OUTPUT_DOMAIN))];
+                preimage.extend(proof.gamma.0);
+                HashOutTarget::from_vec(hash_padded_circuit(self, preimage, 4))
+            }
+        }
+    };
+}
+
+impl_circuit_builder_for_extension_degree!(1);
+impl_circuit_builder_for_extension_degree!(2);
+impl_circuit_builder_for_extension_degree!(4);
+impl_circuit_builder_for_extension_degree!(5);
+
+pub trait PartialWitnessVrf<F: RichField + Extendable<5>>: Witness<F> {
+    fn set_vrf_public_key_target(&mut self, target: VrfPublicKeyTarget, value: PublicKey);
+    fn set_vrf_proof_target(&mut self, target: &VrfProofTarget, value: &Proof);
+}
+
+impl<W: PartialWitnessQuinticExt<GFp>> PartialWitnessVrf<GFp> for W {
+    fn set_vrf_public_key_target(&mut self, target: VrfPublicKeyTarget, value: PublicKey) {
+        self.set_quintic_ext_target(target.0, value.encode());
+    }
+
+    fn set_vrf_proof_target(&mut self, target: &VrfProofTarget, value: &Proof) {
+        self.set_quintic_ext_target(target.gamma, value.gamma.encode());
+        self.set_biguint_target(&target.c, &value.c.to_canonical_biguint());
+        self.set_biguint_target(&target.s, &value.s.to_canonical_biguint());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+    use plonky2::hash::hash_types::HashOut;
+    use plonky2::iop::witness::{PartialWitness, WitnessWrite};
+    use plonky2::plonk::circuit_data::CircuitConfig;
+    use plonky2::plonk::config::{GenericConfig, PoseidonGoldilocksConfig};
+    use plonky2_field::types::Sample;
+    use rand::thread_rng;
+
+    use crate::curve::curve::Point;
+    use crate::vrf::SecretKey;
+
+    use super::*;
+
+    const D: usize = 2;
+    type C = PoseidonGoldilocksConfig;
+    type F = <C as GenericConfig<D>>::F;
+
+    /// Verifies a proof made of `gamma` and the limbs `c` and `s` in-circuit and checks that its
+    /// output is `beta`.
+    fn verify_vrf_circuit_with(
+        pk: PublicKey,
+        alpha: &[F],
+        gamma: Point,
+        c: &BigUint,
+        s: &BigUint,
+        beta: HashOut<F>,
+    ) -> Result<()> {
+        let config = CircuitConfig::standard_recursion_config();
+        let mut builder = CircuitBuilder::<F, D>::new(config);
+
+        let pk_target = builder.add_virtual_vrf_public_key_target();
+        let alpha_target = builder.add_virtual_targets(alpha.len());
+        let proof_target = builder.add_virtual_vrf_proof_target();
+        let beta_target = builder.verify_vrf(pk_target, &alpha_target, &proof_target);
+        builder.register_public_inputs(&beta_target.elements);
+
+        let mut pw = PartialWitness::new();
+        pw.set_vrf_public_key_target(pk_target, pk);
+        pw.set_target_arr(&alpha_target, alpha)?;
+        pw.set_quintic_ext_target(proof_target.gamma, gamma.encode());
+        pw.set_biguint_target(&proof_target.c, c);
+        pw.set_biguint_target(&proof_target.s, s);
+
+        let circuit = builder.build::<C>();
+        let proof = circuit.prove(pw)?;
+        assert_eq!(proof.public_inputs, beta.elements);
+        circuit.verify(proof)
+    }
+
+    #[test]
+    fn test_verify_vrf() -> Result<()> {
+        let sk = SecretKey::sample(&mut thread_rng());
+        let alpha = F::rand_vec(4);
+        let (beta, proof) = sk.prove(&alpha);
+        verify_vrf_circuit_with(sk.public_key(), &alpha, proof.gamma, &proof.c.to_canonical_biguint(), &proof.s.to_canonical_biguint(), beta)
+    }
+
+    #[test]
+    fn test_verify_vrf_wrong_alpha() {
+        let sk = SecretKey::sample(&mut thread_rng());
+        let mut alpha = F::rand_vec(4);
+        let (beta, proof) = sk.prove(&alpha);
+        alpha[0] += F::ONE;
+        assert!(verify_vrf_circuit_with(sk.public_key(), &alpha, proof.gamma, &proof.c.to_canonical_biguint(), &proof.s.to_canonical_biguint(), beta).is_err());
+    }
+
+    #[test]
+    fn test_verify_vrf_wrong_public_key() {
+        let mut rng = thread_rng();
+        let sk = SecretKey::sample(&mut rng);
+        let alpha = F::rand_vec(4);
+        let (beta, proof) = sk.prove(&alpha);
+        let pk = SecretKey::sample(&mut rng).public_key();
+        assert!(verify_vrf_circuit_with(pk, &alpha, proof.gamma, &proof.c.to_canonical_biguint(), &proof.s.to_canonical_biguint(), beta).is_err());
+    }
+
+    #[test]
+    fn test_verify_vrf_wrong_gamma() {
+        let sk = SecretKey::sample(&mut thread_rng());
+        let alpha = F::rand_vec(4);
+        let (beta, proof) = sk.prove(&alpha);
+        let gamma = proof.gamma + Point::GENERATOR;
+        assert!(verify_vrf_circuit_with(sk.public_key(), &alpha, gamma, &proof.c.to_canonical_biguint(), &proof.s.to_canonical_biguint(), beta).is_err());
+    }
+
+    #[test]
+    fn test_verify_vrf_wrong_c() {
+        let sk = SecretKey::sample(&mut thread_rng());
+        let alpha = F::rand_vec(4);
+        let (beta, proof) = sk.prove(&alpha);
+        let c = (proof.c + Scalar::ONE).to_canonical_biguint();
+        assert!(verify_vrf_circuit_with(sk.public_key(), &alpha, proof.gamma, &c, &proof.s.to_canonical_biguint(), beta).is_err());
+    }
+
+    #[test]
+    fn test_verify_vrf_wrong_s() {
+        let sk = SecretKey::sample(&mut thread_rng());
+        let alpha = F::rand_vec(4);
+        let (beta, proof) = sk.prove(&alpha);
+        let s = (proof.s + Scalar::ONE).to_canonical_biguint();
+        assert!(verify_vrf_circuit_with(sk.public_key(), &alpha, proof.gamma, &proof.c.to_canonical_biguint(), &s, beta).is_err());
+    }
+
+    #[test]
+    fn test_verify_vrf_non_canonical_s() {
+        // s + n satisfies the verification equations, but is not a canonical scalar
+        let sk = SecretKey::sample(&mut thread_rng());
+        let alpha = F::rand_vec(4);
+        let (beta, proof) = sk.prove(&alpha);
+        let s = proof.s.to_canonical_biguint() + Scalar::order();
+        assert!(verify_vrf_circuit_with(sk.public_key(), &alpha, proof.gamma, &proof.c.to_canonical_biguint(), &s, beta).is_err());
+    }
+}
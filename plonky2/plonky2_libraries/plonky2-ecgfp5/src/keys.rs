@@ -0,0 +1,70 @@
+//! Key pairs shared by the schemes over EcGFp5. Every scheme defines its own `SecretKey` and
+//! `PublicKey` types with `define_keys!`, so that a key cannot be passed to another scheme by
+//! mistake, and adds its scheme-specific methods to them.
+
+/// Defines `SecretKey`, a nonzero scalar, and `PublicKey`, its non-neutral multiple of the
+/// generator, in the calling module.
+macro_rules! define_keys {
+    () => {
+        #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+        pub struct SecretKey($crate::curve::scalar_field::Scalar);
+
+        #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+        pub struct PublicKey($crate::curve::curve::Point);
+
+        impl SecretKey {
+            pub fn sample<R: rand::RngCore>(rng: &mut R) -> Self {
+                use plonky2_field::types::{Field, Sample};
+                use $crate::curve::scalar_field::Scalar;
+
+                loop {
+                    let sk = Scalar::sample(rng);
+                    if sk != Scalar::ZERO {
+                        return Self(sk);
+                    }
+                }
+            }
+
+            /// Returns `None` for zero.
+            pub fn from_scalar(sk: $crate::curve::scalar_field::Scalar) -> Option<Self> {
+                use plonky2_field::types::Field;
+                use $crate::curve::scalar_field::Scalar;
+
+                if sk == Scalar::ZERO {
+                    None
+                } else {
+                    Some(Self(sk))
+                }
+            }
+
+            pub fn to_scalar(&self) -> $crate::curve::scalar_field::Scalar {
+                self.0
+            }
+
+            pub fn public_key(&self) -> PublicKey {
+                PublicKey($crate::curve::curve::Point::mulgen(self.0))
+            }
+        }
+
+        impl PublicKey {
+            /// Returns `None` for the neutral point.
+            pub fn from_point(point: $crate::curve::curve::Point) -> Option<Self> {
+                if point.is_neutral() {
+                    None
+                } else {
+                    Some(Self(point))
+                }
+            }
+
+            pub fn to_point(&self) -> $crate::curve::curve::Point {
+                self.0
+            }
+
+            pub fn encode(&self) -> $crate::curve::GFp5 {
+                self.0.encode()
+            }
+        }
+    };
+}
+
+pub(crate) use define_keys;
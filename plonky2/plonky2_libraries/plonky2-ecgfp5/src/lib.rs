@@ -1,7 +1,13 @@
 extern crate alloc;
 
 pub mod curve;
+pub mod elgamal;
 pub mod gadgets;
+mod keys;
+pub mod pedersen;
+pub mod schnorr;
+pub mod serialization;
+pub mod vrf;
 
 #[cfg(test)]
 mod macros;
@@ -7,6 +7,19 @@ macro_rules! test_field_arithmetic {
             use plonky2_field::types::{Field, Sample};
             use rand::Rng;
 
+            #[test]
+            fn modular_reduction() {
+                let mut rng = rand::thread_rng();
+                for _ in 0..10 {
+                    let x_lo = rng.gen::<u64>();
+                    let x_hi = rng.gen::<u32>();
+                    let x = (x_lo as u128) + ((x_hi as u128) << 64);
+                    let a = <$field>::from_noncanonical_u128(x);
+                    let b = <$field>::from_noncanonical_u96((x_lo, x_hi));
+                    assert_eq!(a, b);
+                }
+            }
+
             #[test]
             fn batch_inversion() {
                 for n in 0..20 {
@@ -96,3 +109,224 @@ macro_rules! test_field_arithmetic {
         }
     };
 }
+
+/// Like `test_prime_field_arithmetic` in plonky2/field, but for prime fields wider than 64 bits.
+/// Results are checked against `BigUint` arithmetic modulo `order()`.
+#[macro_export]
+macro_rules! test_prime_field_arithmetic {
+    ($field:ty) => {
+        mod prime_field_arithmetic {
+            use num::bigint::BigUint;
+            use num::{One, Zero};
+            use plonky2_field::ops::Square;
+            use plonky2_field::types::{Field, PrimeField, Sample};
+            use rand::Rng;
+
+            type F = $field;
+
+            /// Canonical values covering the limb boundaries and the top of the field, plus a few
+            /// random elements.
+            fn test_inputs() -> Vec<BigUint> {
+                let order = F::order();
+                let mut inputs = (0u32..4).map(BigUint::from).collect::<Vec<_>>();
+                for bits in [32, 63, 64, 128, 192, 256, 318] {
+                    let pow: BigUint = BigUint::one() << bits;
+                    inputs.push(&pow - 1u32);
+                    inputs.push(pow.clone());
+                    inputs.push(pow + 1u32);
+                }
+                inputs.extend((1u32..4).map(|i| &order - i));
+                inputs.extend((0..4).map(|_| F::rand().to_canonical_biguint()));
+                inputs.retain(|x| x < &order);
+                inputs
+            }
+
+            fn run_unaryop_test_cases<UnaryOp, ExpectedOp>(op: UnaryOp, expected_op: ExpectedOp)
+            where
+                UnaryOp: Fn(F) -> F,
+                ExpectedOp: Fn(&BigUint) -> BigUint,
+            {
+                for x in test_inputs() {
+                    let actual = op(F::from_noncanonical_biguint(x.clone())).to_canonical_biguint();
+                    assert_eq!(actual, expected_op(&x), "input {}", x);
+                }
+            }
+
+            fn run_binaryop_test_cases<BinaryOp, ExpectedOp>(op: BinaryOp, expected_op: ExpectedOp)
+            where
+                BinaryOp: Fn(F, F) -> F,
+                ExpectedOp: Fn(&BigUint, &BigUint) -> BigUint,
+            {
+                let inputs = test_inputs();
+                for lhs in &inputs {
+                    for rhs in &inputs {
+                        let actual = op(
+                            F::from_noncanonical_biguint(lhs.clone()),
+                            F::from_noncanonical_biguint(rhs.clone()),
+                        )
+                        .to_canonical_biguint();
+                        assert_eq!(actual, expected_op(lhs, rhs), "inputs ({}, {})", lhs, rhs);
+                    }
+                }
+            }
+
+            #[test]
+            fn arithmetic_addition() {
+                run_binaryop_test_cases(|x, y| x + y, |x, y| (x + y) % F::order())
+            }
+
+            #[test]
+            fn arithmetic_subtraction() {
+                run_binaryop_test_cases(|x, y| x - y, |x, y| (x + F::order() - y) % F::order())
+            }
+
+            #[test]
+            fn arithmetic_negation() {
+                run_unaryop_test_cases(|x| -x, |x| (F::order() - x) % F::order())
+            }
+
+            #[test]
+            fn arithmetic_multiplication() {
+                run_binaryop_test_cases(|x, y| x * y, |x, y| (x * y) % F::order())
+            }
+
+            #[test]
+            fn arithmetic_square() {
+                run_unaryop_test_cases(|x| x.square(), |x| (x * x) % F::order())
+            }
+
+            #[test]
+            fn inversion() {
+                assert_eq!(F::ZERO.try_inverse(), None);
+
+                for x in test_inputs() {
+                    if !x.is_zero() {
+                        let x = F::from_noncanonical_biguint(x);
+                        assert_eq!(x * x.inverse(), F::ONE);
+                    }
+                }
+            }
+
+            #[test]
+            fn inverse_2exp() {
+                let v = F::TWO_ADICITY;
+
+                for e in [0, 1, 2, 3, 4, v - 2, v - 1, v, v + 1, v + 2, 123 * v] {
+                    let x = F::TWO.exp_u64(e as u64);
+                    let y = F::inverse_2exp(e);
+                    assert_eq!(x * y, F::ONE);
+                }
+            }
+
+            #[test]
+            fn constants() {
+                let order = F::order();
+                assert_eq!(F::characteristic(), order);
+                assert_eq!(F::ZERO.to_canonical_biguint(), BigUint::zero());
+                assert_eq!(F::ONE.to_canonical_biguint(), BigUint::one());
+                assert_eq!(F::TWO.to_canonical_biguint(), BigUint::from(2u32));
+                assert_eq!(F::NEG_ONE.to_canonical_biguint(), &order - 1u32);
+                assert!(order.bits() as usize <= F::BITS);
+
+                let n_minus_one = &order - 1u32;
+                assert_eq!(n_minus_one.trailing_zeros(), Some(F::TWO_ADICITY as u64));
+                assert_eq!(F::CHARACTERISTIC_TWO_ADICITY, F::TWO_ADICITY);
+                assert_eq!(
+                    F::MULTIPLICATIVE_GROUP_GENERATOR.exp_biguint(&(n_minus_one >> F::TWO_ADICITY)),
+                    F::POWER_OF_TWO_GENERATOR
+                );
+            }
+
+            #[test]
+            fn field_order() {
+                let x = F::rand();
+                assert_eq!(x.exp_biguint(&(F::order() - 1u32)), F::ONE);
+            }
+
+            #[test]
+            fn from_noncanonical_integers() {
+                let order = F::order();
+                let mut rng = rand::thread_rng();
+                for _ in 0..10 {
+                    let n = rng.gen::<u64>();
+                    assert_eq!(
+                        F::from_noncanonical_u64(n).to_canonical_biguint(),
+                        BigUint::from(n) % &order
+                    );
+
+                    let n = rng.gen::<i64>();
+                    let expected = BigUint::from(n.unsigned_abs()) % &order;
+                    let expected = if n < 0 {
+                        (&order - expected) % &order
+                    } else {
+                        expected
+                    };
+                    assert_eq!(F::from_noncanonical_i64(n).to_canonical_biguint(), expected);
+
+                    let n = rng.gen::<u128>();
+                    assert_eq!(
+                        F::from_noncanonical_u128(n).to_canonical_biguint(),
+                        BigUint::from(n) % &order
+                    );
+                }
+
+                for n in [0, 1, -1, i64::MIN, i64::MAX] {
+                    let expected = if n < 0 {
+                        -F::from_canonical_u64(n.unsigned_abs())
+                    } else {
+                        F::from_canonical_u64(n as u64)
+                    };
+                    assert_eq!(F::from_noncanonical_i64(n), expected);
+                }
+            }
+
+            #[test]
+            fn from_noncanonical_biguint() {
+                let order = F::order();
+                let mut rng = rand::thread_rng();
+
+                let mut inputs = vec![
+                    BigUint::zero(),
+                    order.clone(),
+                    &order + 1u32,
+                    &order * 2u32 - 1u32,
+                    &order * &order,
+                    (BigUint::one() << F::BITS) - 1u32,
+                    (BigUint::one() << (2 * F::BITS)) - 1u32,
+                ];
+                for bits in [F::BITS, F::BITS + 64, 2 * F::BITS, 4 * F::BITS + 7] {
+                    let bytes = (0..bits.div_ceil(8)).map(|_| rng.gen::<u8>()).collect::<Vec<_>>();
+                    inputs.push(BigUint::from_bytes_le(&bytes));
+                }
+
+                for x in inputs {
+                    assert_eq!(
+                        F::from_noncanonical_biguint(x.clone()).to_canonical_biguint(),
+                        x % &order
+                    );
+                }
+            }
+
+            #[test]
+            fn sqrt() {
+                assert_eq!(F::ZERO.sqrt(), Some(F::ZERO));
+                for _ in 0..10 {
+                    let x = F::rand();
+                    let sq = x.square();
+                    let root = sq.sqrt().expect("squares have square roots");
+                    assert!(root == x || root == -x);
+                }
+                assert!(!F::MULTIPLICATIVE_GROUP_GENERATOR.is_quadratic_residue());
+                assert_eq!(F::MULTIPLICATIVE_GROUP_GENERATOR.sqrt(), None);
+            }
+
+            #[test]
+            fn subtraction_double_wraparound() {
+                let a = F::from_noncanonical_biguint((F::order() + 1u32) >> 1);
+                let x = a * F::TWO;
+                assert_eq!(x, F::ONE);
+                assert_eq!(F::ZERO - x, F::NEG_ONE);
+            }
+        }
+    };
+}
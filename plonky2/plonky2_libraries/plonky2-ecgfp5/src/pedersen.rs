@@ -0,0 +1,115 @@
+//! Pedersen vector commitments over EcGFp5.
+//!
+//! A commitment to `m = (m_1, ..., m_k)` with blinding factor `r` is `C = r*H + sum m_i*G_i`.
+//! All generators are derived by `Point::hash_to_curve`, so nobody knows a discrete-log relation
+//! between them. Commitments are additively homomorphic: `commit(a, r) + commit(b, s)` equals
+//! `commit(a + b, r + s)`. The circuit counterpart lives in `gadgets::pedersen` and is re-exported here.
+
+use alloc::vec::Vec;
+use plonky2_field::types::Field;
+
+use crate::curve::curve::Point;
+use crate::curve::scalar_field::Scalar;
+use crate::curve::GFp;
+
+pub use crate::gadgets::pedersen::CircuitBuilderPedersen;
+
+// domain separator for the generators, distinct from the ones used in `schnorr` and `curve`
+pub(crate) const PEDERSEN_DOMAIN: u64 = 5;
+
+/// Generators for committing to vectors of up to `g.len()` scalars.
+#[derive(Clone, Debug)]
+pub struct PedersenGenerators {
+    pub g: Vec<Point>,
+    pub h: Point,
+}
+
+impl PedersenGenerators {
+    /// Derives the blinding generator `H` and `n` message generators `G_i`. The generators for
+    /// `n` are a prefix of the ones for any larger `n`.
+    pub fn new(n: usize) -> Self {
+        let domain = GFp::from_canonical_u64(PEDERSEN_DOMAIN);
+        let h = Point::hash_to_curve(&[domain, GFp::ZERO]);
+        let g = (1..=n)
+            .map(|i| Point::hash_to_curve(&[domain, GFp::from_canonical_usize(i)]))
+            .collect();
+
+        Self { g, h }
+    }
+
+    pub fn commit(&self, msg: &[Scalar], r: Scalar) -> Point {
+        assert!(
+            msg.len() <= self.g.len(),
+            "message longer than the number of generators"
+        );
+
+        msg.iter()
+            .zip(self.g.iter())
+            .fold(self.h * r, |acc, (&m, &g)| acc + g * m)
+    }
+
+    /// Checks that `c` is a commitment to `msg` with blinding factor `r`.
+    pub fn open(&self, c: Point, msg: &[Scalar], r: Scalar) -> bool {
+        msg.len() <= self.g.len() && self.commit(msg, r).equals(c)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use plonky2_field::types::Sample;
+    use rand::thread_rng;
+
+    use super::*;
+
+    #[test]
+    fn test_commit_open() {
+        let mut rng = thread_rng();
+        let gens = PedersenGenerators::new(4);
+        let msg: Vec<Scalar> = (0..4).map(|_| Scalar::sample(&mut rng)).collect();
+        let r = Scalar::sample(&mut rng);
+
+        let c = gens.commit(&msg, r);
+        assert!(gens.open(c, &msg, r));
+        assert!(!gens.open(c, &msg, r + Scalar::ONE));
+
+        let mut other_msg = msg.clone();
+        other_msg[2] += Scalar::ONE;
+        assert!(!gens.open(c, &other_msg, r));
+
+        // shorter messages are padded with zeros
+        assert!(gens
+            .commit(&msg[..2], r)
+            .equals(gens.commit(&[msg[0], msg[1], Scalar::ZERO], r)));
+    }
+
+    #[test]
+    fn test_homomorphic() {
+        let mut rng = thread_rng();
+        let gens = PedersenGenerators::new(3);
+        let a: Vec<Scalar> = (0..3).map(|_| Scalar::sample(&mut rng)).collect();
+        let b: Vec<Scalar> = (0..3).map(|_| Scalar::sample(&mut rng)).collect();
+        let (r, s) = (Scalar::sample(&mut rng), Scalar::sample(&mut rng));
+
+        let sum: Vec<Scalar> = a.iter().zip(b.iter()).map(|(&x, &y)| x + y).collect();
+        assert!((gens.commit(&a, r) + gens.commit(&b, s)).equals(gens.commit(&sum, r + s)));
+    }
+
+    #[test]
+    fn test_generators() {
+        let gens = PedersenGenerators::new(8);
+        let prefix = PedersenGenerators::new(3);
+        assert!(prefix
+            .g
+            .iter()
+            .zip(gens.g.iter())
+            .all(|(&p, &q)| p.equals(q)));
+        assert!(prefix.h.equals(gens.h));
+
+        let mut all = gens.g.clone();
+        all.push(gens.h);
+        for (i, p) in all.iter().enumerate() {
+            assert!(!p.is_neutral());
+            assert!(all[i + 1..].iter().all(|&q| !q.equals(*p)));
+        }
+    }
+}
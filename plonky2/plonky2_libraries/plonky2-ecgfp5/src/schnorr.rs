@@ -0,0 +1,237 @@
+//! Schnorr signatures over EcGFp5, using Poseidon as the hash function.
+//!
+//! A signature on a message `m` (a sequence of Goldilocks elements) under the public key
+//! `Q = sk*G` is a pair `(s, e)` where `e = H(R || Q || m)` for the nonce point `R = k*G`
+//! and `s = k - e*sk`. Verification recomputes `R = s*G + e*Q` and checks `e`.
+//! The circuit counterpart lives in `gadgets::schnorr` and is re-exported here.
+
+use alloc::vec::Vec;
+use plonky2::hash::hashing::{hash_n_to_m_no_pad, PlonkyPermutation};
+use plonky2::hash::poseidon::PoseidonPermutation;
+use plonky2_field::extension::quintic::QuinticExtension;
+use plonky2_field::types::{Field, Field64, PrimeField64};
+
+use crate::curve::curve::Point;
+use crate::curve::scalar_field::Scalar;
+use crate::curve::{GFp, GFp5};
+use crate::keys::define_keys;
+
+pub use crate::gadgets::schnorr::{
+    CircuitBuilderSchnorr, PartialWitnessSchnorr, SchnorrNonceGenerator, SchnorrPublicKeyTarget,
+    SchnorrSignatureTarget,
+};
+
+// domain separators, so that nonces and challenges never collide
+pub(crate) const NONCE_DOMAIN: u64 = 1;
+pub(crate) const CHALLENGE_DOMAIN: u64 = 2;
+pub(crate) const BATCH_DOMAIN: u64 = 3;
+
+/// Pads `input` with `1 0* 1` up to a multiple of the Poseidon rate, so that hashing
+/// messages of different lengths cannot collide.
+pub(crate) fn pad(input: &mut Vec<GFp>) {
+    let rate = PoseidonPermutation::<GFp>::RATE;
+    input.push(GFp::ONE);
+    while !(input.len() + 1).is_multiple_of(rate) {
+        input.push(GFp::ZERO);
+    }
+    input.push(GFp::ONE);
+}
+
+pub(crate) fn hash_padded(mut input: Vec<GFp>, num_outputs: usize) -> Vec<GFp> {
+    pad(&mut input);
+    hash_n_to_m_no_pad::<GFp, PoseidonPermutation<GFp>>(&input, num_outputs)
+}
+
+/// Computes the challenge `e = H(R || Q || m)` from the encodings of `R` and `Q`.
+pub fn schnorr_challenge(r: GFp5, pk: GFp5, msg: &[GFp]) -> Scalar {
+    let mut preimage = vec![GFp::from_canonical_u64(CHALLENGE_DOMAIN)];
+    preimage.extend(r.0);
+    preimage.extend(pk.0);
+    preimage.extend_from_slice(msg);
+
+    let e = hash_padded(preimage, 5);
+    Scalar::from_gfp5(QuinticExtension(e.try_into().unwrap()))
+}
+
+fn gfp5_to_bytes(x: GFp5) -> [u8; 40] {
+    let mut r = [0u8; 40];
+    for i in 0..5 {
+        r[8 * i..8 * i + 8].copy_from_slice(&x.0[i].to_canonical_u64().to_le_bytes());
+    }
+    r
+}
+
+fn gfp5_from_bytes(buf: &[u8; 40]) -> Option<GFp5> {
+    let mut limbs = [GFp::ZERO; 5];
+    for i in 0..5 {
+        let limb = u64::from_le_bytes(buf[8 * i..8 * i + 8].try_into().unwrap());
+        if limb >= GFp::ORDER {
+            return None;
+        }
+        limbs[i] = GFp::from_canonical_u64(limb);
+    }
+    Some(QuinticExtension(limbs))
+}
+
+fn scalar_from_bytes(buf: &[u8]) -> Option<Scalar> {
+    let (s, c) = Scalar::try_from_noncanonical_bytes(buf);
+    if c == 0 {
+        None
+    } else {
+        Some(s)
+    }
+}
+
+define_keys!();
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Signature {
+    pub s: Scalar,
+    pub e: Scalar,
+}
+
+impl SecretKey {
+    /// Encode this key over exactly 40 bytes.
+    pub fn to_bytes(&self) -> [u8; 40] {
+        self.0.encode()
+    }
+
+    /// Decode a key, rejecting non-canonical encodings and zero.
+    pub fn from_bytes(buf: &[u8; 40]) -> Option<Self> {
+        scalar_from_bytes(buf).and_then(Self::from_scalar)
+    }
+
+    // Deterministic nonce k = H(sk || m), so that signing needs no randomness and the
+    // same nonce is never reused for two different messages.
+    fn nonce(&self, msg: &[GFp]) -> Scalar {
+        let mut preimage = vec![GFp::from_canonical_u64(NONCE_DOMAIN)];
+        preimage.extend(
+            self.to_bytes()
+                .chunks(4)
+                .map(|c| GFp::from_canonical_u32(u32::from_le_bytes(c.try_into().unwrap()))),
+        );
+        preimage.extend_from_slice(msg);
+
+        // squeeze 640 bits so that the reduction modulo n is unbiased
+        let bytes: Vec<u8> = hash_padded(preimage, 10)
+            .into_iter()
+            .flat_map(|x| x.to_canonical_u64().to_le_bytes())
+            .collect();
+        Scalar::from_noncanonical_bytes(&bytes)
+    }
+
+    pub fn sign(&self, msg: &[GFp]) -> Signature {
+        let k = self.nonce(msg);
+        let r = Point::mulgen(k);
+        let e = schnorr_challenge(r.encode(), self.public_key().encode(), msg);
+        let s = k - e * self.0;
+
+        Signature { s, e }
+    }
+}
+
+impl PublicKey {
+    /// Encode this key over exactly 40 bytes.
+    pub fn to_bytes(&self) -> [u8; 40] {
+        gfp5_to_bytes(self.encode())
+    }
+
+    /// Decode a key, rejecting invalid encodings and the neutral point.
+    pub fn from_bytes(buf: &[u8; 40]) -> Option<Self> {
+        gfp5_from_bytes(buf)
+            .and_then(Point::decode)
+            .and_then(Self::from_point)
+    }
+
+    pub fn verify(&self, msg: &[GFp], sig: &Signature) -> bool {
+        let r = Point::mulgen(sig.s) + self.0 * sig.e;
+        schnorr_challenge(r.encode(), self.encode(), msg) == sig.e
+    }
+}
+
+impl Signature {
+    /// Encode this signature as `s || e` over exactly 80 bytes.
+    pub fn to_bytes(&self) -> [u8; 80] {
+        let mut r = [0u8; 80];
+        r[..40].copy_from_slice(&self.s.encode());
+        r[40..].copy_from_slice(&self.e.encode());
+        r
+    }
+
+    /// Decode a signature, rejecting non-canonical scalars.
+    pub fn from_bytes(buf: &[u8; 80]) -> Option<Self> {
+        let s = scalar_from_bytes(&buf[..40])?;
+        let e = scalar_from_bytes(&buf[40..])?;
+        Some(Self { s, e })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use plonky2_field::types::Sample;
+    use rand::thread_rng;
+
+    use super::*;
+
+    #[test]
+    fn test_sign_verify() {
+        let mut rng = thread_rng();
+        let sk = SecretKey::sample(&mut rng);
+        let pk = sk.public_key();
+        let msg = GFp::rand_vec(12);
+
+        let sig = sk.sign(&msg);
+        assert!(pk.verify(&msg, &sig));
+
+        let mut other_msg = msg.clone();
+        other_msg[0] += GFp::ONE;
+        assert!(!pk.verify(&other_msg, &sig));
+
+        let other_pk = SecretKey::sample(&mut rng).public_key();
+        assert!(!other_pk.verify(&msg, &sig));
+
+        let forged = Signature { s: sig.s + Scalar::ONE, e: sig.e };
+        assert!(!pk.verify(&msg, &forged));
+    }
+
+    #[test]
+    fn test_deterministic_nonce() {
+        let mut rng = thread_rng();
+        let sk = SecretKey::sample(&mut rng);
+        let msg = GFp::rand_vec(3);
+
+        assert_eq!(sk.sign(&msg), sk.sign(&msg));
+        assert_ne!(sk.sign(&msg).e, sk.sign(&msg[..2]).e);
+    }
+
+    #[test]
+    fn test_padding_separates_lengths() {
+        let r = Point::sample(&mut thread_rng()).encode();
+        let pk = Point::sample(&mut thread_rng()).encode();
+
+        // without padding, a trailing zero would hash to the same challenge
+        let e1 = schnorr_challenge(r, pk, &[GFp::ONE]);
+        let e2 = schnorr_challenge(r, pk, &[GFp::ONE, GFp::ZERO]);
+        assert_ne!(e1, e2);
+    }
+
+    #[test]
+    fn test_encoding_roundtrip() {
+        let mut rng = thread_rng();
+        let sk = SecretKey::sample(&mut rng);
+        let pk = sk.public_key();
+        let sig = sk.sign(&GFp::rand_vec(4));
+
+        assert_eq!(SecretKey::from_bytes(&sk.to_bytes()), Some(sk));
+        assert_eq!(PublicKey::from_bytes(&pk.to_bytes()), Some(pk));
+        assert_eq!(Signature::from_bytes(&sig.to_bytes()), Some(sig));
+
+        // zero key, neutral point and non-canonical scalars are rejected
+        assert_eq!(SecretKey::from_bytes(&[0u8; 40]), None);
+        assert_eq!(PublicKey::from_bytes(&[0u8; 40]), None);
+        assert_eq!(PublicKey::from_bytes(&[0xFF; 40]), None);
+        let mut bad_sig = sig.to_bytes();
+        bad_sig[..40].copy_from_slice(&[0xFF; 40]);
+        assert_eq!(Signature::from_bytes(&bad_sig), None);
+    }
+}
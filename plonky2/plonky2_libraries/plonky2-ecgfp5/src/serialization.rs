@@ -0,0 +1,207 @@
+use alloc::vec::Vec;
+use core::marker::PhantomData;
+
+use plonky2::gadgets::arithmetic::EqualityGenerator;
+use plonky2::gadgets::arithmetic_extension::QuotientGeneratorExtension;
+use plonky2::gadgets::range_check::LowHighGenerator;
+use plonky2::gadgets::split_base::BaseSumGenerator;
+use plonky2::gadgets::split_join::{SplitGenerator, WireSplitGenerator};
+use plonky2::gates::arithmetic_base::ArithmeticBaseGenerator;
+use plonky2::gates::arithmetic_extension::ArithmeticExtensionGenerator;
+use plonky2::gates::base_sum::BaseSplitGenerator;
+use plonky2::gates::coset_interpolation::InterpolationGenerator;
+use plonky2::gates::exponentiation::ExponentiationGenerator;
+use plonky2::gates::lookup::LookupGenerator;
+use plonky2::gates::lookup_table::LookupTableGenerator;
+use plonky2::gates::multiplication_extension::MulExtensionGenerator;
+use plonky2::gates::poseidon::PoseidonGenerator;
+use plonky2::gates::poseidon_mds::PoseidonMdsGenerator;
+use plonky2::gates::random_access::RandomAccessGenerator;
+use plonky2::gates::reducing::ReducingGenerator;
+use plonky2::gates::reducing_extension::ReducingGenerator as ReducingExtensionGenerator;
+use plonky2::hash::hash_types::RichField;
+use plonky2::iop::generator::{
+    ConstantGenerator, CopyGenerator, NonzeroTestGenerator, RandomValueGenerator, SimpleGenerator,
+};
+use plonky2::plonk::config::{AlgebraicHasher, GenericConfig};
+use plonky2::recursion::dummy_circuit::DummyProofGenerator;
+use plonky2::util::serialization::gate_serialization::log;
+use plonky2::util::serialization::{Buffer, IoResult, Read, WitnessGeneratorSerializer, Write};
+use plonky2::{get_generator_tag_impl, impl_generator_serializer, read_generator_impl};
+use plonky2_ecdsa::gadgets::biguint::BigUintDivRemGenerator;
+use plonky2_ecdsa::gadgets::nonnative::{
+    NonNativeAdditionGenerator, NonNativeInverseGenerator, NonNativeMulAddGenerator,
+    NonNativeMultipleAddsGenerator, NonNativeMultiplicationGenerator,
+    NonNativeSubtractionGenerator,
+};
+use plonky2_field::extension::Extendable;
+use plonky2_u32::gates::add_many_u32::U32AddManyGenerator;
+use plonky2_u32::gates::arithmetic_u32::U32ArithmeticGenerator;
+use plonky2_u32::gates::bitwise_u32::U32BitwiseGenerator;
+use plonky2_u32::gates::comparison::ComparisonGenerator;
+use plonky2_u32::gates::range_check_u32::U32RangeCheckGenerator;
+use plonky2_u32::gates::subtraction_u32::U32SubtractionGenerator;
+
+use crate::curve::scalar_field::Scalar;
+use crate::gadgets::base_field::{
+    QuinticExtensionTarget, QuinticQuotientGenerator, QuinticSqrtGenerator,
+};
+use crate::gadgets::schnorr::SchnorrNonceGenerator;
+
+pub trait WriteQuinticExt {
+    fn write_target_quintic_ext(&mut self, x: QuinticExtensionTarget) -> IoResult<()>;
+}
+
+impl WriteQuinticExt for Vec<u8> {
+    #[inline]
+    fn write_target_quintic_ext(&mut self, x: QuinticExtensionTarget) -> IoResult<()> {
+        self.write_target_array(&x.0)
+    }
+}
+
+pub trait ReadQuinticExt {
+    fn read_target_quintic_ext(&mut self) -> IoResult<QuinticExtensionTarget>;
+}
+
+impl ReadQuinticExt for Buffer<'_> {
+    #[inline]
+    fn read_target_quintic_ext(&mut self) -> IoResult<QuinticExtensionTarget> {
+        Ok(QuinticExtensionTarget(self.read_target_array()?))
+    }
+}
+
+/// The gadgets of this crate use the same gates as those of `plonky2_ecdsa`.
+pub use plonky2_ecdsa::serialization::EcdsaGateSerializer as EcGFp5GateSerializer;
+
+/// Serializes the default `plonky2` generators together with the `u32`, `BigUint` and nonnative
+/// generators over the EcGFp5 scalar field, and the generators of this crate.
+#[derive(Debug, Default)]
+pub struct EcGFp5GeneratorSerializer<C: GenericConfig<D>, const D: usize> {
+    pub _phantom: PhantomData<C>,
+}
+
+impl<F, C, const D: usize> WitnessGeneratorSerializer<F, D> for EcGFp5GeneratorSerializer<C, D>
+where
+    F: RichField + Extendable<D> + Extendable<5>,
+    C: GenericConfig<D, F = F> + 'static,
+    C::Hasher: AlgebraicHasher<F>,
+    // the following are only implemented over the Goldilocks field
+    QuinticSqrtGenerator: SimpleGenerator<F, D>,
+    SchnorrNonceGenerator: SimpleGenerator<F, D>,
+{
+    impl_generator_serializer! {
+        EcGFp5GeneratorSerializer,
+        ArithmeticBaseGenerator<F, D>,
+        ArithmeticExtensionGenerator<F, D>,
+        BaseSplitGenerator<2>,
+        BaseSplitGenerator<4>,
+        BaseSumGenerator<2>,
+        BaseSumGenerator<4>,
+        ConstantGenerator<F>,
+        CopyGenerator,
+        DummyProofGenerator<F, C, D>,
+        EqualityGenerator,
+        ExponentiationGenerator<F, D>,
+        InterpolationGenerator<F, D>,
+        LookupGenerator,
+        LookupTableGenerator,
+        LowHighGenerator,
+        MulExtensionGenerator<F, D>,
+        NonzeroTestGenerator,
+        PoseidonGenerator<F, D>,
+        PoseidonMdsGenerator<D>,
+        QuotientGeneratorExtension<D>,
+        RandomAccessGenerator<F, D>,
+        RandomValueGenerator,
+        ReducingGenerator<D>,
+        ReducingExtensionGenerator<D>,
+        SplitGenerator,
+        WireSplitGenerator,
+        ComparisonGenerator<F, D>,
+        U32AddManyGenerator<F, D>,
+        U32ArithmeticGenerator<F, D>,
+        U32BitwiseGenerator<F, D>,
+        U32RangeCheckGenerator<F, D>,
+        U32SubtractionGenerator<F, D>,
+        BigUintDivRemGenerator<F, D>,
+        NonNativeAdditionGenerator<F, D, Scalar>,
+        NonNativeMultipleAddsGenerator<F, D, Scalar>,
+        NonNativeSubtractionGenerator<F, D, Scalar>,
+        NonNativeMultiplicationGenerator<F, D, Scalar>,
+        NonNativeMulAddGenerator<F, D, Scalar>,
+        NonNativeInverseGenerator<F, D, Scalar>,
+        QuinticQuotientGenerator,
+        QuinticSqrtGenerator,
+        SchnorrNonceGenerator
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+    use plonky2::iop::witness::{PartialWitness, WitnessWrite};
+    use plonky2::plonk::circuit_builder::CircuitBuilder;
+    use plonky2::plonk::circuit_data::{CircuitConfig, CircuitData};
+    use plonky2::plonk::config::PoseidonGoldilocksConfig;
+    use plonky2_field::types::Sample;
+    use rand::thread_rng;
+
+    use crate::curve::GFp5;
+    use crate::gadgets::base_field::{CircuitBuilderGFp5, PartialWitnessQuinticExt};
+    use crate::gadgets::schnorr::{CircuitBuilderSchnorr, PartialWitnessSchnorr};
+    use crate::schnorr::SecretKey;
+
+    use super::*;
+
+    #[test]
+    fn test_circuit_data_round_trip() -> Result<()> {
+        const D: usize = 2;
+        type C = PoseidonGoldilocksConfig;
+        type F = <C as GenericConfig<D>>::F;
+
+        let mut rng = thread_rng();
+        let config = CircuitConfig::standard_recursion_config();
+        let mut builder = CircuitBuilder::<F, D>::new(config);
+
+        // batch verification uses every generator of this crate, along with the nonnative ones
+        let pk_targets: Vec<_> = (0..2)
+            .map(|_| builder.add_virtual_schnorr_public_key_target())
+            .collect();
+        let msg_targets: Vec<_> = (0..2).map(|_| builder.add_virtual_targets(3)).collect();
+        let sig_targets: Vec<_> = (0..2)
+            .map(|_| builder.add_virtual_schnorr_signature_target())
+            .collect();
+        builder.verify_schnorr_batch(&pk_targets, &msg_targets, &sig_targets);
+
+        let x = builder.add_virtual_quintic_ext_target();
+        let y = builder.add_virtual_quintic_ext_target();
+        let quotient = builder.div_or_zero_quintic_ext(x, y);
+        builder.register_quintic_ext_public_input(quotient);
+
+        let data = builder.build::<C>();
+
+        let gate_serializer = EcGFp5GateSerializer;
+        let generator_serializer = EcGFp5GeneratorSerializer::<C, D>::default();
+        let bytes = data
+            .to_bytes(&gate_serializer, &generator_serializer)
+            .unwrap();
+        let data_from_bytes =
+            CircuitData::<F, C, D>::from_bytes(&bytes, &gate_serializer, &generator_serializer)
+                .unwrap();
+        assert_eq!(data, data_from_bytes);
+
+        let mut pw = PartialWitness::new();
+        for i in 0..2 {
+            let sk = SecretKey::sample(&mut rng);
+            let msg = F::rand_vec(3);
+            pw.set_schnorr_public_key_target(pk_targets[i], sk.public_key());
+            pw.set_schnorr_signature_target(&sig_targets[i], &sk.sign(&msg));
+            pw.set_target_arr(&msg_targets[i], &msg)?;
+        }
+        pw.set_quintic_ext_target(x, GFp5::sample(&mut rng));
+        pw.set_quintic_ext_target(y, GFp5::sample(&mut rng));
+
+        let proof = data_from_bytes.prove(pw)?;
+        data.verify(proof)
+    }
+}
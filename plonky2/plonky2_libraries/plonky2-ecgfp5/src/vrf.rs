@@ -0,0 +1,179 @@
+//! Verifiable random function over EcGFp5, following the EC-VRF construction of RFC 9381 with
+//! Poseidon as the hash function.
+//!
+//! For a key `Q = sk*G` and an input `alpha` (a sequence of Goldilocks elements), the prover maps
+//! `alpha` to the point `H`, and publishes `Gamma = sk*H` together with a proof `(c, s)` that
+//! `log_G(Q) = log_H(Gamma)`: `c = H(Q || H || Gamma || k*G || k*H)` and `s = k + c*sk`.
+//! The output is `beta = H(Gamma)`, which nobody can predict without `sk` and which the key
+//! holder cannot choose. The circuit counterpart lives in `gadgets::vrf` and is re-exported here.
+
+use alloc::vec::Vec;
+use plonky2::hash::hash_types::HashOut;
+use plonky2_field::extension::quintic::QuinticExtension;
+use plonky2_field::types::{Field, PrimeField64};
+
+use crate::curve::curve::Point;
+use crate::curve::scalar_field::Scalar;
+use crate::curve::{GFp, GFp5};
+use crate::keys::define_keys;
+use crate::schnorr::hash_padded;
+
+pub use crate::gadgets::vrf::{
+    CircuitBuilderVrf, PartialWitnessVrf, VrfProofTarget, VrfPublicKeyTarget,
+};
+
+// domain separators, distinct from the ones used in `schnorr`, `pedersen`, `elgamal` and `curve`
+pub(crate) const VRF_INPUT_DOMAIN: u64 = 7;
+pub(crate) const VRF_NONCE_DOMAIN: u64 = 8;
+pub(crate) const VRF_CHALLENGE_DOMAIN: u64 = 9;
+pub(crate) const VRF_OUTPUT_DOMAIN: u64 = 10;
+
+/// Maps the input `alpha` to a curve point, bound to the public key so that outputs under
+/// different keys are unrelated.
+pub fn vrf_input_point(pk: GFp5, alpha: &[GFp]) -> Point {
+    let mut preimage = vec![GFp::from_canonical_u64(VRF_INPUT_DOMAIN)];
+    preimage.extend(pk.0);
+    preimage.extend_from_slice(alpha);
+    Point::hash_to_curve(&preimage)
+}
+
+/// Computes `c = H(Q || H || Gamma || U || V)` from the encodings of the points.
+pub fn vrf_challenge(pk: GFp5, h: GFp5, gamma: GFp5, u: GFp5, v: GFp5) -> Scalar {
+    let mut preimage = vec![GFp::from_canonical_u64(VRF_CHALLENGE_DOMAIN)];
+    for p in [pk, h, gamma, u, v] {
+        preimage.extend(p.0);
+    }
+
+    let c = hash_padded(preimage, 5);
+    Scalar::from_gfp5(QuinticExtension(c.try_into().unwrap()))
+}
+
+/// Computes the output `beta = H(Gamma)` from the encoding of `Gamma`.
+pub fn vrf_output(gamma: GFp5) -> HashOut<GFp> {
+    let mut preimage = vec![GFp::from_canonical_u64(VRF_OUTPUT_DOMAIN)];
+    preimage.extend(gamma.0);
+    HashOut::from_vec(hash_padded(preimage, 4))
+}
+
+define_keys!();
+
+#[derive(Clone, Copy, Debug)]
+pub struct Proof {
+    pub gamma: Point,
+    pub c: Scalar,
+    pub s: Scalar,
+}
+
+impl SecretKey {
+    // Deterministic nonce k = H(sk || H), as for Schnorr signatures.
+    fn nonce(&self, h: GFp5) -> Scalar {
+        let mut preimage = vec![GFp::from_canonical_u64(VRF_NONCE_DOMAIN)];
+        preimage.extend(
+            self.0
+                .encode()
+                .chunks(4)
+                .map(|c| GFp::from_canonical_u32(u32::from_le_bytes(c.try_into().unwrap()))),
+        );
+        preimage.extend(h.0);
+
+        // squeeze 640 bits so that the reduction modulo n is unbiased
+        let bytes: Vec<u8> = hash_padded(preimage, 10)
+            .into_iter()
+            .flat_map(|x| x.to_canonical_u64().to_le_bytes())
+            .collect();
+        Scalar::from_noncanonical_bytes(&bytes)
+    }
+
+    /// Evaluates the VRF on `alpha`, returning the output together with its proof.
+    pub fn prove(&self, alpha: &[GFp]) -> (HashOut<GFp>, Proof) {
+        let pk = self.public_key().encode();
+        let h = vrf_input_point(pk, alpha);
+        let gamma = h * self.0;
+
+        let k = self.nonce(h.encode());
+        let u = Point::mulgen(k);
+        let v = h * k;
+        let c = vrf_challenge(pk, h.encode(), gamma.encode(), u.encode(), v.encode());
+        let s = k + c * self.0;
+
+        (vrf_output(gamma.encode()), Proof { gamma, c, s })
+    }
+}
+
+impl PublicKey {
+    /// Returns the output for `alpha` if `proof` is valid.
+    pub fn verify(&self, alpha: &[GFp], proof: &Proof) -> Option<HashOut<GFp>> {
+        if proof.gamma.is_neutral() {
+            return None;
+        }
+
+        let h = vrf_input_point(self.encode(), alpha);
+        // U = s*G - c*Q and V = s*H - c*Gamma
+        let u = Point::mulgen(proof.s) - self.0 * proof.c;
+        let v = h * proof.s - proof.gamma * proof.c;
+        let c = vrf_challenge(
+            self.encode(),
+            h.encode(),
+            proof.gamma.encode(),
+            u.encode(),
+            v.encode(),
+        );
+
+        if c == proof.c {
+            Some(vrf_output(proof.gamma.encode()))
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use plonky2_field::types::Sample;
+    use rand::thread_rng;
+
+    use super::*;
+
+    #[test]
+    fn test_prove_verify() {
+        let mut rng = thread_rng();
+        let sk = SecretKey::sample(&mut rng);
+        let pk = sk.public_key();
+        let alpha = GFp::rand_vec(4);
+
+        let (beta, proof) = sk.prove(&alpha);
+        assert_eq!(pk.verify(&alpha, &proof), Some(beta));
+
+        let mut other_alpha = alpha.clone();
+        other_alpha[0] += GFp::ONE;
+        assert_eq!(pk.verify(&other_alpha, &proof), None);
+
+        let other_pk = SecretKey::sample(&mut rng).public_key();
+        assert_eq!(other_pk.verify(&alpha, &proof), None);
+
+        let forged = Proof {
+            s: proof.s + Scalar::ONE,
+            ..proof
+        };
+        assert_eq!(pk.verify(&alpha, &forged), None);
+
+        // a different gamma would change the output, so it must not verify
+        let forged = Proof {
+            gamma: proof.gamma + Point::GENERATOR,
+            ..proof
+        };
+        assert_eq!(pk.verify(&alpha, &forged), None);
+    }
+
+    #[test]
+    fn test_outputs_are_deterministic() {
+        let mut rng = thread_rng();
+        let sk = SecretKey::sample(&mut rng);
+        let alpha = GFp::rand_vec(4);
+
+        let (beta, _) = sk.prove(&alpha);
+        assert_eq!(sk.prove(&alpha).0, beta);
+        assert_ne!(sk.prove(&alpha[..3]).0, beta);
+        assert_ne!(SecretKey::sample(&mut rng).prove(&alpha).0, beta);
+    }
+}
@@ -0,0 +1,456 @@
+use alloc::vec;
+use alloc::vec::Vec;
+
+use plonky2::field::extension::Extendable;
+use plonky2::field::goldilocks_field::GoldilocksField;
+use plonky2::field::types::Field64;
+use plonky2::hash::hash_types::RichField;
+use plonky2::iop::target::{BoolTarget, Target};
+use plonky2::plonk::circuit_builder::CircuitBuilder;
+
+use crate::gadgets::arithmetic_u32::{CircuitBuilderU32, U32Target};
+use crate::gadgets::multiple_comparison::list_le_u32_circuit;
+
+/// `U32AddManyGate` takes at most 16 addends.
+const MAX_ADDENDS: usize = 16;
+
+/// An unsigned integer of `N` 32-bit limbs, least significant first. Like a `U32Target`, each
+/// limb is assumed to be range-checked.
+#[derive(Clone, Copy, Debug)]
+pub struct UIntTarget<const N: usize> {
+    pub limbs: [U32Target; N],
+}
+
+pub type U64Target = UIntTarget<2>;
+
+impl<const N: usize> UIntTarget<N> {
+    pub const BITS: usize = 32 * N;
+}
+
+impl<const N: usize> Default for UIntTarget<N> {
+    fn default() -> Self {
+        Self {
+            limbs: [U32Target::default(); N],
+        }
+    }
+}
+
+pub trait CircuitBuilderUInt<F: RichField + Extendable<D>, const D: usize> {
+    /// Returns a `UIntTarget` whose limbs are not range-checked, like `add_virtual_u32_target`.
+    fn add_virtual_uint_target<const N: usize>(&mut self) -> UIntTarget<N>;
+
+    /// Returns a `UIntTarget` for the value with the given limbs, least significant first.
+    fn constant_uint<const N: usize>(&mut self, limbs: [u32; N]) -> UIntTarget<N>;
+
+    fn constant_u64(&mut self, c: u64) -> U64Target;
+
+    fn zero_uint<const N: usize>(&mut self) -> UIntTarget<N>;
+
+    fn connect_uint<const N: usize>(&mut self, x: UIntTarget<N>, y: UIntTarget<N>);
+
+    /// Returns the canonical representation of a Goldilocks element. Unlike `split_low_high`,
+    /// this rules out the decomposition of `x + p`, so the result is unique.
+    fn split_canonical_u64(&mut self, x: Target) -> U64Target;
+
+    /// Returns `x + y` modulo `2^(32 N)` and the carry, which is 0 or 1.
+    fn add_uint<const N: usize>(
+        &mut self,
+        x: UIntTarget<N>,
+        y: UIntTarget<N>,
+    ) -> (UIntTarget<N>, U32Target);
+
+    /// Returns `x - y` modulo `2^(32 N)` and the borrow, which is 1 iff `y > x`.
+    fn sub_uint<const N: usize>(
+        &mut self,
+        x: UIntTarget<N>,
+        y: UIntTarget<N>,
+    ) -> (UIntTarget<N>, U32Target);
+
+    /// Returns the low and high halves of the full product `x * y`.
+    fn mul_uint<const N: usize>(
+        &mut self,
+        x: UIntTarget<N>,
+        y: UIntTarget<N>,
+    ) -> (UIntTarget<N>, UIntTarget<N>);
+
+    fn is_equal_uint<const N: usize>(&mut self, x: UIntTarget<N>, y: UIntTarget<N>) -> BoolTarget;
+
+    /// Returns whether `x <= y`.
+    fn le_uint<const N: usize>(&mut self, x: UIntTarget<N>, y: UIntTarget<N>) -> BoolTarget;
+
+    /// Returns whether `x < y`.
+    fn lt_uint<const N: usize>(&mut self, x: UIntTarget<N>, y: UIntTarget<N>) -> BoolTarget;
+
+    /// Returns `x << n`, truncated to `32 N` bits.
+    fn shl_uint<const N: usize>(&mut self, x: UIntTarget<N>, n: usize) -> UIntTarget<N>;
+
+    fn shr_uint<const N: usize>(&mut self, x: UIntTarget<N>, n: usize) -> UIntTarget<N>;
+
+    /// Returns the `32 N` bits of `x`, least significant first.
+    fn split_le_uint<const N: usize>(&mut self, x: UIntTarget<N>) -> Vec<BoolTarget>;
+
+    /// Returns the integer with the given bits, least significant first, of which there are at
+    /// most `32 N`.
+    fn le_sum_uint<const N: usize>(&mut self, bits: &[BoolTarget]) -> UIntTarget<N>;
+}
+
+impl<F: RichField + Extendable<D>, const D: usize> CircuitBuilderUInt<F, D>
+    for CircuitBuilder<F, D>
+{
+    fn add_virtual_uint_target<const N: usize>(&mut self) -> UIntTarget<N> {
+        UIntTarget {
+            limbs: core::array::from_fn(|_| self.add_virtual_u32_target()),
+        }
+    }
+
+    fn constant_uint<const N: usize>(&mut self, limbs: [u32; N]) -> UIntTarget<N> {
+        UIntTarget {
+            limbs: limbs.map(|l| self.constant_u32(l)),
+        }
+    }
+
+    fn constant_u64(&mut self, c: u64) -> U64Target {
+        self.constant_uint([c as u32, (c >> 32) as u32])
+    }
+
+    fn zero_uint<const N: usize>(&mut self) -> UIntTarget<N> {
+        self.constant_uint([0; N])
+    }
+
+    fn connect_uint<const N: usize>(&mut self, x: UIntTarget<N>, y: UIntTarget<N>) {
+        for (x_limb, y_limb) in x.limbs.into_iter().zip(y.limbs) {
+            self.connect_u32(x_limb, y_limb);
+        }
+    }
+
+    fn split_canonical_u64(&mut self, x: Target) -> U64Target {
+        assert_eq!(
+            F::ORDER,
+            GoldilocksField::ORDER,
+            "canonical splits are only defined over Goldilocks"
+        );
+
+        let (low, high) = self.split_low_high(x, 32, 64);
+        // The only other decompositions which fit in 64 bits are those of x + p, which have all high
+        // bits set and a nonzero low word since p = 2^64 - 2^32 + 1.
+        let max = self.constant(F::from_canonical_u32(u32::MAX));
+        let high_is_max = self.is_equal(high, max);
+        let non_canonical = self.mul(high_is_max.target, low);
+        self.assert_zero(non_canonical);
+
+        UIntTarget {
+            limbs: [U32Target(low), U32Target(high)],
+        }
+    }
+
+    fn add_uint<const N: usize>(
+        &mut self,
+        x: UIntTarget<N>,
+        y: UIntTarget<N>,
+    ) -> (UIntTarget<N>, U32Target) {
+        let mut carry = self.zero_u32();
+        let limbs = core::array::from_fn(|i| {
+            let (limb, new_carry) = self.add_many_u32(&[carry, x.limbs[i], y.limbs[i]]);
+            carry = new_carry;
+            limb
+        });
+
+        (UIntTarget { limbs }, carry)
+    }
+
+    fn sub_uint<const N: usize>(
+        &mut self,
+        x: UIntTarget<N>,
+        y: UIntTarget<N>,
+    ) -> (UIntTarget<N>, U32Target) {
+        let mut borrow = self.zero_u32();
+        let limbs = core::array::from_fn(|i| {
+            let (limb, new_borrow) = self.sub_u32(x.limbs[i], y.limbs[i], borrow);
+            borrow = new_borrow;
+            limb
+        });
+
+        (UIntTarget { limbs }, borrow)
+    }
+
+    fn mul_uint<const N: usize>(
+        &mut self,
+        x: UIntTarget<N>,
+        y: UIntTarget<N>,
+    ) -> (UIntTarget<N>, UIntTarget<N>) {
+        let mut to_add = vec![vec![]; 2 * N];
+        for i in 0..N {
+            for j in 0..N {
+                let (product, carry) = self.mul_u32(x.limbs[i], y.limbs[j]);
+                to_add[i + j].push(product);
+                to_add[i + j + 1].push(carry);
+            }
+        }
+
+        // Long columns are summed in chunks whose carries move to the next column. The top
+        // column never needs this, as it only holds a single carry.
+        for k in 0..2 * N {
+            while to_add[k].len() > MAX_ADDENDS {
+                let chunk: Vec<_> = to_add[k].drain(..MAX_ADDENDS).collect();
+                let (sum, carry) = self.add_many_u32(&chunk);
+                to_add[k].push(sum);
+                to_add[k + 1].push(carry);
+            }
+        }
+
+        // The product has at most 2N limbs, so the final carry is zero.
+        let mut limbs = Vec::with_capacity(2 * N);
+        let mut carry = self.zero_u32();
+        for summands in &to_add {
+            let (limb, new_carry) = self.add_u32s_with_carry(summands, carry);
+            limbs.push(limb);
+            carry = new_carry;
+        }
+
+        (
+            UIntTarget {
+                limbs: core::array::from_fn(|i| limbs[i]),
+            },
+            UIntTarget {
+                limbs: core::array::from_fn(|i| limbs[N + i]),
+            },
+        )
+    }
+
+    fn is_equal_uint<const N: usize>(&mut self, x: UIntTarget<N>, y: UIntTarget<N>) -> BoolTarget {
+        let mut result = self._true();
+        for (x_limb, y_limb) in x.limbs.into_iter().zip(y.limbs) {
+            let limbs_equal = self.is_equal(x_limb.0, y_limb.0);
+            result = self.and(result, limbs_equal);
+        }
+        result
+    }
+
+    fn le_uint<const N: usize>(&mut self, x: UIntTarget<N>, y: UIntTarget<N>) -> BoolTarget {
+        list_le_u32_circuit(self, x.limbs.to_vec(), y.limbs.to_vec())
+    }
+
+    fn lt_uint<const N: usize>(&mut self, x: UIntTarget<N>, y: UIntTarget<N>) -> BoolTarget {
+        let y_le_x = self.le_uint(y, x);
+        self.not(y_le_x)
+    }
+
+    fn shl_uint<const N: usize>(&mut self, x: UIntTarget<N>, n: usize) -> UIntTarget<N> {
+        let (limb_shift, bit_shift) = (n / 32, n % 32);
+        let zero = self.zero_u32();
+        let shifted: [U32Target; N] = core::array::from_fn(|i| {
+            if i >= limb_shift {
+                x.limbs[i - limb_shift]
+            } else {
+                zero
+            }
+        });
+        if bit_shift == 0 {
+            return UIntTarget { limbs: shifted };
+        }
+
+        // Each limb keeps its low bits, moved up, and passes its high bits on to the next one.
+        let factor = self.constant_u32(1 << bit_shift);
+        let parts: Vec<_> = shifted.iter().map(|&l| self.mul_u32(l, factor)).collect();
+        let limbs = core::array::from_fn(|i| {
+            if i == 0 {
+                parts[0].0
+            } else {
+                U32Target(self.add(parts[i].0 .0, parts[i - 1].1 .0))
+            }
+        });
+
+        UIntTarget { limbs }
+    }
+
+    fn shr_uint<const N: usize>(&mut self, x: UIntTarget<N>, n: usize) -> UIntTarget<N> {
+        let (limb_shift, bit_shift) = (n / 32, n % 32);
+        let zero = self.zero_u32();
+        let shifted: [U32Target; N] = core::array::from_fn(|i| {
+            if i + limb_shift < N {
+                x.limbs[i + limb_shift]
+            } else {
+                zero
+            }
+        });
+        if bit_shift == 0 {
+            return UIntTarget { limbs: shifted };
+        }
+
+        // Multiplying by 2^(32 - bit_shift) leaves the bits that stay in the high half, and
+        // those that move down to the previous limb in the low half.
+        let factor = self.constant_u32(1 << (32 - bit_shift));
+        let parts: Vec<_> = shifted.iter().map(|&l| self.mul_u32(l, factor)).collect();
+        let limbs = core::array::from_fn(|i| {
+            if i + 1 == N {
+                parts[i].1
+            } else {
+                U32Target(self.add(parts[i].1 .0, parts[i + 1].0 .0))
+            }
+        });
+
+        UIntTarget { limbs }
+    }
+
+    fn split_le_uint<const N: usize>(&mut self, x: UIntTarget<N>) -> Vec<BoolTarget> {
+        x.limbs
+            .iter()
+            .flat_map(|l| self.split_le(l.0, 32))
+            .collect()
+    }
+
+    fn le_sum_uint<const N: usize>(&mut self, bits: &[BoolTarget]) -> UIntTarget<N> {
+        assert!(
+            bits.len() <= UIntTarget::<N>::BITS,
+            "too many bits for {} limbs",
+            N
+        );
+
+        let mut limb_bits = bits.chunks(32);
+        let limbs = core::array::from_fn(|_| match limb_bits.next() {
+            Some(chunk) => U32Target(self.le_sum(chunk.iter())),
+            None => self.zero_u32(),
+        });
+
+        UIntTarget { limbs }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+    use plonky2::field::types::{Field, PrimeField64, Sample};
+    use plonky2::iop::witness::{PartialWitness, WitnessWrite};
+    use plonky2::plonk::circuit_data::CircuitConfig;
+    use plonky2::plonk::config::{GenericConfig, PoseidonGoldilocksConfig};
+    use rand::rngs::OsRng;
+    use rand::Rng;
+
+    use super::*;
+    use crate::witness::WitnessUInt;
+
+    #[test]
+    fn test_u64_arithmetic() -> Result<()> {
+        const D: usize = 2;
+        type C = PoseidonGoldilocksConfig;
+        type F = <C as GenericConfig<D>>::F;
+
+        let config = CircuitConfig::standard_recursion_config();
+
+        let mut pw = PartialWitness::new();
+        let mut builder = CircuitBuilder::<F, D>::new(config);
+
+        let mut rng = OsRng;
+        let x_value: u64 = rng.gen();
+        let y_value: u64 = rng.gen();
+        let n = rng.gen_range(1..64);
+
+        let x = builder.add_virtual_uint_target();
+        let y = builder.add_virtual_uint_target();
+        pw.set_u64_target(x, x_value);
+        pw.set_u64_target(y, y_value);
+
+        let (sum, carry) = builder.add_uint(x, y);
+        let (diff, borrow) = builder.sub_uint(x, y);
+        let (low, high) = builder.mul_uint(x, y);
+        let (expected_sum, expected_carry) = x_value.overflowing_add(y_value);
+        let (expected_diff, expected_borrow) = x_value.overflowing_sub(y_value);
+        let expected_product = x_value as u128 * y_value as u128;
+        let results = [
+            (sum, expected_sum),
+            (diff, expected_diff),
+            (low, expected_product as u64),
+            (high, (expected_product >> 64) as u64),
+            (builder.shl_uint(x, n), x_value << n),
+            (builder.shr_uint(x, n), x_value >> n),
+            (builder.shl_uint(x, 32), x_value << 32),
+            (builder.shr_uint(x, 32), x_value >> 32),
+        ];
+        for (result, expected) in results {
+            let expected = builder.constant_u64(expected);
+            builder.connect_uint(result, expected);
+        }
+
+        for (result, expected) in [(carry, expected_carry), (borrow, expected_borrow)] {
+            let expected = builder.constant_u32(expected as u32);
+            builder.connect_u32(result, expected);
+        }
+
+        let flags = [
+            (builder.is_equal_uint(x, y), x_value == y_value),
+            (builder.is_equal_uint(x, x), true),
+            (builder.le_uint(x, y), x_value <= y_value),
+            (builder.lt_uint(x, y), x_value < y_value),
+            (builder.lt_uint(x, x), false),
+        ];
+        for (result, expected) in flags {
+            let expected = builder.constant_bool(expected);
+            builder.connect(result.target, expected.target);
+        }
+
+        let bits = builder.split_le_uint(x);
+        let recombined = builder.le_sum_uint(&bits);
+        builder.connect_uint(recombined, x);
+
+        let data = builder.build::<C>();
+        let proof = data.prove(pw).unwrap();
+        data.verify(proof)
+    }
+
+    #[test]
+    fn test_split_canonical_u64() -> Result<()> {
+        const D: usize = 2;
+        type C = PoseidonGoldilocksConfig;
+        type F = <C as GenericConfig<D>>::F;
+
+        let config = CircuitConfig::standard_recursion_config();
+
+        let mut pw = PartialWitness::new();
+        let mut builder = CircuitBuilder::<F, D>::new(config);
+
+        // p - 1 is the largest element whose high word is all ones
+        for value in [F::NEG_ONE, F::rand()] {
+            let x = builder.add_virtual_target();
+            pw.set_target(x, value)?;
+            let split = builder.split_canonical_u64(x);
+            let expected = builder.constant_u64(value.to_canonical_u64());
+            builder.connect_uint(split, expected);
+        }
+
+        let data = builder.build::<C>();
+        let proof = data.prove(pw).unwrap();
+        data.verify(proof)
+    }
+
+    #[test]
+    fn test_uint_mul_wide() -> Result<()> {
+        const D: usize = 2;
+        type C = PoseidonGoldilocksConfig;
+        type F = <C as GenericConfig<D>>::F;
+        const N: usize = 9;
+
+        let config = CircuitConfig::standard_recursion_config();
+
+        let mut pw = PartialWitness::new();
+        let mut builder = CircuitBuilder::<F, D>::new(config);
+
+        // All-ones operands fill the middle columns with more than 16 limbs.
+        let x = builder.add_virtual_uint_target::<N>();
+        let (low, high) = builder.mul_uint(x, x);
+        pw.set_uint_target(&x, [u32::MAX; N]);
+
+        // (2^k - 1)^2 = 2^(2k) - 2^(k + 1) + 1
+        let mut expected_low = [0; N];
+        expected_low[0] = 1;
+        let mut expected_high = [u32::MAX; N];
+        expected_high[0] = u32::MAX - 1;
+        let expected_low = builder.constant_uint(expected_low);
+        let expected_high = builder.constant_uint(expected_high);
+        builder.connect_uint(low, expected_low);
+        builder.connect_uint(high, expected_high);
+
+        let data = builder.build::<C>();
+        let proof = data.prove(pw).unwrap();
+        data.verify(proof)
+    }
+}
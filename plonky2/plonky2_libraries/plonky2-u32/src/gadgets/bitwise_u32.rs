@@ -0,0 +1,174 @@
+use plonky2::field::extension::Extendable;
+use plonky2::hash::hash_types::RichField;
+use plonky2::iop::target::Target;
+use plonky2::plonk::circuit_builder::CircuitBuilder;
+
+use crate::gadgets::arithmetic_u32::{CircuitBuilderU32, U32Target};
+use crate::gates::bitwise_u32::{U32BitwiseGate, U32BitwiseOp};
+
+/// Bitwise operations on `U32Target`s. `and_u32` and `xor_u32` use a `U32BitwiseGate`, while
+/// rotations and shifts multiply by a power of two with a `U32ArithmeticGate`, whose two output
+/// halves are the bits shifted out and the bits that stay.
+pub trait CircuitBuilderBitwiseU32<F: RichField + Extendable<D>, const D: usize> {
+    fn and_u32(&mut self, x: U32Target, y: U32Target) -> U32Target;
+
+    fn xor_u32(&mut self, x: U32Target, y: U32Target) -> U32Target;
+
+    fn not_u32(&mut self, x: U32Target) -> U32Target;
+
+    fn rotate_left_u32(&mut self, x: U32Target, n: usize) -> U32Target;
+
+    fn rotate_right_u32(&mut self, x: U32Target, n: usize) -> U32Target;
+
+    /// Returns `x << n`, truncated to 32 bits.
+    fn shl_u32(&mut self, x: U32Target, n: usize) -> U32Target;
+
+    fn shr_u32(&mut self, x: U32Target, n: usize) -> U32Target;
+}
+
+impl<F: RichField + Extendable<D>, const D: usize> CircuitBuilderBitwiseU32<F, D>
+    for CircuitBuilder<F, D>
+{
+    fn and_u32(&mut self, x: U32Target, y: U32Target) -> U32Target {
+        bitwise_u32(self, U32BitwiseOp::And, x, y)
+    }
+
+    fn xor_u32(&mut self, x: U32Target, y: U32Target) -> U32Target {
+        bitwise_u32(self, U32BitwiseOp::Xor, x, y)
+    }
+
+    fn not_u32(&mut self, x: U32Target) -> U32Target {
+        let max = self.constant_u32(u32::MAX);
+        U32Target(self.sub(max.0, x.0))
+    }
+
+    fn rotate_left_u32(&mut self, x: U32Target, n: usize) -> U32Target {
+        let n = n % 32;
+        if n == 0 {
+            return x;
+        }
+
+        // The low half holds the bits that stay, moved up, and the high half those that wrap
+        // around, so the two never overlap.
+        let factor = self.constant_u32(1 << n);
+        let (low, high) = self.mul_u32(x, factor);
+        U32Target(self.add(low.0, high.0))
+    }
+
+    fn rotate_right_u32(&mut self, x: U32Target, n: usize) -> U32Target {
+        self.rotate_left_u32(x, 32 - n % 32)
+    }
+
+    fn shl_u32(&mut self, x: U32Target, n: usize) -> U32Target {
+        assert!(n < 32, "shift amount must be less than 32");
+        let factor = self.constant_u32(1 << n);
+        self.mul_u32(x, factor).0
+    }
+
+    fn shr_u32(&mut self, x: U32Target, n: usize) -> U32Target {
+        assert!(n < 32, "shift amount must be less than 32");
+        if n == 0 {
+            return x;
+        }
+
+        let factor = self.constant_u32(1 << (32 - n));
+        self.mul_u32(x, factor).1
+    }
+}
+
+fn bitwise_u32<F: RichField + Extendable<D>, const D: usize>(
+    builder: &mut CircuitBuilder<F, D>,
+    op: U32BitwiseOp,
+    x: U32Target,
+    y: U32Target,
+) -> U32Target {
+    let x_const = builder.target_as_constant(x.0);
+    let y_const = builder.target_as_constant(y.0);
+    if let (Some(x_const), Some(y_const)) = (x_const, y_const) {
+        let (x_const, y_const) = (
+            x_const.to_canonical_u64() as u32,
+            y_const.to_canonical_u64() as u32,
+        );
+        let result = match op {
+            U32BitwiseOp::And => x_const & y_const,
+            U32BitwiseOp::Xor => x_const ^ y_const,
+        };
+        return builder.constant_u32(result);
+    }
+
+    // A constant zero operand, such as a freshly initialized hash state, decides the result.
+    match (
+        op,
+        x_const.is_some_and(|c| c.is_zero()),
+        y_const.is_some_and(|c| c.is_zero()),
+    ) {
+        (U32BitwiseOp::And, true, _) | (U32BitwiseOp::And, _, true) => return builder.zero_u32(),
+        (U32BitwiseOp::Xor, true, _) => return y,
+        (U32BitwiseOp::Xor, _, true) => return x,
+        _ => {}
+    }
+
+    let gate = U32BitwiseGate::<F, D>::new_from_config(&builder.config, op);
+    let (row, copy) = builder.find_slot(gate, &[], &[]);
+
+    builder.connect(Target::wire(row, gate.wire_ith_input_x(copy)), x.0);
+    builder.connect(Target::wire(row, gate.wire_ith_input_y(copy)), y.0);
+
+    U32Target(Target::wire(row, gate.wire_ith_output(copy)))
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+    use plonky2::iop::witness::PartialWitness;
+    use plonky2::plonk::circuit_data::CircuitConfig;
+    use plonky2::plonk::config::{GenericConfig, PoseidonGoldilocksConfig};
+    use rand::rngs::OsRng;
+    use rand::Rng;
+
+    use super::*;
+    use crate::witness::WitnessU32;
+
+    #[test]
+    fn test_bitwise_u32() -> Result<()> {
+        const D: usize = 2;
+        type C = PoseidonGoldilocksConfig;
+        type F = <C as GenericConfig<D>>::F;
+
+        let config = CircuitConfig::standard_recursion_config();
+
+        let mut pw = PartialWitness::new();
+        let mut builder = CircuitBuilder::<F, D>::new(config);
+
+        let mut rng = OsRng;
+        let x_value: u32 = rng.gen();
+        let y_value: u32 = rng.gen();
+        let n = rng.gen_range(1..32);
+
+        let x = builder.add_virtual_u32_target();
+        let y = builder.add_virtual_u32_target();
+        pw.set_u32_target(x, x_value);
+        pw.set_u32_target(y, y_value);
+
+        let results = [
+            (builder.and_u32(x, y), x_value & y_value),
+            (builder.xor_u32(x, y), x_value ^ y_value),
+            (builder.not_u32(x), !x_value),
+            (builder.rotate_left_u32(x, n), x_value.rotate_left(n as u32)),
+            (
+                builder.rotate_right_u32(x, n),
+                x_value.rotate_right(n as u32),
+            ),
+            (builder.shl_u32(x, n), x_value << n),
+            (builder.shr_u32(x, n), x_value >> n),
+        ];
+        for (result, expected) in results {
+            let expected = builder.constant_u32(expected);
+            builder.connect_u32(result, expected);
+        }
+
+        let data = builder.build::<C>();
+        let proof = data.prove(pw).unwrap();
+        data.verify(proof)
+    }
+}
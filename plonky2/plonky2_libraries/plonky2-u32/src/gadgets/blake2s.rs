@@ -0,0 +1,317 @@
+use alloc::vec::Vec;
+
+use plonky2::field::extension::Extendable;
+use plonky2::hash::hash_types::RichField;
+use plonky2::plonk::circuit_builder::CircuitBuilder;
+
+use crate::gadgets::arithmetic_u32::{CircuitBuilderU32, U32Target};
+use crate::gadgets::bitwise_u32::CircuitBuilderBitwiseU32;
+
+/// The BLAKE2s initialization vector, which BLAKE3 shares.
+pub(crate) const BLAKE2S_IV: [u32; 8] = [
+    0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c, 0x1f83d9ab, 0x5be0cd19,
+];
+
+const SIGMA: [[usize; 16]; 10] = [
+    [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15],
+    [14, 10, 4, 8, 9, 15, 13, 6, 1, 12, 0, 2, 11, 7, 5, 3],
+    [11, 8, 12, 0, 5, 2, 15, 13, 10, 14, 3, 6, 7, 1, 9, 4],
+    [7, 9, 3, 1, 13, 12, 11, 14, 2, 6, 5, 10, 4, 0, 15, 8],
+    [9, 0, 5, 7, 2, 4, 10, 15, 14, 1, 11, 12, 6, 8, 3, 13],
+    [2, 12, 6, 10, 0, 11, 8, 3, 4, 13, 7, 5, 15, 14, 1, 9],
+    [12, 5, 1, 15, 14, 13, 4, 10, 0, 7, 6, 3, 9, 2, 8, 11],
+    [13, 11, 7, 14, 12, 1, 3, 9, 5, 0, 15, 4, 8, 6, 2, 10],
+    [6, 15, 14, 9, 11, 3, 0, 8, 12, 2, 13, 7, 1, 4, 10, 5],
+    [10, 2, 8, 4, 7, 6, 1, 5, 15, 11, 9, 14, 3, 12, 13, 0],
+];
+
+/// The number of 32-bit words in a BLAKE2s or BLAKE3 block.
+pub(crate) const BLAKE_BLOCK_WORDS: usize = 16;
+
+pub trait CircuitBuilderBlake2s<F: RichField + Extendable<D>, const D: usize> {
+    /// Applies the BLAKE2s compression function to `h` and a 16-word block, where `counter` is
+    /// the number of bytes hashed so far, including this block.
+    fn blake2s_compress(
+        &mut self,
+        h: &[U32Target; 8],
+        block: &[U32Target],
+        counter: u64,
+        last: bool,
+    ) -> [U32Target; 8];
+
+    /// Computes BLAKE2s-256 of a message of `num_bytes` bytes, given as little-endian words. The
+    /// unused high bytes of the last word must be zero. Returns the digest as little-endian words.
+    fn blake2s(&mut self, msg: &[U32Target], num_bytes: usize) -> [U32Target; 8];
+
+    /// Computes keyed BLAKE2s-256, the BLAKE2s MAC, under a key of `key_bytes` bytes, at most 32,
+    /// given as little-endian words like the message.
+    fn blake2s_keyed(
+        &mut self,
+        key: &[U32Target],
+        key_bytes: usize,
+        msg: &[U32Target],
+        num_bytes: usize,
+    ) -> [U32Target; 8];
+}
+
+impl<F: RichField + Extendable<D>, const D: usize> CircuitBuilderBlake2s<F, D>
+    for CircuitBuilder<F, D>
+{
+    fn blake2s_compress(
+        &mut self,
+        h: &[U32Target; 8],
+        block: &[U32Target],
+        counter: u64,
+        last: bool,
+    ) -> [U32Target; 8] {
+        assert_eq!(
+            block.len(),
+            BLAKE_BLOCK_WORDS,
+            "BLAKE2s blocks have 16 words"
+        );
+
+        // The counter and the finalization flag are known when building the circuit, so the
+        // second half of the state stays constant.
+        let mut v_init = [0; 16];
+        v_init[8..].copy_from_slice(&BLAKE2S_IV);
+        v_init[12] ^= counter as u32;
+        v_init[13] ^= (counter >> 32) as u32;
+        if last {
+            v_init[14] = !v_init[14];
+        }
+        let mut v: [U32Target; 16] = core::array::from_fn(|i| {
+            if i < 8 {
+                h[i]
+            } else {
+                self.constant_u32(v_init[i])
+            }
+        });
+
+        for s in SIGMA.iter() {
+            let m: [U32Target; 16] = core::array::from_fn(|i| block[s[i]]);
+            blake_round(self, &mut v, &m);
+        }
+
+        core::array::from_fn(|i| {
+            let h_xor_v = self.xor_u32(h[i], v[i]);
+            self.xor_u32(h_xor_v, v[i + 8])
+        })
+    }
+
+    fn blake2s(&mut self, msg: &[U32Target], num_bytes: usize) -> [U32Target; 8] {
+        blake2s_with_key_block(self, None, 0, msg, num_bytes)
+    }
+
+    fn blake2s_keyed(
+        &mut self,
+        key: &[U32Target],
+        key_bytes: usize,
+        msg: &[U32Target],
+        num_bytes: usize,
+    ) -> [U32Target; 8] {
+        assert!(
+            0 < key_bytes && key_bytes <= 32,
+            "BLAKE2s keys have 1 to 32 bytes"
+        );
+        assert_eq!(
+            key.len(),
+            key_bytes.div_ceil(4),
+            "BLAKE2s key must have one word per four bytes"
+        );
+        assert_zero_padding(self, key, key_bytes);
+
+        let mut key_block = key.to_vec();
+        key_block.resize(BLAKE_BLOCK_WORDS, self.zero_u32());
+        blake2s_with_key_block(self, Some(key_block), key_bytes, msg, num_bytes)
+    }
+}
+
+fn blake2s_with_key_block<F: RichField + Extendable<D>, const D: usize>(
+    builder: &mut CircuitBuilder<F, D>,
+    key_block: Option<Vec<U32Target>>,
+    key_bytes: usize,
+    msg: &[U32Target],
+    num_bytes: usize,
+) -> [U32Target; 8] {
+    assert_eq!(
+        msg.len(),
+        num_bytes.div_ceil(4),
+        "BLAKE2s input must have one word per four bytes"
+    );
+    assert_zero_padding(builder, msg, num_bytes);
+
+    // The parameter block sets a 32-byte digest, the key length and sequential mode.
+    let mut h_init = BLAKE2S_IV;
+    h_init[0] ^= 0x01010000 ^ ((key_bytes as u32) << 8) ^ 32;
+    let mut h = h_init.map(|w| builder.constant_u32(w));
+
+    // The key block counts as 64 bytes of input, and an empty unkeyed message as one zero block.
+    let mut blocks: Vec<(Vec<U32Target>, u64)> = Vec::new();
+    if let Some(key_block) = key_block {
+        blocks.push((key_block, 64));
+    }
+    let offset = 64 * blocks.len() as u64;
+    for (i, chunk) in msg.chunks(BLAKE_BLOCK_WORDS).enumerate() {
+        let mut block = chunk.to_vec();
+        block.resize(BLAKE_BLOCK_WORDS, builder.zero_u32());
+        let counter = offset + (num_bytes as u64).min(64 * (i as u64 + 1));
+        blocks.push((block, counter));
+    }
+    if blocks.is_empty() {
+        blocks.push((Vec::from([builder.zero_u32(); BLAKE_BLOCK_WORDS]), 0));
+    }
+
+    let num_blocks = blocks.len();
+    for (i, (block, counter)) in blocks.into_iter().enumerate() {
+        h = builder.blake2s_compress(&h, &block, counter, i + 1 == num_blocks);
+    }
+    h
+}
+
+/// Asserts that the bytes of `words` past the first `num_bytes` are zero.
+pub(crate) fn assert_zero_padding<F: RichField + Extendable<D>, const D: usize>(
+    builder: &mut CircuitBuilder<F, D>,
+    words: &[U32Target],
+    num_bytes: usize,
+) {
+    let partial_bytes = num_bytes % 4;
+    if partial_bytes != 0 {
+        let unused = builder.shr_u32(*words.last().unwrap(), 8 * partial_bytes);
+        builder.assert_zero_u32(unused);
+    }
+}
+
+/// One round of the BLAKE2s and BLAKE3 compression functions, given the permuted message words.
+pub(crate) fn blake_round<F: RichField + Extendable<D>, const D: usize>(
+    builder: &mut CircuitBuilder<F, D>,
+    v: &mut [U32Target; 16],
+    m: &[U32Target; 16],
+) {
+    // Columns
+    blake_g(builder, v, [0, 4, 8, 12], m[0], m[1]);
+    blake_g(builder, v, [1, 5, 9, 13], m[2], m[3]);
+    blake_g(builder, v, [2, 6, 10, 14], m[4], m[5]);
+    blake_g(builder, v, [3, 7, 11, 15], m[6], m[7]);
+    // Diagonals
+    blake_g(builder, v, [0, 5, 10, 15], m[8], m[9]);
+    blake_g(builder, v, [1, 6, 11, 12], m[10], m[11]);
+    blake_g(builder, v, [2, 7, 8, 13], m[12], m[13]);
+    blake_g(builder, v, [3, 4, 9, 14], m[14], m[15]);
+}
+
+fn blake_g<F: RichField + Extendable<D>, const D: usize>(
+    builder: &mut CircuitBuilder<F, D>,
+    v: &mut [U32Target; 16],
+    [a, b, c, d]: [usize; 4],
+    x: U32Target,
+    y: U32Target,
+) {
+    v[a] = builder.add_many_u32(&[v[a], v[b], x]).0;
+    let d_xor_a = builder.xor_u32(v[d], v[a]);
+    v[d] = builder.rotate_right_u32(d_xor_a, 16);
+    v[c] = builder.add_u32(v[c], v[d]).0;
+    let b_xor_c = builder.xor_u32(v[b], v[c]);
+    v[b] = builder.rotate_right_u32(b_xor_c, 12);
+    v[a] = builder.add_many_u32(&[v[a], v[b], y]).0;
+    let d_xor_a = builder.xor_u32(v[d], v[a]);
+    v[d] = builder.rotate_right_u32(d_xor_a, 8);
+    v[c] = builder.add_u32(v[c], v[d]).0;
+    let b_xor_c = builder.xor_u32(v[b], v[c]);
+    v[b] = builder.rotate_right_u32(b_xor_c, 7);
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use anyhow::Result;
+    use blake2::digest::{KeyInit, Mac};
+    use blake2::{Blake2s256, Blake2sMac256, Digest};
+    use plonky2::iop::witness::PartialWitness;
+    use plonky2::plonk::circuit_data::CircuitConfig;
+    use plonky2::plonk::config::{GenericConfig, PoseidonGoldilocksConfig};
+
+    use super::*;
+    use crate::witness::WitnessU32;
+
+    pub(crate) fn bytes_to_le_words(bytes: &[u8]) -> Vec<u32> {
+        bytes
+            .chunks(4)
+            .map(|chunk| {
+                let mut word = [0u8; 4];
+                word[..chunk.len()].copy_from_slice(chunk);
+                u32::from_le_bytes(word)
+            })
+            .collect()
+    }
+
+    fn test_blake2s_with(key: Option<&[u8]>, msg: &[u8]) -> Result<()> {
+        const D: usize = 2;
+        type C = PoseidonGoldilocksConfig;
+        type F = <C as GenericConfig<D>>::F;
+
+        let config = CircuitConfig::standard_recursion_config();
+
+        let mut pw = PartialWitness::new();
+        let mut builder = CircuitBuilder::<F, D>::new(config);
+
+        let words = bytes_to_le_words(msg);
+        let msg_targets = builder.add_virtual_u32_targets(words.len());
+        for (&t, &w) in msg_targets.iter().zip(words.iter()) {
+            pw.set_u32_target(t, w);
+        }
+
+        let (digest, expected) = match key {
+            None => (
+                builder.blake2s(&msg_targets, msg.len()),
+                Blake2s256::digest(msg).to_vec(),
+            ),
+            Some(key) => {
+                let key_words = bytes_to_le_words(key);
+                let key_targets = builder.add_virtual_u32_targets(key_words.len());
+                for (&t, &w) in key_targets.iter().zip(key_words.iter()) {
+                    pw.set_u32_target(t, w);
+                }
+                let mut mac = <Blake2sMac256 as KeyInit>::new_from_slice(key).unwrap();
+                mac.update(msg);
+                (
+                    builder.blake2s_keyed(&key_targets, key.len(), &msg_targets, msg.len()),
+                    mac.finalize().into_bytes().to_vec(),
+                )
+            }
+        };
+
+        for (&d, e) in digest.iter().zip(bytes_to_le_words(&expected)) {
+            let e = builder.constant_u32(e);
+            builder.connect_u32(d, e);
+        }
+
+        let data = builder.build::<C>();
+        let proof = data.prove(pw).unwrap();
+
+        data.verify(proof)
+    }
+
+    #[test]
+    fn test_blake2s_empty() -> Result<()> {
+        test_blake2s_with(None, b"")
+    }
+
+    #[test]
+    fn test_blake2s_abc() -> Result<()> {
+        test_blake2s_with(None, b"abc")
+    }
+
+    #[test]
+    fn test_blake2s_two_blocks() -> Result<()> {
+        test_blake2s_with(None, &[0x5a; 65])
+    }
+
+    #[test]
+    fn test_blake2s_keyed() -> Result<()> {
+        test_blake2s_with(Some(&[0x0b; 17]), b"attestation response")
+    }
+
+    #[test]
+    fn test_blake2s_keyed_empty() -> Result<()> {
+        test_blake2s_with(Some(&[0x0b; 32]), b"")
+    }
+}
@@ -0,0 +1,302 @@
+use alloc::vec::Vec;
+
+use plonky2::field::extension::Extendable;
+use plonky2::hash::hash_types::RichField;
+use plonky2::plonk::circuit_builder::CircuitBuilder;
+
+use crate::gadgets::arithmetic_u32::{CircuitBuilderU32, U32Target};
+use crate::gadgets::bitwise_u32::CircuitBuilderBitwiseU32;
+use crate::gadgets::blake2s::{assert_zero_padding, blake_round, BLAKE2S_IV, BLAKE_BLOCK_WORDS};
+
+const MSG_PERMUTATION: [usize; 16] = [2, 6, 3, 10, 7, 0, 4, 13, 1, 11, 12, 5, 9, 14, 15, 8];
+
+const CHUNK_START: u32 = 1 << 0;
+const CHUNK_END: u32 = 1 << 1;
+const PARENT: u32 = 1 << 2;
+const ROOT: u32 = 1 << 3;
+const KEYED_HASH: u32 = 1 << 4;
+
+const CHUNK_BYTES: usize = 1024;
+const BLOCK_BYTES: usize = 64;
+
+pub trait CircuitBuilderBlake3<F: RichField + Extendable<D>, const D: usize> {
+    /// Applies the BLAKE3 compression function to the chaining value `cv` and a 16-word block.
+    /// Returns all 16 output words, the first 8 of which are the next chaining value.
+    fn blake3_compress(
+        &mut self,
+        cv: &[U32Target; 8],
+        block: &[U32Target],
+        counter: u64,
+        block_len: u32,
+        flags: u32,
+    ) -> [U32Target; 16];
+
+    /// Computes the 32-byte BLAKE3 hash of a message of `num_bytes` bytes, given as little-endian
+    /// words. The unused high bytes of the last word must be zero. Returns the digest as
+    /// little-endian words.
+    fn blake3(&mut self, msg: &[U32Target], num_bytes: usize) -> [U32Target; 8];
+
+    /// Computes the BLAKE3 keyed hash, used as a MAC, under a 32-byte key given as little-endian
+    /// words.
+    fn blake3_keyed(
+        &mut self,
+        key: &[U32Target; 8],
+        msg: &[U32Target],
+        num_bytes: usize,
+    ) -> [U32Target; 8];
+}
+
+impl<F: RichField + Extendable<D>, const D: usize> CircuitBuilderBlake3<F, D>
+    for CircuitBuilder<F, D>
+{
+    fn blake3_compress(
+        &mut self,
+        cv: &[U32Target; 8],
+        block: &[U32Target],
+        counter: u64,
+        block_len: u32,
+        flags: u32,
+    ) -> [U32Target; 16] {
+        let v = compress_state(self, cv, block, counter, block_len, flags);
+        core::array::from_fn(|i| {
+            if i < 8 {
+                self.xor_u32(v[i], v[i + 8])
+            } else {
+                self.xor_u32(v[i], cv[i - 8])
+            }
+        })
+    }
+
+    fn blake3(&mut self, msg: &[U32Target], num_bytes: usize) -> [U32Target; 8] {
+        let iv = BLAKE2S_IV.map(|w| self.constant_u32(w));
+        blake3_with_key(self, iv, 0, msg, num_bytes)
+    }
+
+    fn blake3_keyed(
+        &mut self,
+        key: &[U32Target; 8],
+        msg: &[U32Target],
+        num_bytes: usize,
+    ) -> [U32Target; 8] {
+        blake3_with_key(self, *key, KEYED_HASH, msg, num_bytes)
+    }
+}
+
+/// Runs the rounds of the compression function, before the feed-forward.
+fn compress_state<F: RichField + Extendable<D>, const D: usize>(
+    builder: &mut CircuitBuilder<F, D>,
+    cv: &[U32Target; 8],
+    block: &[U32Target],
+    counter: u64,
+    block_len: u32,
+    flags: u32,
+) -> [U32Target; 16] {
+    assert_eq!(
+        block.len(),
+        BLAKE_BLOCK_WORDS,
+        "BLAKE3 blocks have 16 words"
+    );
+
+    let params = [
+        BLAKE2S_IV[0],
+        BLAKE2S_IV[1],
+        BLAKE2S_IV[2],
+        BLAKE2S_IV[3],
+        counter as u32,
+        (counter >> 32) as u32,
+        block_len,
+        flags,
+    ];
+    let mut v: [U32Target; 16] = core::array::from_fn(|i| {
+        if i < 8 {
+            cv[i]
+        } else {
+            builder.constant_u32(params[i - 8])
+        }
+    });
+
+    let mut m: [U32Target; 16] = core::array::from_fn(|i| block[i]);
+    for round in 0..7 {
+        blake_round(builder, &mut v, &m);
+        if round < 6 {
+            m = core::array::from_fn(|i| m[MSG_PERMUTATION[i]]);
+        }
+    }
+    v
+}
+
+/// Computes a chaining value, which is also the 32-byte output of a root compression.
+fn chaining_value<F: RichField + Extendable<D>, const D: usize>(
+    builder: &mut CircuitBuilder<F, D>,
+    cv: &[U32Target; 8],
+    block: &[U32Target],
+    counter: u64,
+    block_len: u32,
+    flags: u32,
+) -> [U32Target; 8] {
+    let v = compress_state(builder, cv, block, counter, block_len, flags);
+    core::array::from_fn(|i| builder.xor_u32(v[i], v[i + 8]))
+}
+
+fn blake3_with_key<F: RichField + Extendable<D>, const D: usize>(
+    builder: &mut CircuitBuilder<F, D>,
+    key: [U32Target; 8],
+    base_flags: u32,
+    msg: &[U32Target],
+    num_bytes: usize,
+) -> [U32Target; 8] {
+    assert_eq!(
+        msg.len(),
+        num_bytes.div_ceil(4),
+        "BLAKE3 input must have one word per four bytes"
+    );
+    assert_zero_padding(builder, msg, num_bytes);
+
+    let parent_cv = |builder: &mut CircuitBuilder<F, D>, left: [U32Target; 8], right, flags| {
+        let block = [left, right].concat();
+        chaining_value(
+            builder,
+            &key,
+            &block,
+            0,
+            BLOCK_BYTES as u32,
+            base_flags | PARENT | flags,
+        )
+    };
+
+    // The message length is fixed, so the shape of the tree is known when building the circuit.
+    let num_chunks = num_bytes.div_ceil(CHUNK_BYTES).max(1);
+    let mut stack: Vec<[U32Target; 8]> = Vec::new();
+    let mut output = None;
+    for chunk_index in 0..num_chunks {
+        let chunk_start = chunk_index * CHUNK_BYTES;
+        let chunk_len = (num_bytes - chunk_start).min(CHUNK_BYTES);
+        let chunk = &msg[chunk_start / 4..(chunk_start + chunk_len).div_ceil(4)];
+        let is_last_chunk = chunk_index + 1 == num_chunks;
+
+        let num_blocks = chunk_len.div_ceil(BLOCK_BYTES).max(1);
+        let mut cv = key;
+        for block_index in 0..num_blocks {
+            let start = block_index * BLAKE_BLOCK_WORDS;
+            let end = (start + BLAKE_BLOCK_WORDS).min(chunk.len());
+            let mut block = chunk[start..end].to_vec();
+            block.resize(BLAKE_BLOCK_WORDS, builder.zero_u32());
+            let block_len = (chunk_len - block_index * BLOCK_BYTES).min(BLOCK_BYTES);
+
+            let mut flags = base_flags;
+            if block_index == 0 {
+                flags |= CHUNK_START;
+            }
+            if block_index + 1 == num_blocks {
+                flags |= CHUNK_END;
+                // A single chunk is the root of the tree.
+                if num_chunks == 1 {
+                    flags |= ROOT;
+                }
+            }
+            cv = chaining_value(
+                builder,
+                &cv,
+                &block,
+                chunk_index as u64,
+                block_len as u32,
+                flags,
+            );
+        }
+
+        if is_last_chunk {
+            output = Some(cv);
+        } else {
+            // Merge every completed pair of subtrees, as in the reference implementation.
+            let mut total_chunks = chunk_index + 1;
+            while total_chunks % 2 == 0 {
+                cv = parent_cv(builder, stack.pop().unwrap(), cv, 0);
+                total_chunks /= 2;
+            }
+            stack.push(cv);
+        }
+    }
+
+    let mut output = output.unwrap();
+    while let Some(left) = stack.pop() {
+        let flags = if stack.is_empty() { ROOT } else { 0 };
+        output = parent_cv(builder, left, output, flags);
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+    use plonky2::iop::witness::PartialWitness;
+    use plonky2::plonk::circuit_data::CircuitConfig;
+    use plonky2::plonk::config::{GenericConfig, PoseidonGoldilocksConfig};
+
+    use super::*;
+    use crate::gadgets::blake2s::tests::bytes_to_le_words;
+    use crate::witness::WitnessU32;
+
+    fn test_blake3_with(key: Option<[u8; 32]>, msg: &[u8]) -> Result<()> {
+        const D: usize = 2;
+        type C = PoseidonGoldilocksConfig;
+        type F = <C as GenericConfig<D>>::F;
+
+        let config = CircuitConfig::standard_recursion_config();
+
+        let mut pw = PartialWitness::new();
+        let mut builder = CircuitBuilder::<F, D>::new(config);
+
+        let words = bytes_to_le_words(msg);
+        let msg_targets = builder.add_virtual_u32_targets(words.len());
+        for (&t, &w) in msg_targets.iter().zip(words.iter()) {
+            pw.set_u32_target(t, w);
+        }
+
+        let (digest, expected) = match key {
+            None => (builder.blake3(&msg_targets, msg.len()), blake3::hash(msg)),
+            Some(key) => {
+                let key_targets: [U32Target; 8] =
+                    core::array::from_fn(|_| builder.add_virtual_u32_target());
+                for (&t, w) in key_targets.iter().zip(bytes_to_le_words(&key)) {
+                    pw.set_u32_target(t, w);
+                }
+                (
+                    builder.blake3_keyed(&key_targets, &msg_targets, msg.len()),
+                    blake3::keyed_hash(&key, msg),
+                )
+            }
+        };
+
+        for (&d, e) in digest.iter().zip(bytes_to_le_words(expected.as_bytes())) {
+            let e = builder.constant_u32(e);
+            builder.connect_u32(d, e);
+        }
+
+        let data = builder.build::<C>();
+        let proof = data.prove(pw).unwrap();
+
+        data.verify(proof)
+    }
+
+    #[test]
+    fn test_blake3_empty() -> Result<()> {
+        test_blake3_with(None, b"")
+    }
+
+    #[test]
+    fn test_blake3_abc() -> Result<()> {
+        test_blake3_with(None, b"abc")
+    }
+
+    #[test]
+    fn test_blake3_three_chunks() -> Result<()> {
+        // Two full chunks are merged into a parent, which is then the left child of the root.
+        let msg: Vec<u8> = (0..2 * CHUNK_BYTES + 1).map(|i| (i % 251) as u8).collect();
+        test_blake3_with(None, &msg)
+    }
+
+    #[test]
+    fn test_blake3_keyed() -> Result<()> {
+        test_blake3_with(Some([0x42; 32]), b"attestation response")
+    }
+}
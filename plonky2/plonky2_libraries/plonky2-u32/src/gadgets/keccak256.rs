@@ -0,0 +1,372 @@
+use alloc::vec::Vec;
+
+use plonky2::field::extension::Extendable;
+use plonky2::hash::hash_types::RichField;
+use plonky2::iop::target::Target;
+use plonky2::plonk::circuit_builder::CircuitBuilder;
+
+use crate::gadgets::arithmetic_u32::{CircuitBuilderU32, U32Target};
+use crate::gadgets::arithmetic_uint::CircuitBuilderUInt;
+use crate::gadgets::bitwise_u32::CircuitBuilderBitwiseU32;
+
+/// The Keccak-256 rate of 136 bytes, in 32-bit words.
+const KECCAK256_RATE_WORDS: usize = 34;
+
+const ROUND_CONSTANTS: [u64; 24] = [
+    0x0000000000000001,
+    0x0000000000008082,
+    0x800000000000808A,
+    0x8000000080008000,
+    0x000000000000808B,
+    0x0000000080000001,
+    0x8000000080008081,
+    0x8000000000008009,
+    0x000000000000008A,
+    0x0000000000000088,
+    0x0000000080008009,
+    0x000000008000000A,
+    0x000000008000808B,
+    0x800000000000008B,
+    0x8000000000008089,
+    0x8000000000008003,
+    0x8000000000008002,
+    0x8000000000000080,
+    0x000000000000800A,
+    0x800000008000000A,
+    0x8000000080008081,
+    0x8000000000008080,
+    0x0000000080000001,
+    0x8000000080008008,
+];
+
+/// The rotation offsets of the rho step, for the lane at `x + 5 * y`.
+const ROTATION_OFFSETS: [usize; 25] = [
+    0, 1, 62, 28, 27, 36, 44, 6, 55, 20, 3, 10, 43, 25, 39, 41, 45, 15, 21, 8, 18, 2, 61, 56, 14,
+];
+
+/// A 64-bit Keccak lane, as its low and high 32-bit words.
+pub type KeccakLaneTarget = [U32Target; 2];
+
+/// The in-circuit counterpart of a `BytesHash<N>` produced by `KeccakHash<N>`, holding one
+/// target per byte.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct KeccakHashTarget<const N: usize>(pub [Target; N]);
+
+pub trait CircuitBuilderKeccak256<F: RichField + Extendable<D>, const D: usize> {
+    /// Applies the Keccak-f[1600] permutation to a state whose lane `x + 5 * y` is at index
+    /// `x + 5 * y`.
+    fn keccak_f1600(&mut self, state: &[KeccakLaneTarget; 25]) -> [KeccakLaneTarget; 25];
+
+    /// Computes Keccak-256, as used by Ethereum, of a message of `num_bytes` bytes given as
+    /// little-endian words. The unused high bytes of the last word must be zero. Returns the
+    /// digest as little-endian words.
+    fn keccak256(&mut self, msg: &[U32Target], num_bytes: usize) -> [U32Target; 8];
+
+    fn add_virtual_keccak_hash_target<const N: usize>(&mut self) -> KeccakHashTarget<N>;
+
+    /// Computes `KeccakHash::<N>::hash_no_pad` of Goldilocks elements.
+    fn keccak_hash_no_pad<const N: usize>(&mut self, input: &[Target]) -> KeccakHashTarget<N>;
+
+    /// Computes `KeccakHash::<N>::two_to_one`.
+    fn keccak_two_to_one<const N: usize>(
+        &mut self,
+        left: KeccakHashTarget<N>,
+        right: KeccakHashTarget<N>,
+    ) -> KeccakHashTarget<N>;
+}
+
+impl<F: RichField + Extendable<D>, const D: usize> CircuitBuilderKeccak256<F, D>
+    for CircuitBuilder<F, D>
+{
+    fn keccak_f1600(&mut self, state: &[KeccakLaneTarget; 25]) -> [KeccakLaneTarget; 25] {
+        let mut a = *state;
+        for &round_constant in ROUND_CONSTANTS.iter() {
+            // Theta
+            let c: [KeccakLaneTarget; 5] = core::array::from_fn(|x| {
+                let mut parity = a[x];
+                for y in 1..5 {
+                    parity = xor_lane(self, parity, a[x + 5 * y]);
+                }
+                parity
+            });
+            for x in 0..5 {
+                let rotated = rotate_left_lane(self, c[(x + 1) % 5], 1);
+                let d = xor_lane(self, c[(x + 4) % 5], rotated);
+                for y in 0..5 {
+                    a[x + 5 * y] = xor_lane(self, a[x + 5 * y], d);
+                }
+            }
+
+            // Rho and pi
+            let mut b = a;
+            for x in 0..5 {
+                for y in 0..5 {
+                    b[y + 5 * ((2 * x + 3 * y) % 5)] =
+                        rotate_left_lane(self, a[x + 5 * y], ROTATION_OFFSETS[x + 5 * y]);
+                }
+            }
+
+            // Chi
+            for y in 0..5 {
+                for x in 0..5 {
+                    let not_next = b[(x + 1) % 5 + 5 * y].map(|w| self.not_u32(w));
+                    let masked = [
+                        self.and_u32(not_next[0], b[(x + 2) % 5 + 5 * y][0]),
+                        self.and_u32(not_next[1], b[(x + 2) % 5 + 5 * y][1]),
+                    ];
+                    a[x + 5 * y] = xor_lane(self, b[x + 5 * y], masked);
+                }
+            }
+
+            // Iota
+            let round_constant = [
+                self.constant_u32(round_constant as u32),
+                self.constant_u32((round_constant >> 32) as u32),
+            ];
+            a[0] = xor_lane(self, a[0], round_constant);
+        }
+        a
+    }
+
+    fn keccak256(&mut self, msg: &[U32Target], num_bytes: usize) -> [U32Target; 8] {
+        assert_eq!(
+            msg.len(),
+            num_bytes.div_ceil(4),
+            "Keccak-256 input must have one word per four bytes"
+        );
+
+        // Pad with 0x01, zeros and a final 0x80, which share a byte if only one is left.
+        let mut padded = msg.to_vec();
+        let partial_bytes = num_bytes % 4;
+        if partial_bytes == 0 {
+            padded.push(self.one_u32());
+        } else {
+            let last = padded.pop().unwrap();
+            let unused = self.shr_u32(last, 8 * partial_bytes);
+            self.assert_zero_u32(unused);
+            let marker = self.constant_u32(1 << (8 * partial_bytes));
+            padded.push(U32Target(self.add(last.0, marker.0)));
+        }
+        padded.resize(
+            padded.len().next_multiple_of(KECCAK256_RATE_WORDS),
+            self.zero_u32(),
+        );
+        let last = padded.pop().unwrap();
+        let marker = self.constant_u32(0x80000000);
+        padded.push(U32Target(self.add(last.0, marker.0)));
+
+        let zero = self.zero_u32();
+        let mut state = [[zero; 2]; 25];
+        for block in padded.chunks(KECCAK256_RATE_WORDS) {
+            for (lane, words) in state.iter_mut().zip(block.chunks(2)) {
+                *lane = xor_lane(self, *lane, [words[0], words[1]]);
+            }
+            state = self.keccak_f1600(&state);
+        }
+
+        core::array::from_fn(|i| state[i / 2][i % 2])
+    }
+
+    fn add_virtual_keccak_hash_target<const N: usize>(&mut self) -> KeccakHashTarget<N> {
+        let bytes = self.add_virtual_target_arr::<N>();
+        for &b in bytes.iter() {
+            self.range_check(b, 8);
+        }
+        KeccakHashTarget(bytes)
+    }
+
+    fn keccak_hash_no_pad<const N: usize>(&mut self, input: &[Target]) -> KeccakHashTarget<N> {
+        // Elements are hashed as their canonical little-endian bytes, so as (low, high) words.
+        let words: Vec<U32Target> = input
+            .iter()
+            .flat_map(|&x| self.split_canonical_u64(x).limbs)
+            .collect();
+        let digest = self.keccak256(&words, 8 * input.len());
+        digest_to_bytes(self, &digest)
+    }
+
+    fn keccak_two_to_one<const N: usize>(
+        &mut self,
+        left: KeccakHashTarget<N>,
+        right: KeccakHashTarget<N>,
+    ) -> KeccakHashTarget<N> {
+        let bytes: Vec<Target> = left.0.iter().chain(right.0.iter()).copied().collect();
+        let mut words = Vec::with_capacity(bytes.len().div_ceil(4));
+        for chunk in bytes.chunks(4) {
+            let mut word = self.zero();
+            for &b in chunk.iter().rev() {
+                word = self.mul_const_add(F::from_canonical_u32(1 << 8), word, b);
+            }
+            words.push(U32Target(word));
+        }
+        let digest = self.keccak256(&words, bytes.len());
+        digest_to_bytes(self, &digest)
+    }
+}
+
+fn xor_lane<F: RichField + Extendable<D>, const D: usize>(
+    builder: &mut CircuitBuilder<F, D>,
+    x: KeccakLaneTarget,
+    y: KeccakLaneTarget,
+) -> KeccakLaneTarget {
+    [builder.xor_u32(x[0], y[0]), builder.xor_u32(x[1], y[1])]
+}
+
+fn rotate_left_lane<F: RichField + Extendable<D>, const D: usize>(
+    builder: &mut CircuitBuilder<F, D>,
+    lane: KeccakLaneTarget,
+    n: usize,
+) -> KeccakLaneTarget {
+    let [low, high] = if n % 64 >= 32 {
+        [lane[1], lane[0]]
+    } else {
+        lane
+    };
+    let n = n % 32;
+    if n == 0 {
+        return [low, high];
+    }
+
+    // Each word is split into the bits that stay and those that move over to the other word.
+    let factor = builder.constant_u32(1 << n);
+    let (low_stay, low_moved) = builder.mul_u32(low, factor);
+    let (high_stay, high_moved) = builder.mul_u32(high, factor);
+    [
+        U32Target(builder.add(low_stay.0, high_moved.0)),
+        U32Target(builder.add(high_stay.0, low_moved.0)),
+    ]
+}
+
+fn digest_to_bytes<F: RichField + Extendable<D>, const D: usize, const N: usize>(
+    builder: &mut CircuitBuilder<F, D>,
+    digest: &[U32Target; 8],
+) -> KeccakHashTarget<N> {
+    assert!(N <= 32, "Keccak-256 digests have 32 bytes");
+
+    let mut bytes = Vec::with_capacity(32);
+    for word in digest {
+        let bits = builder.split_le(word.0, 32);
+        for byte_bits in bits.chunks(8) {
+            bytes.push(builder.le_sum(byte_bits.iter()));
+        }
+    }
+    KeccakHashTarget(core::array::from_fn(|i| bytes[i]))
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+    use plonky2::field::types::{Field, Sample};
+    use plonky2::hash::keccak::KeccakHash;
+    use plonky2::iop::witness::{PartialWitness, WitnessWrite};
+    use plonky2::plonk::circuit_data::CircuitConfig;
+    use plonky2::plonk::config::{GenericConfig, Hasher, PoseidonGoldilocksConfig};
+    use sha3::{Digest, Keccak256};
+
+    use super::*;
+    use crate::witness::WitnessU32;
+
+    fn bytes_to_keccak_words(bytes: &[u8]) -> Vec<u32> {
+        bytes
+            .chunks(4)
+            .map(|chunk| {
+                let mut word = [0u8; 4];
+                word[..chunk.len()].copy_from_slice(chunk);
+                u32::from_le_bytes(word)
+            })
+            .collect()
+    }
+
+    fn test_keccak256_with(msg: &[u8]) -> Result<()> {
+        const D: usize = 2;
+        type C = PoseidonGoldilocksConfig;
+        type F = <C as GenericConfig<D>>::F;
+
+        let config = CircuitConfig::standard_recursion_config();
+
+        let mut pw = PartialWitness::new();
+        let mut builder = CircuitBuilder::<F, D>::new(config);
+
+        let words = bytes_to_keccak_words(msg);
+        let msg_targets = builder.add_virtual_u32_targets(words.len());
+        let digest = builder.keccak256(&msg_targets, msg.len());
+
+        let expected = bytes_to_keccak_words(&Keccak256::digest(msg));
+        for (&d, e) in digest.iter().zip(expected) {
+            let e = builder.constant_u32(e);
+            builder.connect_u32(d, e);
+        }
+
+        for (&t, w) in msg_targets.iter().zip(words) {
+            pw.set_u32_target(t, w);
+        }
+
+        let data = builder.build::<C>();
+        let proof = data.prove(pw).unwrap();
+
+        data.verify(proof)
+    }
+
+    #[test]
+    fn test_keccak256_empty() -> Result<()> {
+        test_keccak256_with(b"")
+    }
+
+    #[test]
+    fn test_keccak256_abc() -> Result<()> {
+        test_keccak256_with(b"abc")
+    }
+
+    #[test]
+    fn test_keccak256_single_padding_byte() -> Result<()> {
+        // Both padding bits fall into the last byte of the block.
+        test_keccak256_with(&[0x5a; 135])
+    }
+
+    #[test]
+    fn test_keccak256_full_block() -> Result<()> {
+        test_keccak256_with(&[0xa5; 136])
+    }
+
+    #[test]
+    fn test_keccak_hash_compatibility() -> Result<()> {
+        const D: usize = 2;
+        const N: usize = 25;
+        type C = PoseidonGoldilocksConfig;
+        type F = <C as GenericConfig<D>>::F;
+
+        let config = CircuitConfig::standard_recursion_config();
+
+        let mut pw = PartialWitness::new();
+        let mut builder = CircuitBuilder::<F, D>::new(config);
+
+        let input = F::rand_vec(20);
+        let left = <KeccakHash<N> as Hasher<F>>::hash_no_pad(&input);
+        let right = <KeccakHash<N> as Hasher<F>>::hash_no_pad(&input[..7]);
+        let expected = <KeccakHash<N> as Hasher<F>>::two_to_one(left, right);
+
+        let input_targets = builder.add_virtual_targets(input.len());
+        let left_target = builder.keccak_hash_no_pad::<N>(&input_targets);
+        let right_target = builder.add_virtual_keccak_hash_target::<N>();
+        let result = builder.keccak_two_to_one(left_target, right_target);
+
+        for (&t, &b) in left_target.0.iter().zip(left.0.iter()) {
+            let b = builder.constant(F::from_canonical_u32(b.into()));
+            builder.connect(t, b);
+        }
+        for (&t, &b) in result.0.iter().zip(expected.0.iter()) {
+            let b = builder.constant(F::from_canonical_u32(b.into()));
+            builder.connect(t, b);
+        }
+
+        pw.set_target_arr(&input_targets, &input)?;
+        for (&t, &b) in right_target.0.iter().zip(right.0.iter()) {
+            pw.set_target(t, F::from_canonical_u32(b.into()))?;
+        }
+
+        let data = builder.build::<C>();
+        let proof = data.prove(pw).unwrap();
+
+        data.verify(proof)
+    }
+}
@@ -1,3 +1,9 @@
 pub mod arithmetic_u32;
+pub mod arithmetic_uint;
+pub mod bitwise_u32;
+pub mod blake2s;
+pub mod blake3;
+pub mod keccak256;
 pub mod multiple_comparison;
 pub mod range_check;
+pub mod sha256;
@@ -1,4 +1,3 @@
-use alloc::vec;
 use alloc::vec::Vec;
 
 use plonky2::field::extension::Extendable;
@@ -13,11 +12,12 @@ pub fn range_check_u32_circuit<F: RichField + Extendable<D>, const D: usize>(
     builder: &mut CircuitBuilder<F, D>,
     vals: Vec<U32Target>,
 ) {
-    let num_input_limbs = vals.len();
-    let gate = U32RangeCheckGate::<F, D>::new(num_input_limbs);
-    let row = builder.add_gate(gate, vec![]);
-
-    for i in 0..num_input_limbs {
-        builder.connect(Target::wire(row, gate.wire_ith_input_limb(i)), vals[i].0);
+    // Every limb takes the next free slot of a full-width gate, so range checks from different
+    // calls are batched into the same rows.
+    let max_limbs = U32RangeCheckGate::<F, D>::max_num_input_limbs(&builder.config);
+    let gate = U32RangeCheckGate::<F, D>::new(max_limbs);
+    for val in vals {
+        let (row, i) = builder.find_slot(gate, &[], &[]);
+        builder.connect(Target::wire(row, gate.wire_ith_input_limb(i)), val.0);
     }
 }
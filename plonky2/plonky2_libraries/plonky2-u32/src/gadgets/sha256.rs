@@ -0,0 +1,326 @@
+use alloc::vec::Vec;
+
+use plonky2::field::extension::Extendable;
+use plonky2::hash::hash_types::RichField;
+use plonky2::plonk::circuit_builder::CircuitBuilder;
+
+use crate::gadgets::arithmetic_u32::{CircuitBuilderU32, U32Target};
+use crate::gadgets::bitwise_u32::CircuitBuilderBitwiseU32;
+
+const ROUND_CONSTANTS: [u32; 64] = [
+    0x428a2f98, 0x71374491, 0xb5c0fbcf, 0xe9b5dba5, 0x3956c25b, 0x59f111f1, 0x923f82a4, 0xab1c5ed5,
+    0xd807aa98, 0x12835b01, 0x243185be, 0x550c7dc3, 0x72be5d74, 0x80deb1fe, 0x9bdc06a7, 0xc19bf174,
+    0xe49b69c1, 0xefbe4786, 0x0fc19dc6, 0x240ca1cc, 0x2de92c6f, 0x4a7484aa, 0x5cb0a9dc, 0x76f988da,
+    0x983e5152, 0xa831c66d, 0xb00327c8, 0xbf597fc7, 0xc6e00bf3, 0xd5a79147, 0x06ca6351, 0x14292967,
+    0x27b70a85, 0x2e1b2138, 0x4d2c6dfc, 0x53380d13, 0x650a7354, 0x766a0abb, 0x81c2c92e, 0x92722c85,
+    0xa2bfe8a1, 0xa81a664b, 0xc24b8b70, 0xc76c51a3, 0xd192e819, 0xd6990624, 0xf40e3585, 0x106aa070,
+    0x19a4c116, 0x1e376c08, 0x2748774c, 0x34b0bcb5, 0x391c0cb3, 0x4ed8aa4a, 0x5b9cca4f, 0x682e6ff3,
+    0x748f82ee, 0x78a5636f, 0x84c87814, 0x8cc70208, 0x90befffa, 0xa4506ceb, 0xbef9a3f7, 0xc67178f2,
+];
+
+pub const SHA256_INITIAL_STATE: [u32; 8] = [
+    0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c, 0x1f83d9ab, 0x5be0cd19,
+];
+
+const HMAC_INNER_PAD: u32 = 0x36363636;
+const HMAC_OUTER_PAD: u32 = 0x5c5c5c5c;
+
+pub trait CircuitBuilderSha256<F: RichField + Extendable<D>, const D: usize> {
+    /// Computes SHA-256 of a message of `num_bytes` bytes, given as big-endian words. The unused
+    /// low bytes of the last word must be zero. The message length is fixed when the circuit is
+    /// built, so padding costs almost nothing. Returns the digest as big-endian words.
+    fn sha256(&mut self, msg: &[U32Target], num_bytes: usize) -> [U32Target; 8];
+
+    /// Applies the SHA-256 compression function to `state` and a 16-word block.
+    fn sha256_compress(&mut self, state: &[U32Target; 8], block: &[U32Target]) -> [U32Target; 8];
+
+    /// Computes HMAC-SHA256 of a message of `num_bytes` bytes, given as in `sha256`, under a key
+    /// of at most 64 bytes given as big-endian words. Longer keys must be hashed first.
+    fn hmac_sha256(
+        &mut self,
+        key: &[U32Target],
+        msg: &[U32Target],
+        num_bytes: usize,
+    ) -> [U32Target; 8];
+}
+
+impl<F: RichField + Extendable<D>, const D: usize> CircuitBuilderSha256<F, D>
+    for CircuitBuilder<F, D>
+{
+    fn sha256(&mut self, msg: &[U32Target], num_bytes: usize) -> [U32Target; 8] {
+        assert_eq!(
+            msg.len(),
+            num_bytes.div_ceil(4),
+            "SHA-256 input must have one word per four bytes"
+        );
+
+        let mut padded = msg.to_vec();
+        let partial_bytes = num_bytes % 4;
+        if partial_bytes == 0 {
+            padded.push(self.constant_u32(0x80000000));
+        } else {
+            // The 0x80 byte goes right after the message in its last word, whose remaining bits
+            // must be zero so that adding it sets exactly one bit.
+            let last = padded.pop().unwrap();
+            let unused = self.shl_u32(last, 8 * partial_bytes);
+            self.assert_zero_u32(unused);
+            let marker = self.constant_u32(0x80 << (8 * (3 - partial_bytes)));
+            padded.push(U32Target(self.add(last.0, marker.0)));
+        }
+        while padded.len() % 16 != 14 {
+            padded.push(self.zero_u32());
+        }
+        let num_bits = 8 * num_bytes as u64;
+        padded.push(self.constant_u32((num_bits >> 32) as u32));
+        padded.push(self.constant_u32(num_bits as u32));
+
+        let mut state = SHA256_INITIAL_STATE.map(|h| self.constant_u32(h));
+        for block in padded.chunks(16) {
+            state = self.sha256_compress(&state, block);
+        }
+        state
+    }
+
+    fn sha256_compress(&mut self, state: &[U32Target; 8], block: &[U32Target]) -> [U32Target; 8] {
+        assert_eq!(block.len(), 16, "SHA-256 blocks have 16 words");
+
+        let mut w = block.to_vec();
+        for t in 16..64 {
+            let s0 = {
+                let x = w[t - 15];
+                let (r7, r18, s3) = (
+                    self.rotate_right_u32(x, 7),
+                    self.rotate_right_u32(x, 18),
+                    self.shr_u32(x, 3),
+                );
+                xor3_u32(self, r7, r18, s3)
+            };
+            let s1 = {
+                let x = w[t - 2];
+                let (r17, r19, s10) = (
+                    self.rotate_right_u32(x, 17),
+                    self.rotate_right_u32(x, 19),
+                    self.shr_u32(x, 10),
+                );
+                xor3_u32(self, r17, r19, s10)
+            };
+            let (next, _) = self.add_many_u32(&[s1, w[t - 7], s0, w[t - 16]]);
+            w.push(next);
+        }
+
+        let [mut a, mut b, mut c, mut d, mut e, mut f, mut g, mut h] = *state;
+        for t in 0..64 {
+            let big_s1 = {
+                let (r6, r11, r25) = (
+                    self.rotate_right_u32(e, 6),
+                    self.rotate_right_u32(e, 11),
+                    self.rotate_right_u32(e, 25),
+                );
+                xor3_u32(self, r6, r11, r25)
+            };
+            // (e AND f) XOR (NOT e AND g), where the two sides never share a set bit.
+            let ch = {
+                let e_and_f = self.and_u32(e, f);
+                let not_e = self.not_u32(e);
+                let not_e_and_g = self.and_u32(not_e, g);
+                U32Target(self.add(e_and_f.0, not_e_and_g.0))
+            };
+            let k = self.constant_u32(ROUND_CONSTANTS[t]);
+            let (t1, _) = self.add_many_u32(&[h, big_s1, ch, k, w[t]]);
+
+            let big_s0 = {
+                let (r2, r13, r22) = (
+                    self.rotate_right_u32(a, 2),
+                    self.rotate_right_u32(a, 13),
+                    self.rotate_right_u32(a, 22),
+                );
+                xor3_u32(self, r2, r13, r22)
+            };
+            // The majority is (a AND (b XOR c)) + (b AND c), whose two sides are disjoint too.
+            let maj = {
+                let b_xor_c = self.xor_u32(b, c);
+                let a_and_b_xor_c = self.and_u32(a, b_xor_c);
+                let b_and_c = self.and_u32(b, c);
+                U32Target(self.add(a_and_b_xor_c.0, b_and_c.0))
+            };
+
+            h = g;
+            g = f;
+            f = e;
+            e = self.add_u32(d, t1).0;
+            d = c;
+            c = b;
+            b = a;
+            a = self.add_many_u32(&[t1, big_s0, maj]).0;
+        }
+
+        let updated = [a, b, c, d, e, f, g, h];
+        let mut result = *state;
+        for (r, u) in result.iter_mut().zip(updated) {
+            *r = self.add_u32(*r, u).0;
+        }
+        result
+    }
+
+    fn hmac_sha256(
+        &mut self,
+        key: &[U32Target],
+        msg: &[U32Target],
+        num_bytes: usize,
+    ) -> [U32Target; 8] {
+        assert!(
+            key.len() <= 16,
+            "HMAC keys longer than a block must be hashed first"
+        );
+
+        // The padded key fills exactly one block, so the message stays word-aligned after it.
+        let mut padded_key = key.to_vec();
+        padded_key.resize(16, self.zero_u32());
+        let mut keyed_block = |pad: u32| -> Vec<U32Target> {
+            let pad = self.constant_u32(pad);
+            padded_key.iter().map(|&k| self.xor_u32(k, pad)).collect()
+        };
+        let mut inner = keyed_block(HMAC_INNER_PAD);
+        let mut outer = keyed_block(HMAC_OUTER_PAD);
+
+        inner.extend_from_slice(msg);
+        let inner_hash = self.sha256(&inner, 64 + num_bytes);
+        outer.extend_from_slice(&inner_hash);
+        self.sha256(&outer, 96)
+    }
+}
+
+fn xor3_u32<F: RichField + Extendable<D>, const D: usize>(
+    builder: &mut CircuitBuilder<F, D>,
+    x: U32Target,
+    y: U32Target,
+    z: U32Target,
+) -> U32Target {
+    let x_xor_y = builder.xor_u32(x, y);
+    builder.xor_u32(x_xor_y, z)
+}
+
+/// Packs bytes into the big-endian words taken by `CircuitBuilderSha256::sha256`, padding the
+/// last word with zero bytes.
+pub fn bytes_to_sha256_words(bytes: &[u8]) -> Vec<u32> {
+    bytes
+        .chunks(4)
+        .map(|chunk| {
+            let mut word = [0u8; 4];
+            word[..chunk.len()].copy_from_slice(chunk);
+            u32::from_be_bytes(word)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+    use hmac::{Hmac, Mac};
+    use plonky2::iop::witness::PartialWitness;
+    use plonky2::plonk::circuit_data::CircuitConfig;
+    use plonky2::plonk::config::{GenericConfig, PoseidonGoldilocksConfig};
+    use sha2::{Digest, Sha256};
+
+    use super::*;
+    use crate::witness::WitnessU32;
+
+    fn test_sha256_with(msg: &[u8]) -> Result<()> {
+        const D: usize = 2;
+        type C = PoseidonGoldilocksConfig;
+        type F = <C as GenericConfig<D>>::F;
+
+        let config = CircuitConfig::standard_recursion_config();
+
+        let mut pw = PartialWitness::new();
+        let mut builder = CircuitBuilder::<F, D>::new(config);
+
+        let words = bytes_to_sha256_words(msg);
+        let msg_targets = builder.add_virtual_u32_targets(words.len());
+        let digest = builder.sha256(&msg_targets, msg.len());
+
+        let expected = bytes_to_sha256_words(&Sha256::digest(msg));
+        for (&d, e) in digest.iter().zip(expected) {
+            let e = builder.constant_u32(e);
+            builder.connect_u32(d, e);
+        }
+
+        for (&t, w) in msg_targets.iter().zip(words) {
+            pw.set_u32_target(t, w);
+        }
+
+        let data = builder.build::<C>();
+        let proof = data.prove(pw).unwrap();
+
+        data.verify(proof)
+    }
+
+    #[test]
+    fn test_sha256_empty() -> Result<()> {
+        test_sha256_with(b"")
+    }
+
+    #[test]
+    fn test_sha256_abc() -> Result<()> {
+        test_sha256_with(b"abc")
+    }
+
+    #[test]
+    fn test_sha256_two_blocks() -> Result<()> {
+        test_sha256_with(b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq")
+    }
+
+    #[test]
+    fn test_sha256_partial_last_word() -> Result<()> {
+        // 61 bytes: the length spills into a second block and the last word has one byte.
+        test_sha256_with(&[0x5a; 61])
+    }
+
+    fn test_hmac_sha256_with(key: &[u8], msg: &[u8]) -> Result<()> {
+        const D: usize = 2;
+        type C = PoseidonGoldilocksConfig;
+        type F = <C as GenericConfig<D>>::F;
+
+        let config = CircuitConfig::standard_recursion_config();
+
+        let mut pw = PartialWitness::new();
+        let mut builder = CircuitBuilder::<F, D>::new(config);
+
+        let key_words = bytes_to_sha256_words(key);
+        let msg_words = bytes_to_sha256_words(msg);
+        let key_targets = builder.add_virtual_u32_targets(key_words.len());
+        let msg_targets = builder.add_virtual_u32_targets(msg_words.len());
+        let tag = builder.hmac_sha256(&key_targets, &msg_targets, msg.len());
+
+        let mut mac = Hmac::<Sha256>::new_from_slice(key).unwrap();
+        mac.update(msg);
+        let expected = bytes_to_sha256_words(&mac.finalize().into_bytes());
+        for (&t, e) in tag.iter().zip(expected) {
+            let e = builder.constant_u32(e);
+            builder.connect_u32(t, e);
+        }
+
+        for (&t, w) in key_targets.iter().zip(key_words) {
+            pw.set_u32_target(t, w);
+        }
+        for (&t, w) in msg_targets.iter().zip(msg_words) {
+            pw.set_u32_target(t, w);
+        }
+
+        let data = builder.build::<C>();
+        let proof = data.prove(pw).unwrap();
+
+        data.verify(proof)
+    }
+
+    #[test]
+    fn test_hmac_sha256_rfc4231() -> Result<()> {
+        // Test case 2 of RFC 4231, whose key is shorter than a word.
+        test_hmac_sha256_with(b"Jefe", b"what do ya want for nothing?")
+    }
+
+    #[test]
+    fn test_hmac_sha256_full_block_key() -> Result<()> {
+        test_hmac_sha256_with(&[0xaa; 64], &[0xdd; 50])
+    }
+}
@@ -32,7 +32,7 @@ pub fn ceil_div_usize(a: usize, b: usize) -> usize {
 
 
 /// A gate to perform addition on `num_addends` different 32-bit values, plus a small carry
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, Debug, Default)]
 pub struct U32AddManyGate<F: RichField + Extendable<D>, const D: usize> {
     pub num_addends: usize,
     pub num_ops: usize,
@@ -292,8 +292,8 @@ impl<F: RichField + Extendable<D>, const D: usize> Gate<F, D> for U32AddManyGate
     }
 }
 
-#[derive(Clone, Debug)]
-struct U32AddManyGenerator<F: RichField + Extendable<D>, const D: usize> {
+#[derive(Clone, Debug, Default)]
+pub struct U32AddManyGenerator<F: RichField + Extendable<D>, const D: usize> {
     gate: U32AddManyGate<F, D>,
     row: usize,
     i: usize,
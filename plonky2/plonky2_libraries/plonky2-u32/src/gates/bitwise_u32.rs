@@ -0,0 +1,420 @@
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use alloc::{format, vec};
+use core::marker::PhantomData;
+
+use plonky2::field::extension::Extendable;
+use plonky2::field::types::Field;
+use plonky2::gates::gate::Gate;
+use plonky2::gates::util::StridedConstraintConsumer;
+use plonky2::hash::hash_types::RichField;
+use plonky2::iop::ext_target::ExtensionTarget;
+use plonky2::iop::generator::{GeneratedValues, SimpleGenerator, WitnessGeneratorRef};
+use plonky2::iop::target::Target;
+use plonky2::iop::wire::Wire;
+use plonky2::iop::witness::{PartitionWitness, Witness, WitnessWrite};
+use plonky2::plonk::circuit_builder::CircuitBuilder;
+use plonky2::plonk::circuit_data::{CircuitConfig, CommonCircuitData};
+use plonky2::plonk::vars::{EvaluationTargets, EvaluationVars, EvaluationVarsBase};
+use plonky2::util::serialization::{Buffer, IoError, IoResult, Read, Write};
+
+/// The bitwise operations supported by `U32BitwiseGate`.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum U32BitwiseOp {
+    #[default]
+    And,
+    Xor,
+}
+
+impl U32BitwiseOp {
+    /// The operation on single bits, as a polynomial which is exact for boolean inputs.
+    fn apply<T: Field>(self, x: T, y: T) -> T {
+        match self {
+            U32BitwiseOp::And => x * y,
+            U32BitwiseOp::Xor => x + y - (x * y).double(),
+        }
+    }
+}
+
+/// A gate to perform a bitwise operation on two 32-bit values. Both inputs are decomposed into
+/// bits, which are combined with `op` and recomposed into the output.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct U32BitwiseGate<F: RichField + Extendable<D>, const D: usize> {
+    pub op: U32BitwiseOp,
+    pub num_ops: usize,
+    _phantom: PhantomData<F>,
+}
+
+impl<F: RichField + Extendable<D>, const D: usize> U32BitwiseGate<F, D> {
+    pub fn new_from_config(config: &CircuitConfig, op: U32BitwiseOp) -> Self {
+        Self {
+            op,
+            num_ops: Self::num_ops(config),
+            _phantom: PhantomData,
+        }
+    }
+
+    pub(crate) fn num_ops(config: &CircuitConfig) -> usize {
+        let wires_per_op = Self::routed_wires_per_op() + Self::num_bits();
+        (config.num_wires / wires_per_op).min(config.num_routed_wires / Self::routed_wires_per_op())
+    }
+
+    pub fn wire_ith_input_x(&self, i: usize) -> usize {
+        debug_assert!(i < self.num_ops);
+        Self::routed_wires_per_op() * i
+    }
+    pub fn wire_ith_input_y(&self, i: usize) -> usize {
+        debug_assert!(i < self.num_ops);
+        Self::routed_wires_per_op() * i + 1
+    }
+
+    pub fn wire_ith_output(&self, i: usize) -> usize {
+        debug_assert!(i < self.num_ops);
+        Self::routed_wires_per_op() * i + 2
+    }
+
+    pub fn routed_wires_per_op() -> usize {
+        3
+    }
+    /// The bits of both inputs.
+    pub fn num_bits() -> usize {
+        64
+    }
+
+    /// The `j`th bit of the `i`th operation: bits `0..32` are those of `x`, and bits `32..64`
+    /// those of `y`, least significant first.
+    pub fn wire_ith_jth_bit(&self, i: usize, j: usize) -> usize {
+        debug_assert!(i < self.num_ops);
+        debug_assert!(j < Self::num_bits());
+        Self::routed_wires_per_op() * self.num_ops + Self::num_bits() * i + j
+    }
+}
+
+impl<F: RichField + Extendable<D>, const D: usize> Gate<F, D> for U32BitwiseGate<F, D> {
+    fn id(&self) -> String {
+        format!("{self:?}")
+    }
+
+    fn serialize(&self, dst: &mut Vec<u8>, _common_data: &CommonCircuitData<F, D>) -> IoResult<()> {
+        dst.write_u8(self.op as u8)?;
+        dst.write_usize(self.num_ops)
+    }
+
+    fn deserialize(src: &mut Buffer, _common_data: &CommonCircuitData<F, D>) -> IoResult<Self> {
+        let op = match src.read_u8()? {
+            0 => U32BitwiseOp::And,
+            1 => U32BitwiseOp::Xor,
+            _ => return Err(IoError),
+        };
+        let num_ops = src.read_usize()?;
+        Ok(Self {
+            op,
+            num_ops,
+            _phantom: PhantomData,
+        })
+    }
+
+    fn eval_unfiltered(&self, vars: EvaluationVars<F, D>) -> Vec<F::Extension> {
+        let mut constraints = Vec::with_capacity(self.num_constraints());
+        for i in 0..self.num_ops {
+            let x = vars.local_wires[self.wire_ith_input_x(i)];
+            let y = vars.local_wires[self.wire_ith_input_y(i)];
+            let output = vars.local_wires[self.wire_ith_output(i)];
+
+            let mut combined_x = F::Extension::ZERO;
+            let mut combined_y = F::Extension::ZERO;
+            let mut combined_output = F::Extension::ZERO;
+            let base = F::Extension::TWO;
+            for j in (0..32).rev() {
+                let x_bit = vars.local_wires[self.wire_ith_jth_bit(i, j)];
+                let y_bit = vars.local_wires[self.wire_ith_jth_bit(i, 32 + j)];
+                constraints.push(x_bit * (x_bit - F::Extension::ONE));
+                constraints.push(y_bit * (y_bit - F::Extension::ONE));
+
+                combined_x = base * combined_x + x_bit;
+                combined_y = base * combined_y + y_bit;
+                combined_output = base * combined_output + self.op.apply(x_bit, y_bit);
+            }
+            constraints.push(combined_x - x);
+            constraints.push(combined_y - y);
+            constraints.push(combined_output - output);
+        }
+
+        constraints
+    }
+
+    fn eval_unfiltered_base_one(
+        &self,
+        vars: EvaluationVarsBase<F>,
+        mut yield_constr: StridedConstraintConsumer<F>,
+    ) {
+        for i in 0..self.num_ops {
+            let x = vars.local_wires[self.wire_ith_input_x(i)];
+            let y = vars.local_wires[self.wire_ith_input_y(i)];
+            let output = vars.local_wires[self.wire_ith_output(i)];
+
+            let mut combined_x = F::ZERO;
+            let mut combined_y = F::ZERO;
+            let mut combined_output = F::ZERO;
+            let base = F::TWO;
+            for j in (0..32).rev() {
+                let x_bit = vars.local_wires[self.wire_ith_jth_bit(i, j)];
+                let y_bit = vars.local_wires[self.wire_ith_jth_bit(i, 32 + j)];
+                yield_constr.one(x_bit * (x_bit - F::ONE));
+                yield_constr.one(y_bit * (y_bit - F::ONE));
+
+                combined_x = base * combined_x + x_bit;
+                combined_y = base * combined_y + y_bit;
+                combined_output = base * combined_output + self.op.apply(x_bit, y_bit);
+            }
+            yield_constr.one(combined_x - x);
+            yield_constr.one(combined_y - y);
+            yield_constr.one(combined_output - output);
+        }
+    }
+
+    fn eval_unfiltered_circuit(
+        &self,
+        builder: &mut CircuitBuilder<F, D>,
+        vars: EvaluationTargets<D>,
+    ) -> Vec<ExtensionTarget<D>> {
+        let mut constraints = Vec::with_capacity(self.num_constraints());
+
+        for i in 0..self.num_ops {
+            let x = vars.local_wires[self.wire_ith_input_x(i)];
+            let y = vars.local_wires[self.wire_ith_input_y(i)];
+            let output = vars.local_wires[self.wire_ith_output(i)];
+
+            let mut combined_x = builder.zero_extension();
+            let mut combined_y = builder.zero_extension();
+            let mut combined_output = builder.zero_extension();
+            let base = builder.two_extension();
+            for j in (0..32).rev() {
+                let x_bit = vars.local_wires[self.wire_ith_jth_bit(i, j)];
+                let y_bit = vars.local_wires[self.wire_ith_jth_bit(i, 32 + j)];
+                constraints.push(builder.mul_sub_extension(x_bit, x_bit, x_bit));
+                constraints.push(builder.mul_sub_extension(y_bit, y_bit, y_bit));
+
+                let output_bit = match self.op {
+                    U32BitwiseOp::And => builder.mul_extension(x_bit, y_bit),
+                    U32BitwiseOp::Xor => {
+                        // x + y - 2xy
+                        let sum = builder.add_extension(x_bit, y_bit);
+                        builder.arithmetic_extension(-F::TWO, F::ONE, x_bit, y_bit, sum)
+                    }
+                };
+
+                combined_x = builder.mul_add_extension(base, combined_x, x_bit);
+                combined_y = builder.mul_add_extension(base, combined_y, y_bit);
+                combined_output = builder.mul_add_extension(base, combined_output, output_bit);
+            }
+            constraints.push(builder.sub_extension(combined_x, x));
+            constraints.push(builder.sub_extension(combined_y, y));
+            constraints.push(builder.sub_extension(combined_output, output));
+        }
+
+        constraints
+    }
+
+    fn generators(&self, row: usize, _local_constants: &[F]) -> Vec<WitnessGeneratorRef<F, D>> {
+        (0..self.num_ops)
+            .map(|i| {
+                WitnessGeneratorRef::new(
+                    U32BitwiseGenerator {
+                        gate: *self,
+                        row,
+                        i,
+                        _phantom: PhantomData,
+                    }
+                    .adapter(),
+                )
+            })
+            .collect()
+    }
+
+    fn num_wires(&self) -> usize {
+        self.num_ops * (Self::routed_wires_per_op() + Self::num_bits())
+    }
+
+    fn num_constants(&self) -> usize {
+        0
+    }
+
+    fn degree(&self) -> usize {
+        2
+    }
+
+    fn num_constraints(&self) -> usize {
+        self.num_ops * (Self::num_bits() + 3)
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct U32BitwiseGenerator<F: RichField + Extendable<D>, const D: usize> {
+    gate: U32BitwiseGate<F, D>,
+    row: usize,
+    i: usize,
+    _phantom: PhantomData<F>,
+}
+
+impl<F: RichField + Extendable<D>, const D: usize> SimpleGenerator<F, D>
+    for U32BitwiseGenerator<F, D>
+{
+    fn id(&self) -> String {
+        "U32BitwiseGenerator".to_string()
+    }
+
+    fn serialize(&self, dst: &mut Vec<u8>, common_data: &CommonCircuitData<F, D>) -> IoResult<()> {
+        self.gate.serialize(dst, common_data)?;
+        dst.write_usize(self.row)?;
+        dst.write_usize(self.i)
+    }
+
+    fn deserialize(src: &mut Buffer, common_data: &CommonCircuitData<F, D>) -> IoResult<Self> {
+        let gate = U32BitwiseGate::deserialize(src, common_data)?;
+        let row = src.read_usize()?;
+        let i = src.read_usize()?;
+        Ok(Self {
+            gate,
+            row,
+            i,
+            _phantom: PhantomData,
+        })
+    }
+
+    fn dependencies(&self) -> Vec<Target> {
+        let local_target = |column| Target::wire(self.row, column);
+
+        vec![
+            local_target(self.gate.wire_ith_input_x(self.i)),
+            local_target(self.gate.wire_ith_input_y(self.i)),
+        ]
+    }
+
+    fn run_once(
+        &self,
+        witness: &PartitionWitness<F>,
+        out_buffer: &mut GeneratedValues<F>,
+    ) -> Result<(), anyhow::Error> {
+        let local_wire = |column| Wire {
+            row: self.row,
+            column,
+        };
+
+        let get_local_wire = |column| witness.get_wire(local_wire(column));
+
+        let x = get_local_wire(self.gate.wire_ith_input_x(self.i)).to_canonical_u64() as u32;
+        let y = get_local_wire(self.gate.wire_ith_input_y(self.i)).to_canonical_u64() as u32;
+        let output = match self.gate.op {
+            U32BitwiseOp::And => x & y,
+            U32BitwiseOp::Xor => x ^ y,
+        };
+
+        out_buffer.set_wire(
+            local_wire(self.gate.wire_ith_output(self.i)),
+            F::from_canonical_u32(output),
+        )?;
+        for j in 0..32 {
+            out_buffer.set_wire(
+                local_wire(self.gate.wire_ith_jth_bit(self.i, j)),
+                F::from_bool((x >> j) & 1 == 1),
+            )?;
+            out_buffer.set_wire(
+                local_wire(self.gate.wire_ith_jth_bit(self.i, 32 + j)),
+                F::from_bool((y >> j) & 1 == 1),
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+    use plonky2::field::extension::quartic::QuarticExtension;
+    use plonky2::field::goldilocks_field::GoldilocksField;
+    use plonky2::field::types::Sample;
+    use plonky2::gates::gate_testing::{test_eval_fns, test_low_degree};
+    use plonky2::hash::hash_types::HashOut;
+    use plonky2::plonk::config::{GenericConfig, PoseidonGoldilocksConfig};
+    use rand::rngs::OsRng;
+    use rand::Rng;
+
+    use super::*;
+
+    #[test]
+    fn low_degree() {
+        for op in [U32BitwiseOp::And, U32BitwiseOp::Xor] {
+            test_low_degree::<GoldilocksField, _, 4>(U32BitwiseGate::<GoldilocksField, 4> {
+                op,
+                num_ops: 3,
+                _phantom: PhantomData,
+            })
+        }
+    }
+
+    #[test]
+    fn eval_fns() -> Result<()> {
+        const D: usize = 2;
+        type C = PoseidonGoldilocksConfig;
+        type F = <C as GenericConfig<D>>::F;
+        for op in [U32BitwiseOp::And, U32BitwiseOp::Xor] {
+            test_eval_fns::<F, C, _, D>(U32BitwiseGate::<GoldilocksField, D> {
+                op,
+                num_ops: 3,
+                _phantom: PhantomData,
+            })?;
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_gate_constraint() {
+        type F = GoldilocksField;
+        type FF = QuarticExtension<GoldilocksField>;
+        const D: usize = 4;
+        const NUM_U32_BITWISE_OPS: usize = 3;
+
+        fn get_wires(op: U32BitwiseOp, xs: &[u32], ys: &[u32]) -> Vec<FF> {
+            let mut v0 = Vec::new();
+            let mut v1 = Vec::new();
+
+            for (&x, &y) in xs.iter().zip(ys) {
+                let output = match op {
+                    U32BitwiseOp::And => x & y,
+                    U32BitwiseOp::Xor => x ^ y,
+                };
+                v0.push(F::from_canonical_u32(x));
+                v0.push(F::from_canonical_u32(y));
+                v0.push(F::from_canonical_u32(output));
+                v1.extend((0..32).map(|j| F::from_bool((x >> j) & 1 == 1)));
+                v1.extend((0..32).map(|j| F::from_bool((y >> j) & 1 == 1)));
+            }
+
+            v0.iter().chain(v1.iter()).map(|&x| x.into()).collect()
+        }
+
+        let mut rng = OsRng;
+        let xs: Vec<u32> = (0..NUM_U32_BITWISE_OPS).map(|_| rng.gen()).collect();
+        let ys: Vec<u32> = (0..NUM_U32_BITWISE_OPS).map(|_| rng.gen()).collect();
+
+        for op in [U32BitwiseOp::And, U32BitwiseOp::Xor] {
+            let gate = U32BitwiseGate::<F, D> {
+                op,
+                num_ops: NUM_U32_BITWISE_OPS,
+                _phantom: PhantomData,
+            };
+
+            let vars = EvaluationVars {
+                local_constants: &[],
+                local_wires: &get_wires(op, &xs, &ys),
+                public_inputs_hash: &HashOut::rand(),
+            };
+
+            assert!(
+                gate.eval_unfiltered(vars).iter().all(|x| x.is_zero()),
+                "Gate constraints are not satisfied."
+            );
+        }
+    }
+}
@@ -1,5 +1,6 @@
 pub mod add_many_u32;
 pub mod arithmetic_u32;
+pub mod bitwise_u32;
 pub mod comparison;
 pub mod range_check_u32;
 pub mod subtraction_u32;
@@ -2,7 +2,7 @@ use alloc::string::{String, ToString};
 use alloc::vec::Vec;
 use alloc::{format, vec};
 use core::marker::PhantomData;
-use plonky2::plonk::circuit_data::CommonCircuitData;
+use plonky2::plonk::circuit_data::{CircuitConfig, CommonCircuitData};
 use plonky2::util::serialization::{Buffer, IoResult, Read, Write};
 
 use plonky2::field::extension::Extendable;
@@ -28,7 +28,7 @@ const fn ceil_div_usize(a: usize, b: usize) -> usize {
 
 
 /// A gate which can decompose a number into base B little-endian limbs.
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, Debug, Default)]
 pub struct U32RangeCheckGate<F: RichField + Extendable<D>, const D: usize> {
     pub num_input_limbs: usize,
     _phantom: PhantomData<F>,
@@ -45,6 +45,12 @@ impl<F: RichField + Extendable<D>, const D: usize> U32RangeCheckGate<F, D> {
     pub const AUX_LIMB_BITS: usize = 2;
     pub const BASE: usize = 1 << Self::AUX_LIMB_BITS;
 
+    /// The largest number of limbs a single gate can range-check under `config`.
+    pub fn max_num_input_limbs(config: &CircuitConfig) -> usize {
+        let wires_per_limb = 1 + ceil_div_usize(32, Self::AUX_LIMB_BITS);
+        (config.num_wires / wires_per_limb).min(config.num_routed_wires)
+    }
+
     fn aux_limbs_per_input_limb(&self) -> usize {
         ceil_div_usize(32, Self::AUX_LIMB_BITS)
     }
@@ -159,10 +165,18 @@ impl<F: RichField + Extendable<D>, const D: usize> Gate<F, D> for U32RangeCheckG
         constraints
     }
 
+    // One generator per input limb, so that `find_slot` can share a gate between range checks.
     fn generators(&self, row: usize, _local_constants: &[F]) -> Vec<WitnessGeneratorRef<F, D>> {
-        let gen = U32RangeCheckGenerator { gate: *self, row };
-        let x = WitnessGeneratorRef::new(gen.adapter());
-        vec![x]
+        (0..self.num_input_limbs)
+            .map(|i| {
+                let gen = U32RangeCheckGenerator {
+                    gate: *self,
+                    row,
+                    i,
+                };
+                WitnessGeneratorRef::new(gen.adapter())
+            })
+            .collect()
     }
 
     fn num_wires(&self) -> usize {
@@ -184,10 +198,11 @@ impl<F: RichField + Extendable<D>, const D: usize> Gate<F, D> for U32RangeCheckG
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Default)]
 pub struct U32RangeCheckGenerator<F: RichField + Extendable<D>, const D: usize> {
     gate: U32RangeCheckGate<F, D>,
     row: usize,
+    i: usize,
 }
 
 impl<F: RichField + Extendable<D>, const D: usize> SimpleGenerator<F, D>
@@ -198,46 +213,43 @@ impl<F: RichField + Extendable<D>, const D: usize> SimpleGenerator<F, D>
     }
 
     fn dependencies(&self) -> Vec<Target> {
-        let num_input_limbs = self.gate.num_input_limbs;
-        (0..num_input_limbs)
-            .map(|i| Target::wire(self.row, self.gate.wire_ith_input_limb(i)))
-            .collect()
+        vec![Target::wire(self.row, self.gate.wire_ith_input_limb(self.i))]
     }
 
     fn run_once(&self, witness: &PartitionWitness<F>, out_buffer: &mut GeneratedValues<F>) -> Result<(), anyhow::Error> {
-        let num_input_limbs = self.gate.num_input_limbs;
-        for i in 0..num_input_limbs {
-            let sum_value = witness
-                .get_target(Target::wire(self.row, self.gate.wire_ith_input_limb(i)))
-                .to_canonical_u64() as u32;
-
-            let base = U32RangeCheckGate::<F, D>::BASE as u32;
-            let limbs = (0..self.gate.aux_limbs_per_input_limb())
-                .map(|j| Target::wire(self.row, self.gate.wire_ith_input_limb_jth_aux_limb(i, j)));
-            let limbs_value = (0..self.gate.aux_limbs_per_input_limb())
-                .scan(sum_value, |acc, _| {
-                    let tmp = *acc % base;
-                    *acc /= base;
-                    Some(F::from_canonical_u32(tmp))
-                })
-                .collect::<Vec<_>>();
-
-            for (b, b_value) in limbs.zip(limbs_value) {
-                out_buffer.set_target(b, b_value)?;
-            }
+        let i = self.i;
+        let sum_value = witness
+            .get_target(Target::wire(self.row, self.gate.wire_ith_input_limb(i)))
+            .to_canonical_u64() as u32;
+
+        let base = U32RangeCheckGate::<F, D>::BASE as u32;
+        let limbs = (0..self.gate.aux_limbs_per_input_limb())
+            .map(|j| Target::wire(self.row, self.gate.wire_ith_input_limb_jth_aux_limb(i, j)));
+        let limbs_value = (0..self.gate.aux_limbs_per_input_limb())
+            .scan(sum_value, |acc, _| {
+                let tmp = *acc % base;
+                *acc /= base;
+                Some(F::from_canonical_u32(tmp))
+            })
+            .collect::<Vec<_>>();
+
+        for (b, b_value) in limbs.zip(limbs_value) {
+            out_buffer.set_target(b, b_value)?;
         }
         Ok(())
     }
 
     fn serialize(&self, dst: &mut Vec<u8>, common_data: &CommonCircuitData<F, D>) -> IoResult<()> {
         self.gate.serialize(dst, common_data)?;
-        dst.write_usize(self.row)
+        dst.write_usize(self.row)?;
+        dst.write_usize(self.i)
     }
 
     fn deserialize(src: &mut Buffer, common_data: &CommonCircuitData<F, D>) -> IoResult<Self> {
         let gate = U32RangeCheckGate::deserialize(src, common_data)?;
         let row = src.read_usize()?;
-        Ok(Self { row, gate })
+        let i = src.read_usize()?;
+        Ok(Self { gate, row, i })
     }
 }
 
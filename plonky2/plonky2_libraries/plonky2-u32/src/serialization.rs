@@ -2,6 +2,7 @@ use alloc::vec::Vec;
 use plonky2::util::serialization::{Buffer, IoResult, Read, Write};
 
 use crate::gadgets::arithmetic_u32::U32Target;
+use crate::gadgets::arithmetic_uint::UIntTarget;
 
 pub trait WriteU32 {
     fn write_target_u32(&mut self, x: U32Target) -> IoResult<()>;
@@ -24,3 +25,34 @@ impl ReadU32 for Buffer<'_> {
         Ok(U32Target(self.read_target()?))
     }
 }
+
+pub trait WriteUInt {
+    fn write_target_uint<const N: usize>(&mut self, x: &UIntTarget<N>) -> IoResult<()>;
+}
+
+impl WriteUInt for Vec<u8> {
+    #[inline]
+    fn write_target_uint<const N: usize>(&mut self, x: &UIntTarget<N>) -> IoResult<()> {
+        for &limb in &x.limbs {
+            self.write_target_u32(limb)?;
+        }
+
+        Ok(())
+    }
+}
+
+pub trait ReadUInt {
+    fn read_target_uint<const N: usize>(&mut self) -> IoResult<UIntTarget<N>>;
+}
+
+impl ReadUInt for Buffer<'_> {
+    #[inline]
+    fn read_target_uint<const N: usize>(&mut self) -> IoResult<UIntTarget<N>> {
+        let mut limbs = [U32Target::default(); N];
+        for limb in &mut limbs {
+            *limb = self.read_target_u32()?;
+        }
+
+        Ok(UIntTarget { limbs })
+    }
+}
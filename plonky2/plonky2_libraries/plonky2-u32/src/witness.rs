@@ -3,6 +3,7 @@ use plonky2::iop::generator::GeneratedValues;
 use plonky2::iop::witness::{Witness, WitnessWrite};
 
 use crate::gadgets::arithmetic_u32::U32Target;
+use crate::gadgets::arithmetic_uint::{U64Target, UIntTarget};
 
 pub trait WitnessU32<F: PrimeField64>: Witness<F> {
     fn set_u32_target(&mut self, target: U32Target, value: u32);
@@ -31,3 +32,52 @@ impl<F: Field> GeneratedValuesU32<F> for GeneratedValues<F> {
         let _ = self.set_target(target.0, F::from_canonical_u32(value));
     }
 }
+
+pub trait WitnessUInt<F: PrimeField64>: Witness<F> {
+    fn set_uint_target<const N: usize>(&mut self, target: &UIntTarget<N>, limbs: [u32; N]);
+    fn get_uint_target<const N: usize>(&self, target: &UIntTarget<N>) -> [u32; N];
+    fn set_u64_target(&mut self, target: U64Target, value: u64);
+    fn get_u64_target(&self, target: U64Target) -> u64;
+}
+
+impl<T: Witness<F>, F: PrimeField64> WitnessUInt<F> for T {
+    fn set_uint_target<const N: usize>(&mut self, target: &UIntTarget<N>, limbs: [u32; N]) {
+        for (&t, l) in target.limbs.iter().zip(limbs) {
+            self.set_u32_target(t, l);
+        }
+    }
+
+    fn get_uint_target<const N: usize>(&self, target: &UIntTarget<N>) -> [u32; N] {
+        target.limbs.map(|t| {
+            let (low, high) = self.get_u32_target(t);
+            assert_eq!(high, 0);
+            low
+        })
+    }
+
+    fn set_u64_target(&mut self, target: U64Target, value: u64) {
+        self.set_uint_target(&target, [value as u32, (value >> 32) as u32]);
+    }
+
+    fn get_u64_target(&self, target: U64Target) -> u64 {
+        let [low, high] = self.get_uint_target(&target);
+        ((high as u64) << 32) | low as u64
+    }
+}
+
+pub trait GeneratedValuesUInt<F: Field> {
+    fn set_uint_target<const N: usize>(&mut self, target: &UIntTarget<N>, limbs: [u32; N]);
+    fn set_u64_target(&mut self, target: U64Target, value: u64);
+}
+
+impl<F: Field> GeneratedValuesUInt<F> for GeneratedValues<F> {
+    fn set_uint_target<const N: usize>(&mut self, target: &UIntTarget<N>, limbs: [u32; N]) {
+        for (&t, l) in target.limbs.iter().zip(limbs) {
+            self.set_u32_target(t, l);
+        }
+    }
+
+    fn set_u64_target(&mut self, target: U64Target, value: u64) {
+        self.set_uint_target(&target, [value as u32, (value >> 32) as u32]);
+    }
+}
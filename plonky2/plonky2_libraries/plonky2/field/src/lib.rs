@@ -12,12 +12,16 @@ pub(crate) mod arch;
 
 pub mod batch_util;
 pub mod cosets;
+pub mod ed25519_base;
+pub mod ed25519_scalar;
 pub mod extension;
 pub mod fft;
 pub mod goldilocks_extensions;
 pub mod goldilocks_field;
 pub mod interpolation;
 pub mod ops;
+pub mod p256_base;
+pub mod p256_scalar;
 pub mod packable;
 pub mod packed;
 pub mod polynomial;